@@ -0,0 +1,244 @@
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+use syn::{
+    parse_macro_input, Attribute, Data, DeriveInput, Error, Expr, Fields, GenericArgument, LitStr,
+    Path, PathArguments, Result, Type,
+};
+
+/// Derive `crochet::Parse`, parsing each field in order using its own `Parse` implementation
+///
+/// Structs and variants accept `#[parse(prefix = "..", suffix = "..")]` to require literals around them, and enums try
+/// each variant in order. Fields accept `prefix` and `suffix` as well, `with = parser` to use a parser expression instead
+/// of the field type's implementation, and either `sep = ".."` or `repeat` to parse a container of elements. The error
+/// type defaults to `ParserError`, and can be set on the type with `#[parse(error = MyError)]`.
+#[proc_macro_derive(Parse, attributes(parse))]
+pub fn derive_parse(input: proc_macro::TokenStream) -> proc_macro::TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    derive(input)
+        .unwrap_or_else(Error::into_compile_error)
+        .into()
+}
+
+#[derive(Default)]
+struct Attrs {
+    error: Option<Path>,
+    prefix: Option<LitStr>,
+    suffix: Option<LitStr>,
+    with: Option<Expr>,
+    sep: Option<LitStr>,
+    repeat: bool,
+}
+
+impl Attrs {
+    fn parse(attrs: &[Attribute]) -> Result<Self> {
+        let mut parsed = Attrs::default();
+        for attr in attrs.iter().filter(|attr| attr.path().is_ident("parse")) {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("error") {
+                    parsed.error = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("prefix") {
+                    parsed.prefix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("suffix") {
+                    parsed.suffix = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("with") {
+                    parsed.with = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("sep") {
+                    parsed.sep = Some(meta.value()?.parse()?);
+                } else if meta.path.is_ident("repeat") {
+                    parsed.repeat = true;
+                } else {
+                    return Err(meta.error("unknown parse attribute"));
+                }
+                Ok(())
+            })?;
+        }
+        Ok(parsed)
+    }
+}
+
+fn derive(input: DeriveInput) -> Result<TokenStream> {
+    if !input.generics.params.is_empty() {
+        return Err(Error::new_spanned(
+            &input.generics,
+            "Parse cannot be derived for generic types",
+        ));
+    }
+    let attrs = Attrs::parse(&input.attrs)?;
+    let name = &input.ident;
+    let error = match &attrs.error {
+        Some(error) => quote!(#error),
+        None => quote!(::crochet::error::ParserError),
+    };
+    let body = match &input.data {
+        Data::Struct(data) => {
+            let parse = parse_fields(quote!(#name), &data.fields, &attrs, &error)?;
+            quote! {
+                fn parse_struct(input: &str) -> ::crochet::ParserResult<'_, #name, #error> {
+                    #parse
+                }
+                parse_struct(input)
+            }
+        }
+        Data::Enum(data) => {
+            if attrs.prefix.is_some() || attrs.suffix.is_some() {
+                return Err(Error::new_spanned(
+                    name,
+                    "prefix and suffix must be placed on enum variants",
+                ));
+            }
+            let mut variants = vec![];
+            for (i, variant) in data.variants.iter().enumerate() {
+                let ident = &variant.ident;
+                let func = format_ident!("parse_variant_{}", i);
+                let parse = parse_fields(
+                    quote!(#name::#ident),
+                    &variant.fields,
+                    &Attrs::parse(&variant.attrs)?,
+                    &error,
+                )?;
+                variants.push((
+                    func.clone(),
+                    quote! {
+                        fn #func(input: &str) -> ::crochet::ParserResult<'_, #name, #error> {
+                            #parse
+                        }
+                    },
+                ));
+            }
+            let Some(((last, _), rest)) = variants.split_last() else {
+                return Err(Error::new_spanned(
+                    name,
+                    "Parse cannot be derived for empty enums",
+                ));
+            };
+            let funcs = variants.iter().map(|(_, func)| func);
+            let attempts = rest.iter().map(|(func, _)| func);
+            quote! {
+                #(#funcs)*
+                #(
+                    let res = #attempts(input);
+                    if res.is_ok() {
+                        return res;
+                    }
+                )*
+                #last(input)
+            }
+        }
+        Data::Union(_) => {
+            return Err(Error::new_spanned(
+                name,
+                "Parse cannot be derived for unions",
+            ))
+        }
+    };
+    Ok(quote! {
+        impl<'a> ::crochet::Parse<'a> for #name {
+            type Error = #error;
+
+            fn parse(input: &'a str) -> ::crochet::ParserResult<'a, Self, #error> {
+                #body
+            }
+        }
+    })
+}
+
+/// Generate a sequence of statements parsing `fields` from `input`, then returning the constructed value
+fn parse_fields(
+    ctor: TokenStream,
+    fields: &Fields,
+    attrs: &Attrs,
+    error: &TokenStream,
+) -> Result<TokenStream> {
+    let mut steps = vec![];
+    steps.extend(attrs.prefix.as_ref().map(parse_literal));
+    let mut bindings = vec![];
+    for (i, field) in fields.iter().enumerate() {
+        let field_attrs = Attrs::parse(&field.attrs)?;
+        if field_attrs.error.is_some() {
+            return Err(Error::new_spanned(
+                field,
+                "error must be placed on the type",
+            ));
+        }
+        let binding = format_ident!("field_{}", i);
+        steps.extend(field_attrs.prefix.as_ref().map(parse_literal));
+        let parser = field_parser(&field.ty, &field_attrs, error)?;
+        steps.push(quote! {
+            let (#binding, s) = ::crochet::try_parse!(#parser);
+        });
+        steps.extend(field_attrs.suffix.as_ref().map(parse_literal));
+        bindings.push((field.ident.as_ref(), binding));
+    }
+    steps.extend(attrs.suffix.as_ref().map(parse_literal));
+    let value = match fields {
+        Fields::Named(_) => {
+            let fields = bindings
+                .iter()
+                .map(|(ident, binding)| quote!(#ident: #binding));
+            quote!(#ctor { #(#fields),* })
+        }
+        Fields::Unnamed(_) => {
+            let fields = bindings.iter().map(|(_, binding)| binding);
+            quote!(#ctor(#(#fields),*))
+        }
+        Fields::Unit => ctor,
+    };
+    Ok(quote! {
+        let s = input;
+        #(#steps)*
+        ::crochet::ParserResult::from_val(#value, s)
+    })
+}
+
+fn parse_literal(lit: &LitStr) -> TokenStream {
+    quote! {
+        let (_, s) = ::crochet::try_parse!(::crochet::literal(#lit, s));
+    }
+}
+
+/// Generate an expression parsing a single field from `s`
+fn field_parser(ty: &Type, attrs: &Attrs, error: &TokenStream) -> Result<TokenStream> {
+    let repeated = attrs.sep.is_some() || attrs.repeat;
+    let elem = match &attrs.with {
+        Some(with) => quote!(|s| ::crochet::Parser::parse(&(#with), s).err_into::<#error>()),
+        None => {
+            let ty = if repeated { element_type(ty)? } else { ty };
+            quote!(|s| <#ty as ::crochet::Parse>::parse(s).err_into::<#error>())
+        }
+    };
+    Ok(match (&attrs.sep, attrs.repeat) {
+        (Some(_), true) => {
+            return Err(Error::new_spanned(
+                ty,
+                "sep and repeat cannot be used together",
+            ))
+        }
+        (Some(sep), false) => quote! {
+            ::crochet::delimited_list::<_, _, #error, _, ()>(
+                #elem,
+                |s| ::crochet::literal(#sep, s).err_into::<#error>(),
+                s,
+            )
+            .map(|(elems, _)| elems)
+        },
+        (None, true) => quote!(::crochet::repeating_into::<_, #error, _>(#elem, .., s)),
+        (None, false) => quote!((#elem)(s)),
+    })
+}
+
+/// Get the element type of a container type such as `Vec<T>`
+fn element_type(ty: &Type) -> Result<&Type> {
+    if let Type::Path(path) = ty {
+        if let Some(PathArguments::AngleBracketed(args)) =
+            path.path.segments.last().map(|seg| &seg.arguments)
+        {
+            if let Some(GenericArgument::Type(elem)) = args.args.first() {
+                return Ok(elem);
+            }
+        }
+    }
+    Err(Error::new_spanned(
+        ty,
+        "repeated fields must be containers with an element type, or use `with`",
+    ))
+}
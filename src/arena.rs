@@ -0,0 +1,65 @@
+use std::ops::RangeBounds;
+
+use bumpalo::{collections, Bump};
+
+use crate::{error::ParserError, lower_bound, repeating_with, Parser, ParserResult};
+
+/// A collection allocated in an arena which parsed elements can be added to, like a [Container](crate::container::Container)
+/// which needs the arena to be created
+pub trait ArenaContainer<'bump, T> {
+    /// Create an empty collection in the arena, preallocating space for `hint` elements if it supports doing so
+    fn with_capacity_in(hint: usize, bump: &'bump Bump) -> Self;
+
+    fn add(&mut self, data: T);
+}
+
+impl<'bump, T> ArenaContainer<'bump, T> for collections::Vec<'bump, T> {
+    fn with_capacity_in(hint: usize, bump: &'bump Bump) -> Self {
+        collections::Vec::with_capacity_in(hint, bump)
+    }
+
+    fn add(&mut self, data: T) {
+        self.push(data);
+    }
+}
+
+impl<'bump> ArenaContainer<'bump, char> for collections::String<'bump> {
+    fn with_capacity_in(hint: usize, bump: &'bump Bump) -> Self {
+        collections::String::with_capacity_in(hint, bump)
+    }
+
+    fn add(&mut self, data: char) {
+        self.push(data);
+    }
+}
+
+impl<'a, 'bump> ArenaContainer<'bump, &'a str> for collections::String<'bump> {
+    fn with_capacity_in(hint: usize, bump: &'bump Bump) -> Self {
+        collections::String::with_capacity_in(hint, bump)
+    }
+
+    fn add(&mut self, data: &'a str) {
+        self.push_str(data);
+    }
+}
+
+/// Run a parser and move its value into the arena, for building tree nodes without a [Box] for each
+pub fn alloc_in<'a, 'bump, T, E>(
+    bump: &'bump Bump,
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, &'bump mut T, E> {
+    parser.parse(input).map(|value| &mut *bump.alloc(value))
+}
+
+/// Parse a number of elements within a range into a collection allocated in the arena, like
+/// [repeating_into](crate::repeating_into)
+pub fn repeating_in<'a, 'bump, T, E: From<ParserError>, C: ArenaContainer<'bump, T>>(
+    bump: &'bump Bump,
+    parser: impl Parser<'a, T, E>,
+    bounds: impl RangeBounds<usize> + 'a,
+    source: &'a str,
+) -> ParserResult<'a, C, E> {
+    let elems = C::with_capacity_in(lower_bound(bounds.start_bound()), bump);
+    repeating_with(elems, C::add, parser, bounds, source)
+}
@@ -0,0 +1,68 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, ParserResult};
+
+/// Parse a single ASCII byte matching a predicate, without decoding UTF-8
+pub fn matching_byte<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    filter: impl Fn(u8) -> bool,
+    input: &'a str,
+) -> ParserResult<'a, u8, ParserError> {
+    match input.as_bytes().first() {
+        Some(&b) if b.is_ascii() && filter(b) => ParserResult::from_val(b, &input[1..]),
+        _ => ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input),
+    }
+}
+
+/// Consume ASCII bytes as long as they match a predicate, without decoding UTF-8
+pub fn take_while<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    filter: impl Fn(u8) -> bool,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let len = input
+        .bytes()
+        .position(|b| !b.is_ascii() || !filter(b))
+        .unwrap_or(input.len());
+    if len == 0 {
+        ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input)
+    } else {
+        let (parsed, rest) = input.split_at(len);
+        ParserResult::from_val(parsed, rest)
+    }
+}
+
+/// Parse a single ASCII digit
+pub fn digit(input: &str) -> ParserResult<'_, u8, ParserError> {
+    matching_byte("digit", |b| b.is_ascii_digit(), input)
+}
+
+/// Parse a single ASCII letter
+pub fn alpha(input: &str) -> ParserResult<'_, u8, ParserError> {
+    matching_byte("letter", |b| b.is_ascii_alphabetic(), input)
+}
+
+/// Parse a single ASCII letter or digit
+pub fn alphanumeric(input: &str) -> ParserResult<'_, u8, ParserError> {
+    matching_byte("letter or digit", |b| b.is_ascii_alphanumeric(), input)
+}
+
+/// Consume at least one ASCII digit
+pub fn digit1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("digits", |b| b.is_ascii_digit(), input)
+}
+
+/// Consume at least one ASCII hexadecimal digit
+pub fn hex_digit1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("hex digits", |b| b.is_ascii_hexdigit(), input)
+}
+
+/// Consume at least one ASCII letter
+pub fn alpha1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("letters", |b| b.is_ascii_alphabetic(), input)
+}
+
+/// Consume at least one ASCII letter or digit
+pub fn alphanumeric1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("letters or digits", |b| b.is_ascii_alphanumeric(), input)
+}
@@ -0,0 +1,86 @@
+use crate::{error::ParserError, Parser, ParserResult};
+
+/// Parse a region enclosed in delimiters such as `(` and `)`, which may contain nested pairs, and return the slice
+/// between the outer delimiters
+///
+/// An unclosed region is reported at its opening delimiter.
+pub fn balanced(open: char, close: char, input: &str) -> ParserResult<'_, &str, ParserError> {
+    balanced_with(
+        open,
+        close,
+        |s| ParserResult::<(), ()>::from_err((), s),
+        input,
+    )
+}
+
+/// Parse a balanced region like [balanced], skipping over anything `skip` parses, such as string literals which may
+/// contain unmatched delimiters
+pub fn balanced_with<'a, T, E>(
+    open: char,
+    close: char,
+    skip: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let Some(body) = input.strip_prefix(open) else {
+        return ParserResult::from_err(
+            ParserError::ExpectedToken(format!("'{open}'").into()),
+            input,
+        );
+    };
+    let mut depth = 1;
+    let mut s = body;
+    loop {
+        let skipped = skip.parse(s);
+        if skipped.is_ok() && skipped.source.len() < s.len() {
+            s = skipped.source;
+            continue;
+        }
+        let Some(c) = s.chars().next() else {
+            return ParserResult::from_err(
+                ParserError::ExpectedToken(format!("'{close}'").into()),
+                input,
+            );
+        };
+        let end = body.len() - s.len();
+        s = &s[c.len_utf8()..];
+        if c == close {
+            depth -= 1;
+            if depth == 0 {
+                return ParserResult::from_val(&body[..end], s);
+            }
+        } else if c == open {
+            depth += 1;
+        }
+    }
+}
+
+/// Consume input up to, but not including, a closing delimiter which isn't matched by an earlier opening delimiter,
+/// skipping any character preceded by `escape`, such as `\)`
+///
+/// If the input ends first, the error is reported at the innermost unmatched opening delimiter, or at the end of the
+/// input if there is none.
+pub fn take_until_balanced(
+    open: char,
+    close: char,
+    escape: char,
+    input: &str,
+) -> ParserResult<'_, &str, ParserError> {
+    let mut openers = vec![];
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == escape {
+            chars.next();
+        } else if c == close {
+            if openers.pop().is_none() {
+                return ParserResult::from_val(&input[..i], &input[i..]);
+            }
+        } else if c == open {
+            openers.push(i);
+        }
+    }
+    let at = openers.last().map_or(input.len(), |&i| i);
+    ParserResult::from_err(
+        ParserError::ExpectedToken(format!("'{close}'").into()),
+        &input[at..],
+    )
+}
@@ -0,0 +1,312 @@
+use crate::{error::ParserError, parser_result::result_methods, Needed, ParserResultType};
+
+/// Return early from a function returning a [BinaryResult] if a result didn't succeed, like
+/// [try_parse!](crate::try_parse!) does for text
+macro_rules! try_binary {
+    ($res:expr) => {
+        match $crate::binary::BinaryResult::into_result($res) {
+            Ok(parsed) => parsed,
+            Err(err) => return err.err_into(),
+        }
+    };
+}
+
+pub(crate) use try_binary;
+
+/// An output of a [BinaryParser], containing the byte slice to resume parsing from
+#[must_use]
+pub struct BinaryResult<'a, T, E> {
+    pub source: &'a [u8],
+    pub typ: ParserResultType<T, E>,
+}
+
+result_methods!(BinaryResult, &'a [u8]);
+
+/// A parser over bytes rather than text, which reports [Incomplete](ParserResultType::Incomplete) when the input ends
+/// before the value it is reading, so that it can be retried once more input has arrived
+pub trait BinaryParser<'a, T, E> {
+    /// Parse a value from the input
+    fn parse(&self, input: &'a [u8]) -> BinaryResult<'a, T, E>;
+
+    /// Map the output type of this parser using a mapping function
+    fn map<V>(&self, f: impl Fn(T) -> V) -> impl BinaryParser<'a, V, E>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).map(&f)
+    }
+}
+
+impl<'a, T, E, F> BinaryParser<'a, T, E> for F
+where
+    F: Fn(&'a [u8]) -> BinaryResult<'a, T, E>,
+{
+    fn parse(&self, input: &'a [u8]) -> BinaryResult<'a, T, E> {
+        self(input)
+    }
+}
+
+/// Take exactly `n` bytes
+pub fn take<E>(n: usize, input: &[u8]) -> BinaryResult<'_, &[u8], E> {
+    if input.len() < n {
+        return BinaryResult::incomplete_needing(Needed::new(n - input.len()), input);
+    }
+    let (bytes, rest) = input.split_at(n);
+    BinaryResult::from_val(bytes, rest)
+}
+
+/// Match an exact sequence of bytes, such as a magic number
+pub fn tag<'a, E: From<ParserError>>(
+    bytes: &'static [u8],
+    input: &'a [u8],
+) -> BinaryResult<'a, &'a [u8], E> {
+    let len = bytes.len().min(input.len());
+    if input[..len] != bytes[..len] {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken(format!("{bytes:02X?}").into()).into(),
+            input,
+        );
+    }
+    take(bytes.len(), input)
+}
+
+macro_rules! int_parsers {
+    ($($t:ident: $be:ident, $le:ident;)*) => {
+        $(
+            #[doc = concat!("Parse a big-endian `", stringify!($t), "`")]
+            pub fn $be<E>(input: &[u8]) -> BinaryResult<'_, $t, E> {
+                take(size_of::<$t>(), input).map(|b| $t::from_be_bytes(b.try_into().unwrap()))
+            }
+
+            #[doc = concat!("Parse a little-endian `", stringify!($t), "`")]
+            pub fn $le<E>(input: &[u8]) -> BinaryResult<'_, $t, E> {
+                take(size_of::<$t>(), input).map(|b| $t::from_le_bytes(b.try_into().unwrap()))
+            }
+        )*
+    };
+}
+
+int_parsers! {
+    u16: be_u16, le_u16;
+    u32: be_u32, le_u32;
+    u64: be_u64, le_u64;
+    i16: be_i16, le_i16;
+    i32: be_i32, le_i32;
+    i64: be_i64, le_i64;
+    f32: be_f32, le_f32;
+    f64: be_f64, le_f64;
+}
+
+/// Widen the bits of an IEEE 754 half precision float to an `f32`, which represents every half precision value exactly
+pub fn f16_to_f32(bits: u16) -> f32 {
+    let exp = i32::from((bits >> 10) & 0x1F);
+    let mantissa = f32::from(bits & 0x3FF);
+    let value = match exp {
+        0 => mantissa * 2f32.powi(-24),
+        31 if mantissa == 0.0 => f32::INFINITY,
+        31 => f32::NAN,
+        _ => (mantissa + 1024.0) * 2f32.powi(exp - 25),
+    };
+    if bits & 0x8000 != 0 {
+        -value
+    } else {
+        value
+    }
+}
+
+/// Parse a big-endian half precision float, widened to an `f32`
+pub fn be_f16<E>(input: &[u8]) -> BinaryResult<'_, f32, E> {
+    be_u16(input).map(f16_to_f32)
+}
+
+/// Parse a little-endian half precision float, widened to an `f32`
+pub fn le_f16<E>(input: &[u8]) -> BinaryResult<'_, f32, E> {
+    le_u16(input).map(f16_to_f32)
+}
+
+/// Parse a single byte
+pub fn u8<E>(input: &[u8]) -> BinaryResult<'_, u8, E> {
+    take(1, input).map(|b| b[0])
+}
+
+/// Parse a length field and then take that many bytes, which is incomplete until the whole body has arrived
+pub fn length_take<'a, N: TryInto<usize>, E: From<ParserError>>(
+    len: impl BinaryParser<'a, N, E>,
+    input: &'a [u8],
+) -> BinaryResult<'a, &'a [u8], E> {
+    let (n, s) = try_binary!(len.parse(input));
+    let Ok(n) = n.try_into() else {
+        return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input);
+    };
+    if s.len() < n {
+        return BinaryResult::incomplete_needing(Needed::new(n - s.len()), input);
+    }
+    take(n, s)
+}
+
+/// Parse a length field and then parse a value from exactly that many bytes, treating the body parser running out of
+/// bytes as an error since the frame has fully arrived
+pub fn length_value<'a, N: TryInto<usize>, T, E: From<ParserError>>(
+    len: impl BinaryParser<'a, N, E>,
+    body: impl BinaryParser<'a, T, E>,
+    input: &'a [u8],
+) -> BinaryResult<'a, T, E> {
+    let (frame, rest) = try_binary!(length_take(len, input));
+    let res = body.parse(frame);
+    match res.typ {
+        ParserResultType::Ok(val) => BinaryResult::from_val(val, rest),
+        ParserResultType::Err(err) => BinaryResult::from_err(err, res.source),
+        ParserResultType::Incomplete(_) => {
+            BinaryResult::from_err(ParserError::UnexpectedEndOfFile.into(), res.source)
+        }
+    }
+}
+
+/// Read the groups of an LEB128 varint into a value of at most `bits` bits, returning it along with the number of
+/// bits read
+fn leb128<'a, E: From<ParserError>>(
+    bits: u32,
+    signed: bool,
+    input: &'a [u8],
+) -> BinaryResult<'a, (u64, u32), E> {
+    let max_len = bits.div_ceil(7) as usize;
+    let mut value = 0u64;
+    for (i, &byte) in input.iter().enumerate().take(max_len) {
+        let shift = i as u32 * 7;
+        let group = u64::from(byte & 0x7F);
+        // The bits of the last group which don't fit must be zero, or copies of the sign bit for a signed value
+        if shift + 7 > bits {
+            let unused = bits - shift - u32::from(signed);
+            if group >> unused != 0 && !(signed && group >> unused == 0x7F >> unused) {
+                return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input);
+            }
+        }
+        value |= group << shift;
+        if byte & 0x80 == 0 {
+            return BinaryResult::from_val((value, shift + 7), &input[i + 1..]);
+        }
+    }
+    if input.len() < max_len {
+        return BinaryResult::incomplete_needing(Needed::new(1), input);
+    }
+    BinaryResult::from_err(
+        ParserError::ExpectedToken(format!("varint of at most {max_len} bytes").into()).into(),
+        input,
+    )
+}
+
+macro_rules! leb128_parsers {
+    ($($u:ident, $i:ident: $uleb:ident, $sleb:ident, $zigzag:ident;)*) => {
+        $(
+            #[doc = concat!("Parse an unsigned LEB128 varint into a `", stringify!($u), "`, as used by protobuf, WASM and DWARF")]
+            pub fn $uleb<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, $u, E> {
+                leb128($u::BITS, false, input).map(|(value, _)| value as $u)
+            }
+
+            #[doc = concat!("Parse a signed LEB128 varint into an `", stringify!($i), "`, whose last group is sign-extended, as used by WASM and DWARF")]
+            pub fn $sleb<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, $i, E> {
+                leb128($i::BITS, true, input).map(|(value, len)| {
+                    let shift = 64u32.saturating_sub(len);
+                    ((value << shift) as i64 >> shift) as $i
+                })
+            }
+
+            #[doc = concat!("Parse a zigzag-encoded varint into an `", stringify!($i), "`, as used by protobuf's signed types")]
+            pub fn $zigzag<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, $i, E> {
+                $uleb(input).map(|n| (n >> 1) as $i ^ -((n & 1) as $i))
+            }
+        )*
+    };
+}
+
+leb128_parsers! {
+    u32, i32: uleb128_u32, sleb128_i32, zigzag_i32;
+    u64, i64: uleb128_u64, sleb128_i64, zigzag_i64;
+}
+
+/// Parse a frame followed by a checksum field, checking the field against the checksum of the bytes the frame parser
+/// consumed, and failing with [ChecksumMismatch](ParserError::ChecksumMismatch) at the start of the frame if they differ
+///
+/// The checksum can be any function of the bytes, such as [crc32], [crc16_ccitt], [crc16_modbus] or [xor8].
+pub fn checksummed<'a, T, C: PartialEq, E: From<ParserError>>(
+    frame: impl BinaryParser<'a, T, E>,
+    checksum: impl Fn(&[u8]) -> C,
+    field: impl BinaryParser<'a, C, E>,
+    input: &'a [u8],
+) -> BinaryResult<'a, T, E> {
+    let (value, s) = try_binary!(frame.parse(input));
+    let computed = checksum(&input[..input.len() - s.len()]);
+    let (expected, s) = try_binary!(field.parse(s));
+    if computed != expected {
+        return BinaryResult::from_err(ParserError::ChecksumMismatch.into(), input);
+    }
+    BinaryResult::from_val(value, s)
+}
+
+/// Compute the CRC-32 used by Ethernet, zlib and PNG
+pub fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &byte in bytes {
+        crc ^= u32::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xEDB8_8320
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+/// Compute the CRC-16 with polynomial `0x1021` and initial value `0xFFFF`, known as CRC-16/CCITT-FALSE
+pub fn crc16_ccitt(bytes: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for &byte in bytes {
+        crc ^= u16::from(byte) << 8;
+        for _ in 0..8 {
+            crc = if crc & 0x8000 != 0 {
+                (crc << 1) ^ 0x1021
+            } else {
+                crc << 1
+            };
+        }
+    }
+    crc
+}
+
+/// Compute the CRC-16 used by Modbus RTU, which is sent little-endian
+pub fn crc16_modbus(bytes: &[u8]) -> u16 {
+    let mut crc = 0xFFFFu16;
+    for &byte in bytes {
+        crc ^= u16::from(byte);
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                (crc >> 1) ^ 0xA001
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    crc
+}
+
+/// Compute the XOR of every byte, as used by NMEA sentences and many serial protocols
+pub fn xor8(bytes: &[u8]) -> u8 {
+    bytes.iter().fold(0, |acc, byte| acc ^ byte)
+}
+
+/// Run a byte parser on input which is known to be final, turning an incomplete result into an
+/// [UnexpectedEndOfFile](ParserError::UnexpectedEndOfFile) error, like [complete](crate::complete) does for text
+pub fn complete<'a, T, E: From<ParserError>>(
+    parser: impl BinaryParser<'a, T, E>,
+    input: &'a [u8],
+) -> BinaryResult<'a, T, E> {
+    let res = parser.parse(input);
+    match res.typ {
+        ParserResultType::Incomplete(_) => {
+            BinaryResult::from_err(ParserError::UnexpectedEndOfFile.into(), res.source)
+        }
+        _ => res,
+    }
+}
@@ -0,0 +1,167 @@
+use std::fmt::Debug;
+
+use crate::{
+    binary::{BinaryParser, BinaryResult},
+    error::ParserError,
+    parser_result::result_methods,
+    Needed, ParserResultType,
+};
+
+macro_rules! try_bits {
+    ($res:expr) => {
+        match $crate::bits::BitResult::into_result($res) {
+            Ok(parsed) => parsed,
+            Err(err) => return err.err_into(),
+        }
+    };
+}
+
+/// A position within a byte slice at bit granularity, where bits are read from the most significant bit of each byte
+/// first
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Bits<'a> {
+    pub bytes: &'a [u8],
+    /// The number of bits of the first byte which have already been read, from 0 to 7
+    pub offset: u32,
+}
+
+impl<'a> Bits<'a> {
+    /// Start reading bits from the first bit of a byte slice
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { bytes, offset: 0 }
+    }
+
+    /// Returns whether the position is on a byte boundary
+    pub fn is_aligned(&self) -> bool {
+        self.offset == 0
+    }
+
+    /// Get the number of bits left to read
+    pub fn remaining(&self) -> usize {
+        self.bytes.len() * 8 - self.offset as usize
+    }
+
+    /// Move forward by a number of bits, which must not be more than are remaining
+    fn advance(self, n: usize) -> Self {
+        let pos = self.offset as usize + n;
+        Self {
+            bytes: &self.bytes[pos / 8..],
+            offset: (pos % 8) as u32,
+        }
+    }
+}
+
+/// An output of a [BitParser], containing the bit position to resume parsing from
+#[must_use]
+pub struct BitResult<'a, T, E> {
+    pub source: Bits<'a>,
+    pub typ: ParserResultType<T, E>,
+}
+
+result_methods!(BitResult, Bits<'a>);
+
+/// A parser reading individual bits, such as the flags and sub-byte fields of a packet header
+pub trait BitParser<'a, T, E> {
+    /// Parse a value from the input
+    fn parse(&self, input: Bits<'a>) -> BitResult<'a, T, E>;
+
+    /// Map the output type of this parser using a mapping function
+    fn map<V>(&self, f: impl Fn(T) -> V) -> impl BitParser<'a, V, E>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).map(&f)
+    }
+}
+
+impl<'a, T, E, F> BitParser<'a, T, E> for F
+where
+    F: Fn(Bits<'a>) -> BitResult<'a, T, E>,
+{
+    fn parse(&self, input: Bits<'a>) -> BitResult<'a, T, E> {
+        self(input)
+    }
+}
+
+/// Read `n` bits as an unsigned number, where `n` is at most 64
+pub fn take_bits<E: From<ParserError>>(n: u32, input: Bits<'_>) -> BitResult<'_, u64, E> {
+    if n > 64 {
+        return BitResult::from_err(ParserError::IntegerOverflow.into(), input);
+    }
+    if input.remaining() < n as usize {
+        let bits = n as usize - input.remaining();
+        return BitResult::incomplete_needing(Needed::new(bits.div_ceil(8)), input);
+    }
+    let mut value = 0u64;
+    let mut s = input;
+    let mut left = n;
+    while left > 0 {
+        let available = 8 - s.offset;
+        let count = available.min(left);
+        let bits = (s.bytes[0] >> (available - count)) & (0xFF >> (8 - count));
+        value = (value << count) | u64::from(bits);
+        left -= count;
+        s = s.advance(count as usize);
+    }
+    BitResult::from_val(value, s)
+}
+
+/// Read a single bit as a flag
+pub fn flag<E: From<ParserError>>(input: Bits<'_>) -> BitResult<'_, bool, E> {
+    take_bits(1, input).map(|bit| bit == 1)
+}
+
+/// Skip to the start of the next byte, unless already on a byte boundary
+pub fn align<E>(input: Bits<'_>) -> BitResult<'_, (), E> {
+    if input.is_aligned() {
+        return BitResult::from_val((), input);
+    }
+    BitResult::from_val((), input.advance(8 - input.offset as usize))
+}
+
+/// Run a byte parser from the current position, which must be on a byte boundary
+pub fn bytes<'a, T, E: From<ParserError>>(
+    parser: impl BinaryParser<'a, T, E>,
+    input: Bits<'a>,
+) -> BitResult<'a, T, E> {
+    if !input.is_aligned() {
+        return BitResult::from_err(
+            ParserError::ExpectedToken("byte boundary".into()).into(),
+            input,
+        );
+    }
+    let res = parser.parse(input.bytes);
+    BitResult {
+        source: Bits::new(res.source),
+        typ: res.typ,
+    }
+}
+
+/// Run a bit parser over byte input, then skip any bits left over in the last byte it read from
+pub fn bits<'a, T, E>(parser: impl BitParser<'a, T, E>, input: &'a [u8]) -> BinaryResult<'a, T, E> {
+    let res = parser.parse(Bits::new(input));
+    let source = align::<E>(res.source).source;
+    let source = match res.typ {
+        ParserResultType::Ok(_) => source.bytes,
+        _ => res.source.bytes,
+    };
+    BinaryResult {
+        source,
+        typ: res.typ,
+    }
+}
+
+/// Parse a sequence of fields of the given widths at once, such as the `4`, `4` and `8` bit fields of an IPv4 header
+pub fn fields<'a, const N: usize, E: From<ParserError>>(
+    widths: [u32; N],
+    input: Bits<'a>,
+) -> BitResult<'a, [u64; N], E> {
+    let mut values = [0; N];
+    let mut s = input;
+    for (value, width) in values.iter_mut().zip(widths) {
+        let (v, rest) = try_bits!(take_bits::<E>(width, s));
+        *value = v;
+        s = rest;
+    }
+    BitResult::from_val(values, s)
+}
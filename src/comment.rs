@@ -0,0 +1,55 @@
+use crate::{error::ParserError, literal, not_line_ending, try_parse, ParserResult};
+
+/// Parse a comment from a prefix such as `//`, `#` or `--` up to the end of the line, returning its body
+pub fn line_comment<'a>(
+    prefix: &'static str,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let (_, s) = try_parse!(literal(prefix, input));
+    not_line_ending(s)
+}
+
+/// Parse a block comment such as `/* ... */`, ending at the first closing delimiter, and return its body
+pub fn block_comment<'a>(
+    open: &'static str,
+    close: &'static str,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let (_, s) = try_parse!(literal(open, input));
+    match s.find(close) {
+        Some(end) => ParserResult::from_val(&s[..end], &s[end + close.len()..]),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral(close), input),
+    }
+}
+
+/// Parse a block comment which may contain nested comments, such as `/* a /* b */ c */`, and return its body
+///
+/// An unterminated comment is reported at its opening delimiter.
+pub fn nested_block_comment<'a>(
+    open: &'static str,
+    close: &'static str,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let (_, body) = try_parse!(literal(open, input));
+    let mut depth = 1;
+    let mut pos = 0;
+    while depth > 0 {
+        let rest = &body[pos..];
+        let next_open = rest.find(open);
+        let Some(next_close) = rest.find(close) else {
+            return ParserResult::from_err(ParserError::ExpectedLiteral(close), input);
+        };
+        match next_open {
+            Some(o) if o < next_close => {
+                depth += 1;
+                pos += o + open.len();
+            }
+            _ => {
+                depth -= 1;
+                pos += next_close + close.len();
+            }
+        }
+    }
+    let end = pos - close.len();
+    ParserResult::from_val(&body[..end], &body[pos..])
+}
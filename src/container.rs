@@ -1,21 +1,198 @@
-use std::collections::VecDeque;
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque},
+    hash::Hash,
+};
 
 pub trait Container<T>: Default {
     fn add(&mut self, data: T);
+
+    /// Create an empty container, preallocating space for `hint` elements if it supports doing so
+    fn with_capacity(hint: usize) -> Self {
+        let mut container = Self::default();
+        container.reserve(hint);
+        container
+    }
+
+    /// Preallocate space for `additional` more elements if the container supports doing so
+    fn reserve(&mut self, _additional: usize) {}
 }
 
 impl<T> Container<T> for Vec<T> {
     fn add(&mut self, data: T) {
         self.push(data);
     }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
+/// Collects elements in order at the back of the queue
 impl<T> Container<T> for VecDeque<T> {
     fn add(&mut self, data: T) {
         self.push_back(data);
     }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
 }
 
+/// Discards every element, for repetitions which only need to consume their input
 impl<T> Container<T> for () {
     fn add(&mut self, _data: T) {}
 }
+
+#[cfg(feature = "smallvec")]
+impl<A: smallvec::Array> Container<A::Item> for smallvec::SmallVec<A> {
+    fn add(&mut self, data: A::Item) {
+        self.push(data);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+/// A container which only counts how many elements were added, without storing them
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct Count(pub usize);
+
+impl<T> Container<T> for Count {
+    fn add(&mut self, _data: T) {
+        self.0 += 1;
+    }
+}
+
+/// A container which keeps only the first element added to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct First<T>(pub Option<T>);
+
+impl<T> Default for First<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Container<T> for First<T> {
+    fn add(&mut self, data: T) {
+        if self.0.is_none() {
+            self.0 = Some(data);
+        }
+    }
+}
+
+/// A container which keeps only the last element added to it
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Last<T>(pub Option<T>);
+
+impl<T> Default for Last<T> {
+    fn default() -> Self {
+        Self(None)
+    }
+}
+
+impl<T> Container<T> for Last<T> {
+    fn add(&mut self, data: T) {
+        self.0 = Some(data);
+    }
+}
+
+impl<K: Eq + Hash, V> Container<(K, V)> for HashMap<K, V> {
+    fn add(&mut self, (key, value): (K, V)) {
+        self.insert(key, value);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<K: Ord, V> Container<(K, V)> for BTreeMap<K, V> {
+    fn add(&mut self, (key, value): (K, V)) {
+        self.insert(key, value);
+    }
+}
+
+impl Container<char> for String {
+    fn add(&mut self, data: char) {
+        self.push(data);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<'a> Container<&'a str> for String {
+    fn add(&mut self, data: &'a str) {
+        self.push_str(data);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<T: Eq + Hash> Container<T> for HashSet<T> {
+    fn add(&mut self, data: T) {
+        self.insert(data);
+    }
+
+    fn reserve(&mut self, additional: usize) {
+        self.reserve(additional);
+    }
+}
+
+impl<T: Ord> Container<T> for BTreeSet<T> {
+    fn add(&mut self, data: T) {
+        self.insert(data);
+    }
+}
+
+/// A set which keeps any duplicate elements it is given, so they can be reported instead of silently dropped
+#[derive(Debug)]
+pub struct Unique<S, T> {
+    pub set: S,
+    pub duplicates: Vec<T>,
+}
+
+impl<S: Default, T> Default for Unique<S, T> {
+    fn default() -> Self {
+        Self {
+            set: S::default(),
+            duplicates: vec![],
+        }
+    }
+}
+
+impl<S, T> Unique<S, T> {
+    /// Get the set, or the duplicate elements if there were any
+    pub fn into_result(self) -> Result<S, Vec<T>> {
+        if self.duplicates.is_empty() {
+            Ok(self.set)
+        } else {
+            Err(self.duplicates)
+        }
+    }
+}
+
+impl<T: Eq + Hash> Container<T> for Unique<HashSet<T>, T> {
+    fn add(&mut self, data: T) {
+        if self.set.contains(&data) {
+            self.duplicates.push(data);
+        } else {
+            self.set.insert(data);
+        }
+    }
+}
+
+impl<T: Ord> Container<T> for Unique<BTreeSet<T>, T> {
+    fn add(&mut self, data: T) {
+        if self.set.contains(&data) {
+            self.duplicates.push(data);
+        } else {
+            self.set.insert(data);
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use std::{
+    cell::{Cell, RefCell},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    time::Instant,
+};
+
+use crate::{error::ParserError, Parser, ParserResult};
+
+thread_local! {
+    /// The deadline of the active [with_deadline] call on this thread, if any
+    static DEADLINE: Cell<Option<Instant>> = const { Cell::new(None) };
+    /// The tokens of the active [with_cancellation] calls on this thread, innermost last
+    static TOKENS: RefCell<Vec<CancellationToken>> = const { RefCell::new(Vec::new()) };
+    /// Whether a checked parser has been refused since the active call started
+    static ABORTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// A flag which can be set from another thread to stop a parse running under [with_cancellation]
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(Arc<AtomicBool>);
+
+impl CancellationToken {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Make every checked parser running under this token fail
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// The state of the calls enclosing an active [with_deadline] or [with_cancellation] call, which is restored when it
+/// ends, even by unwinding
+struct Scope {
+    deadline: Option<Instant>,
+    tokens: usize,
+    outer_aborted: bool,
+}
+
+impl Scope {
+    /// Save the state of the enclosing calls and reset the abort flag
+    fn enter() -> Self {
+        Self {
+            deadline: DEADLINE.with(Cell::get),
+            tokens: TOKENS.with(|c| c.borrow().len()),
+            outer_aborted: ABORTED.with(|c| c.replace(false)),
+        }
+    }
+
+    /// Run a function in this scope, returning `None` if a checked parser was refused while it ran
+    fn run<R>(self, f: impl FnOnce() -> R) -> Option<R> {
+        let res = f();
+        let aborted = ABORTED.with(Cell::get);
+        drop(self);
+        (!aborted).then_some(res)
+    }
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        DEADLINE.with(|c| c.set(self.deadline));
+        TOKENS.with(|c| c.borrow_mut().truncate(self.tokens));
+        ABORTED.with(|c| c.set(self.outer_aborted));
+    }
+}
+
+/// Run a function on this thread until a deadline, returning `None` if it passed
+///
+/// Once the deadline passes every checked parser fails with [ParserError::DeadlineExceeded], and the result is
+/// discarded even if an alternative which isn't checked succeeded afterwards, like [with_fuel](crate::fuel::with_fuel)
+/// does when fuel runs out. An enclosing deadline which is earlier still applies.
+pub fn with_deadline<R>(deadline: Instant, f: impl FnOnce() -> R) -> Option<R> {
+    let scope = Scope::enter();
+    let deadline = scope.deadline.map_or(deadline, |outer| outer.min(deadline));
+    DEADLINE.with(|c| c.set(Some(deadline)));
+    scope.run(f)
+}
+
+/// Run a function on this thread until a token is cancelled, returning `None` if it was, see [with_deadline]
+///
+/// The tokens of enclosing calls still apply, so cancelling any of them stops the parse.
+pub fn with_cancellation<R>(token: &CancellationToken, f: impl FnOnce() -> R) -> Option<R> {
+    let scope = Scope::enter();
+    TOKENS.with(|c| c.borrow_mut().push(token.clone()));
+    scope.run(f)
+}
+
+/// Run a parser, failing with [ParserError::DeadlineExceeded] or [ParserError::Cancelled] instead if the active
+/// [with_deadline] or [with_cancellation] call says to stop
+pub fn checked<'a, T, E: From<ParserError>>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let err = if TOKENS.with(|c| c.borrow().iter().any(CancellationToken::is_cancelled)) {
+        Some(ParserError::Cancelled)
+    } else if DEADLINE.with(|c| c.get().is_some_and(|d| Instant::now() >= d)) {
+        Some(ParserError::DeadlineExceeded)
+    } else {
+        None
+    };
+    if let Some(err) = err {
+        ABORTED.with(|c| c.set(true));
+        return ParserResult::from_err(err.into(), input);
+    }
+    parser.parse(input)
+}
@@ -0,0 +1,199 @@
+use crate::error::ParserError;
+
+/// A position in a document as the Language Server Protocol specifies it, with a line and a character offset within it
+/// counted in UTF-16 code units, both starting from 0
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+impl Position {
+    /// Find the position of a byte offset in a document, where lines are split on `\n`, `\r\n` or `\r`
+    pub fn at(document: &str, offset: usize) -> Self {
+        let before = &document[..offset];
+        let mut line = 0;
+        let mut line_start = 0;
+        let mut chars = before.char_indices().peekable();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '\r' if chars.peek().is_some_and(|(_, c)| *c == '\n') => {}
+                // A `\r` ending the text may be followed by `\n`, but it still ends a line either way
+                '\r' | '\n' => {
+                    line += 1;
+                    line_start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        let character = before[line_start..].encode_utf16().count();
+        Self {
+            line,
+            character: character as u32,
+        }
+    }
+}
+
+/// A range between two positions in a document, excluding the end
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Range {
+    pub start: Position,
+    pub end: Position,
+}
+
+impl Range {
+    /// Find the range of a byte range in a document
+    pub fn at(document: &str, range: std::ops::Range<usize>) -> Self {
+        Self {
+            start: Position::at(document, range.start),
+            end: Position::at(document, range.end),
+        }
+    }
+}
+
+/// How serious a diagnostic is, numbered as in the Language Server Protocol
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Severity {
+    Error = 1,
+    Warning = 2,
+    Information = 3,
+    Hint = 4,
+}
+
+/// Another place in the same document which explains a diagnostic, such as where an unclosed bracket was opened
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RelatedInformation {
+    pub range: Range,
+    pub message: String,
+}
+
+/// A diagnostic for an editor or CI annotation, with the same fields as the Language Server Protocol's `Diagnostic`
+///
+/// Related information holds only a range, since the document's URI is up to the language server. With the `serde`
+/// feature it serializes with the field names and numbering the protocol uses.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub range: Range,
+    pub severity: Severity,
+    /// The stable [code](ParserError::code) of the error, if there is one
+    pub code: Option<&'static str>,
+    /// The name of the tool reporting the diagnostic
+    pub source: Option<String>,
+    pub message: String,
+    pub related_information: Vec<RelatedInformation>,
+}
+
+impl Diagnostic {
+    /// Create an error diagnostic at the input remaining where a parser failed, covering the next character if there
+    /// is one
+    pub fn new(document: &str, remaining: &str, message: impl Into<String>) -> Self {
+        let start = document.len() - remaining.len();
+        let end = start + remaining.chars().next().map_or(0, char::len_utf8);
+        Self {
+            range: Range::at(document, start..end),
+            severity: Severity::Error,
+            code: None,
+            source: None,
+            message: message.into(),
+            related_information: vec![],
+        }
+    }
+
+    /// Create an error diagnostic for a [ParserError] at the input remaining where it occurred, with its message and
+    /// code
+    pub fn from_error(document: &str, remaining: &str, err: &ParserError) -> Self {
+        Self {
+            code: Some(err.code().as_str()),
+            ..Self::new(document, remaining, err.to_string())
+        }
+    }
+
+    /// Set the severity, which is [Error](Severity::Error) by default
+    pub fn severity(mut self, severity: Severity) -> Self {
+        self.severity = severity;
+        self
+    }
+
+    /// Set the name of the tool reporting the diagnostic
+    pub fn source(mut self, source: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self
+    }
+
+    /// Add related information about a byte range of the document
+    pub fn related(
+        mut self,
+        document: &str,
+        range: std::ops::Range<usize>,
+        message: impl Into<String>,
+    ) -> Self {
+        self.related_information.push(RelatedInformation {
+            range: Range::at(document, range),
+            message: message.into(),
+        });
+        self
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+    use super::{Diagnostic, Position, Range, RelatedInformation, Severity};
+
+    impl Serialize for Position {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Position", 2)?;
+            state.serialize_field("line", &self.line)?;
+            state.serialize_field("character", &self.character)?;
+            state.end()
+        }
+    }
+
+    impl Serialize for Range {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Range", 2)?;
+            state.serialize_field("start", &self.start)?;
+            state.serialize_field("end", &self.end)?;
+            state.end()
+        }
+    }
+
+    impl Serialize for Severity {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_u8(*self as u8)
+        }
+    }
+
+    impl Serialize for RelatedInformation {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("RelatedInformation", 2)?;
+            state.serialize_field("range", &self.range)?;
+            state.serialize_field("message", &self.message)?;
+            state.end()
+        }
+    }
+
+    impl Serialize for Diagnostic {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("Diagnostic", 6)?;
+            state.serialize_field("range", &self.range)?;
+            state.serialize_field("severity", &self.severity)?;
+            match self.code {
+                Some(code) => state.serialize_field("code", code)?,
+                None => state.skip_field("code")?,
+            }
+            match &self.source {
+                Some(source) => state.serialize_field("source", source)?,
+                None => state.skip_field("source")?,
+            }
+            state.serialize_field("message", &self.message)?;
+            if self.related_information.is_empty() {
+                state.skip_field("relatedInformation")?;
+            } else {
+                state.serialize_field("relatedInformation", &self.related_information)?;
+            }
+            state.end()
+        }
+    }
+}
@@ -0,0 +1,76 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, parser::BoxedParser, Parser, ParserResult};
+
+/// A parser which chooses between alternatives by the next character of the input using a jump table, instead of
+/// trying each alternative in turn
+///
+/// Alternatives whose character sets overlap are tried in the order they were added.
+pub struct Dispatch<'a, T, E> {
+    name: Cow<'static, str>,
+    parsers: Vec<BoxedParser<'a, T, E>>,
+    /// The indices of the parsers to try for each ASCII character
+    ascii: Vec<Vec<usize>>,
+    /// The character set of each parser, used for characters outside of ASCII
+    sets: Vec<Box<dyn Fn(char) -> bool + 'a>>,
+}
+
+impl<'a, T, E> Dispatch<'a, T, E> {
+    /// Create a dispatcher with no alternatives, which reports an expected token error naming what it parses if no
+    /// alternative accepts the next character
+    pub fn new(name: impl Into<Cow<'static, str>>) -> Self {
+        Self {
+            name: name.into(),
+            parsers: vec![],
+            ascii: vec![vec![]; 128],
+            sets: vec![],
+        }
+    }
+
+    /// Add an alternative to run when the next character is in the given set
+    pub fn on(
+        mut self,
+        set: impl Fn(char) -> bool + 'a,
+        parser: impl Parser<'a, T, E> + 'a,
+    ) -> Self {
+        let index = self.parsers.len();
+        for (c, indices) in (0..128u8).map(char::from).zip(&mut self.ascii) {
+            if set(c) {
+                indices.push(index);
+            }
+        }
+        self.parsers.push(Box::new(parser));
+        self.sets.push(Box::new(set));
+        self
+    }
+
+    /// Add an alternative to run when the next character is one of the given characters
+    pub fn on_chars(self, chars: &'a str, parser: impl Parser<'a, T, E> + 'a) -> Self {
+        self.on(move |c| chars.contains(c), parser)
+    }
+}
+
+impl<'a, T, E: From<ParserError>> Parser<'a, T, E> for Dispatch<'a, T, E> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        let Some(c) = input.chars().next() else {
+            return ParserResult::from_err(ParserError::UnexpectedEndOfFile.into(), input);
+        };
+        let ascii = c.is_ascii().then(|| self.ascii[c as usize].iter().copied());
+        let other = (!c.is_ascii()).then(|| (0..self.sets.len()).filter(|&i| self.sets[i](c)));
+        let mut res = None;
+        for index in ascii
+            .into_iter()
+            .flatten()
+            .chain(other.into_iter().flatten())
+        {
+            let parsed = self.parsers[index].parse(input);
+            if parsed.is_ok() {
+                return parsed;
+            }
+            res = Some(parsed);
+        }
+        res.unwrap_or_else(|| {
+            ParserResult::from_err(ParserError::ExpectedToken(self.name.clone()).into(), input)
+        })
+    }
+}
@@ -0,0 +1,922 @@
+use std::{
+    borrow::Cow, collections::HashMap, error::Error, fmt::Display, io, ops::Bound, path::Path,
+};
+
+use crate::{
+    comment::{block_comment, line_comment},
+    error::ParserError,
+    escape::{escape, COMMON_ESCAPES},
+    identifier, literal, multispace0, try_parse, Literal, Parser, ParserResult, ParserResultType,
+};
+
+/// An error from compiling a grammar with [Grammar::new]
+#[derive(Debug, Clone, PartialEq)]
+pub enum GrammarError {
+    /// A syntax error in the grammar, along with its byte offset
+    Syntax(ParserError, usize),
+    /// A rule which is referred to but never defined
+    UndefinedRule(String),
+    /// A rule which is defined more than once
+    DuplicateRule(String),
+    /// A rule which can refer to itself, directly or through other rules, before consuming any input
+    LeftRecursion(String),
+    /// A rule whose name can't be used as a Rust identifier by [Grammar::to_rust], such as `self`
+    InvalidIdentifier(String),
+}
+
+impl Display for GrammarError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GrammarError::Syntax(err, offset) => write!(f, "{err} at offset {offset}"),
+            GrammarError::UndefinedRule(name) => write!(f, "Undefined rule '{name}'"),
+            GrammarError::DuplicateRule(name) => {
+                write!(f, "Rule '{name}' is defined more than once")
+            }
+            GrammarError::LeftRecursion(name) => write!(f, "Rule '{name}' is left-recursive"),
+            GrammarError::InvalidIdentifier(name) => {
+                write!(f, "Rule '{name}' can't be used as a Rust identifier")
+            }
+        }
+    }
+}
+
+impl Error for GrammarError {}
+
+/// An expression in the body of a grammar rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Literal(String),
+    /// An inclusive character range such as `'a'..'z'`
+    Range(char, char),
+    /// A reference to another rule by its index
+    Rule(usize),
+    Seq(Vec<Expr>),
+    /// Alternatives which are tried in order, taking the first which succeeds
+    Alt(Vec<Expr>),
+    /// An expression repeated greedily, as written with `*`, `+`, `?`, `[...]` or `{...}`
+    Repeat {
+        expr: Box<Expr>,
+        min: usize,
+        max: Option<usize>,
+    },
+}
+
+/// A node of the generic parse tree produced by a [Grammar], with a node for each rule which was matched
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Tree<'g, 'a> {
+    pub rule: &'g str,
+    /// The slice of input matched by the rule
+    pub text: &'a str,
+    pub children: Vec<Tree<'g, 'a>>,
+}
+
+/// A typed action for a rule, given to [Grammar::to_rust_with_actions] and [compile_file_with_actions]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Action<'s> {
+    /// The type which the action produces
+    pub output: &'s str,
+    /// An expression of type `fn(&str) -> output`, which is applied to the slice matched by the rule
+    pub map: &'s str,
+}
+
+/// Rule names mapped to their indices while a grammar is being read, along with the bodies defined so far
+#[derive(Default)]
+struct Rules {
+    indices: HashMap<String, usize>,
+    names: Vec<String>,
+    bodies: Vec<Option<Expr>>,
+}
+
+impl Rules {
+    fn index(&mut self, name: &str) -> usize {
+        if let Some(&index) = self.indices.get(name) {
+            return index;
+        }
+        self.indices.insert(name.to_string(), self.names.len());
+        self.names.push(name.to_string());
+        self.bodies.push(None);
+        self.names.len() - 1
+    }
+}
+
+/// Skip whitespace, `#` comments and `(* ... *)` comments
+fn trivia(mut s: &str) -> &str {
+    loop {
+        s = multispace0(s).source;
+        let comment = line_comment("#", s).or(|s| block_comment("(*", "*)", s), s);
+        if !comment.is_ok() {
+            return s;
+        }
+        s = comment.source;
+    }
+}
+
+fn token<'a>(lit: &'static str, s: &'a str) -> ParserResult<'a, Literal, ParserError> {
+    literal(lit, trivia(s))
+}
+
+/// Parse a string in single or double quotes, which may contain backslash escapes
+fn string(input: &str) -> ParserResult<'_, String, ParserError> {
+    let quote = match input.chars().next() {
+        Some(c @ ('"' | '\'')) => c,
+        _ => return ParserResult::from_err(ParserError::ExpectedToken("string".into()), input),
+    };
+    let mut s = &input[1..];
+    let mut string = String::new();
+    loop {
+        let (c, rest) = if s.starts_with('\\') {
+            try_parse!(escape(COMMON_ESCAPES, s))
+        } else {
+            match s.chars().next() {
+                Some(c) if c == quote => return ParserResult::from_val(string, &s[1..]),
+                Some(c) => (c, &s[c.len_utf8()..]),
+                None => return ParserResult::from_err(ParserError::UnexpectedEndOfFile, input),
+            }
+        };
+        string.push(c);
+        s = rest;
+    }
+}
+
+/// Parse a string, or a character range if it is a single character followed by `..` or `..=` and another
+fn string_or_range(input: &str) -> ParserResult<'_, Expr, ParserError> {
+    let (start, s) = try_parse!(string(input));
+    let mut chars = start.chars();
+    let (Some(lo), None) = (chars.next(), chars.next()) else {
+        return ParserResult::from_val(Expr::Literal(start), s);
+    };
+    let Some(rest) = trivia(s).strip_prefix("..") else {
+        return ParserResult::from_val(Expr::Literal(start), s);
+    };
+    let rest = rest.strip_prefix('=').unwrap_or(rest);
+    let end_start = trivia(rest);
+    let (end, s) = try_parse!(string(end_start));
+    let mut chars = end.chars();
+    match (chars.next(), chars.next()) {
+        (Some(hi), None) if lo <= hi => ParserResult::from_val(Expr::Range(lo, hi), s),
+        _ => ParserResult::from_err(
+            ParserError::ExpectedCharInRange(Bound::Included(lo), Bound::Unbounded),
+            end_start,
+        ),
+    }
+}
+
+/// Write a rule name as a Rust identifier, using a raw identifier if it is a keyword
+fn rust_ident(name: &str) -> Result<Cow<'_, str>, GrammarError> {
+    const KEYWORDS: &[&str] = &[
+        "as", "async", "await", "box", "break", "const", "continue", "do", "dyn", "else", "enum",
+        "extern", "false", "final", "fn", "for", "gen", "if", "impl", "in", "let", "loop", "macro",
+        "match", "mod", "move", "mut", "override", "priv", "pub", "ref", "return", "static",
+        "struct", "trait", "true", "try", "type", "typeof", "unsafe", "unsized", "use", "virtual",
+        "where", "while", "yield", "abstract", "become",
+    ];
+    // Path keywords can't be written as raw identifiers either
+    if ["self", "super", "crate", "Self", "_"].contains(&name) {
+        Err(GrammarError::InvalidIdentifier(name.to_string()))
+    } else if KEYWORDS.contains(&name) {
+        Ok(Cow::Owned(format!("r#{name}")))
+    } else {
+        Ok(Cow::Borrowed(name))
+    }
+}
+
+fn write_string(string: &str, quote: char, out: &mut String) {
+    out.push(quote);
+    for c in string.chars() {
+        match COMMON_ESCAPES.iter().find(|(_, escaped)| *escaped == c) {
+            Some((name, _)) if c == quote || !matches!(c, '"' | '\'') => {
+                out.push('\\');
+                out.push(*name);
+            }
+            _ => out.push(c),
+        }
+    }
+    out.push(quote);
+}
+
+fn group<'a>(
+    rules: &mut Rules,
+    close: &'static str,
+    s: &'a str,
+) -> ParserResult<'a, Expr, ParserError> {
+    let (expr, s) = try_parse!(alternatives(rules, s));
+    let (_, s) = try_parse!(token(close, s));
+    ParserResult::from_val(expr, s)
+}
+
+fn atom<'a>(rules: &mut Rules, input: &'a str) -> ParserResult<'a, Expr, ParserError> {
+    let s = trivia(input);
+    let repeat = |expr, max| Expr::Repeat {
+        expr: Box::new(expr),
+        min: 0,
+        max,
+    };
+    match s.chars().next() {
+        Some('"' | '\'') => string_or_range(s),
+        Some('(') => group(rules, ")", &s[1..]),
+        Some('[') => group(rules, "]", &s[1..]).map(|expr| repeat(expr, Some(1))),
+        Some('{') => group(rules, "}", &s[1..]).map(|expr| repeat(expr, None)),
+        _ => {
+            let (name, rest) = try_parse!(identifier(s));
+            ParserResult::from_val(Expr::Rule(rules.index(name)), rest)
+        }
+    }
+}
+
+/// Check whether the input starts with the name of the next rule, when the previous one didn't end with `;`
+fn starts_rule(s: &str) -> bool {
+    let rest = identifier(s).source;
+    rest.len() < s.len() && (token("=", rest).is_ok() || token("::=", rest).is_ok())
+}
+
+fn postfix<'a>(rules: &mut Rules, input: &'a str) -> ParserResult<'a, Expr, ParserError> {
+    let (expr, s) = try_parse!(atom(rules, input));
+    let after = trivia(s);
+    let (min, max) = match after.chars().next() {
+        Some('*') => (0, None),
+        Some('+') => (1, None),
+        Some('?') => (0, Some(1)),
+        _ => return ParserResult::from_val(expr, s),
+    };
+    let expr = Expr::Repeat {
+        expr: Box::new(expr),
+        min,
+        max,
+    };
+    ParserResult::from_val(expr, &after[1..])
+}
+
+fn sequence<'a>(rules: &mut Rules, mut s: &'a str) -> ParserResult<'a, Expr, ParserError> {
+    let mut exprs = vec![];
+    loop {
+        let next = trivia(s);
+        if next.is_empty() || next.starts_with(['|', ')', ']', '}', ';']) || starts_rule(next) {
+            break;
+        }
+        let (expr, rest) = try_parse!(postfix(rules, next));
+        exprs.push(expr);
+        s = rest;
+    }
+    if exprs.len() == 1 {
+        return ParserResult::from_val(exprs.pop().unwrap(), s);
+    }
+    ParserResult::from_val(Expr::Seq(exprs), s)
+}
+
+fn alternatives<'a>(rules: &mut Rules, input: &'a str) -> ParserResult<'a, Expr, ParserError> {
+    let (first, mut s) = try_parse!(sequence(rules, input));
+    let mut alts = vec![first];
+    while let Ok((_, rest)) = token("|", s).into_result::<()>() {
+        let (alt, rest) = try_parse!(sequence(rules, rest));
+        alts.push(alt);
+        s = rest;
+    }
+    if alts.len() == 1 {
+        return ParserResult::from_val(alts.pop().unwrap(), s);
+    }
+    ParserResult::from_val(Expr::Alt(alts), s)
+}
+
+/// Parse a `name = body ;` rule, where the body may also be followed by nothing but the next rule
+fn rule<'a>(rules: &mut Rules, input: &'a str) -> ParserResult<'a, (usize, Expr), ParserError> {
+    let (name, s) = try_parse!(identifier(trivia(input)));
+    let (_, s) = try_parse!(token("::=", s).or(|s| token("=", s), s));
+    let index = rules.index(name);
+    let (body, s) = try_parse!(alternatives(rules, s));
+    let s = token(";", s).optional(s).source;
+    ParserResult::from_val((index, body), s)
+}
+
+/// A grammar read at runtime from an EBNF description, whose rules can be used as parsers producing a [Tree]
+///
+/// Rules are written as `name = body;` or `name ::= body;`, where the `;` is optional. A body is made of alternatives
+/// separated by `|`, each a sequence of terms: a string in single or double quotes, a character range such as
+/// `'a'..'z'`, the name of a rule, `( ... )` for grouping, `[ ... ]` for an optional part or `{ ... }` for a part
+/// repeated any number of times. Terms may be followed by `*`, `+` or `?`. Alternatives are tried in order and
+/// repetition is greedy without backtracking, as with [parser!](crate::parser!), so left-recursive rules are rejected
+/// with [GrammarError::LeftRecursion]. Comments are written with `#` or `(* ... *)`.
+/// Example:
+/// ```
+/// use crochet::{ebnf::Grammar, Parser};
+/// let grammar = Grammar::new(r#"
+///     number = '0'..'9'+;
+///     sum = number { ("+" | "-") number };
+/// "#).unwrap();
+/// let tree = grammar.rule("sum").unwrap().parse("1+2").unwrap();
+/// assert_eq!(tree.children.len(), 2);
+/// assert_eq!(tree.children[1].text, "2");
+/// ```
+#[derive(Debug, Clone)]
+pub struct Grammar {
+    names: Vec<String>,
+    bodies: Vec<Expr>,
+}
+
+impl Grammar {
+    /// Read a grammar, checking that every rule it refers to is defined exactly once and that none are left-recursive
+    pub fn new(source: &str) -> Result<Grammar, GrammarError> {
+        let mut rules = Rules::default();
+        let mut s = trivia(source);
+        while !s.is_empty() {
+            let res = rule(&mut rules, s);
+            let offset = source.len() - res.source.len();
+            let (index, body) = match res.typ {
+                ParserResultType::Ok(rule) => rule,
+                ParserResultType::Err(err) => return Err(GrammarError::Syntax(err, offset)),
+                ParserResultType::Incomplete(_) => {
+                    return Err(GrammarError::Syntax(
+                        ParserError::UnexpectedEndOfFile,
+                        offset,
+                    ))
+                }
+            };
+            if rules.bodies[index].replace(body).is_some() {
+                return Err(GrammarError::DuplicateRule(rules.names[index].clone()));
+            }
+            s = trivia(res.source);
+        }
+        let bodies = rules
+            .bodies
+            .into_iter()
+            .zip(&rules.names)
+            .map(|(body, name)| body.ok_or_else(|| GrammarError::UndefinedRule(name.clone())))
+            .collect::<Result<_, _>>()?;
+        let grammar = Grammar {
+            names: rules.names,
+            bodies,
+        };
+        grammar.check_left_recursion()?;
+        Ok(grammar)
+    }
+
+    /// Whether an expression can match without consuming any input, given which rules can
+    fn nullable(expr: &Expr, rules: &[bool]) -> bool {
+        match expr {
+            Expr::Literal(lit) => lit.is_empty(),
+            Expr::Range(..) => false,
+            Expr::Rule(index) => rules[*index],
+            Expr::Seq(exprs) => exprs.iter().all(|expr| Self::nullable(expr, rules)),
+            Expr::Alt(exprs) => exprs.iter().any(|expr| Self::nullable(expr, rules)),
+            Expr::Repeat { expr, min, .. } => *min == 0 || Self::nullable(expr, rules),
+        }
+    }
+
+    /// Get the rules which the expression can refer to before consuming any input
+    fn leading_references(expr: &Expr, nullable: &[bool], refs: &mut Vec<usize>) {
+        match expr {
+            Expr::Literal(_) | Expr::Range(..) => {}
+            Expr::Rule(index) => refs.push(*index),
+            Expr::Seq(exprs) => {
+                for expr in exprs {
+                    Self::leading_references(expr, nullable, refs);
+                    if !Self::nullable(expr, nullable) {
+                        break;
+                    }
+                }
+            }
+            Expr::Alt(exprs) => exprs
+                .iter()
+                .for_each(|expr| Self::leading_references(expr, nullable, refs)),
+            Expr::Repeat { expr, .. } => Self::leading_references(expr, nullable, refs),
+        }
+    }
+
+    /// Fail on the first rule which can reach itself through its leading references, which would recurse forever
+    fn check_left_recursion(&self) -> Result<(), GrammarError> {
+        let mut nullable = vec![false; self.bodies.len()];
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, body) in self.bodies.iter().enumerate() {
+                if !nullable[index] && Self::nullable(body, &nullable) {
+                    nullable[index] = true;
+                    changed = true;
+                }
+            }
+        }
+        let leading: Vec<Vec<usize>> = self
+            .bodies
+            .iter()
+            .map(|body| {
+                let mut refs = vec![];
+                Self::leading_references(body, &nullable, &mut refs);
+                refs
+            })
+            .collect();
+        for start in 0..self.bodies.len() {
+            let mut seen = vec![false; self.bodies.len()];
+            let mut stack = leading[start].clone();
+            while let Some(index) = stack.pop() {
+                if index == start {
+                    return Err(GrammarError::LeftRecursion(self.names[start].clone()));
+                }
+                if !std::mem::replace(&mut seen[index], true) {
+                    stack.extend(&leading[index]);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Get the names of the rules in the order they first appear
+    pub fn rule_names(&self) -> impl Iterator<Item = &str> {
+        self.names.iter().map(String::as_str)
+    }
+
+    /// Get the body of a rule
+    pub fn body(&self, name: &str) -> Option<&Expr> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(&self.bodies[index])
+    }
+
+    /// Get a parser for a rule, which parses a prefix of its input
+    pub fn rule(&self, name: &str) -> Option<Rule<'_>> {
+        let index = self.names.iter().position(|n| n == name)?;
+        Some(Rule {
+            grammar: self,
+            index,
+        })
+    }
+
+    /// Get a generator of random inputs which the grammar accepts, seeded so that its output can be reproduced
+    pub fn generator(&self, seed: u64) -> Generator<'_> {
+        Generator::new(self, seed)
+    }
+
+    /// Write an expression in the notation accepted by [Grammar::new], or by [parser!](crate::parser!) if `rust` gives
+    /// the Rust name of each rule, with `outer` set when it is the whole body of a rule or an alternative and so
+    /// doesn't need parentheses
+    fn write_expr(&self, expr: &Expr, outer: bool, rust: Option<&[String]>, out: &mut String) {
+        match expr {
+            Expr::Literal(lit) if rust.is_some() => out.push_str(&format!("{lit:?}")),
+            Expr::Literal(lit) => write_string(lit, '"', out),
+            Expr::Range(lo, hi) if rust.is_some() => out.push_str(&format!("({lo:?}..={hi:?})")),
+            Expr::Range(lo, hi) => {
+                write_string(lo.encode_utf8(&mut [0; 4]), '\'', out);
+                out.push_str("..");
+                write_string(hi.encode_utf8(&mut [0; 4]), '\'', out);
+            }
+            Expr::Rule(index) => match rust {
+                Some(names) => out.push_str(&names[*index]),
+                None => out.push_str(&self.names[*index]),
+            },
+            Expr::Seq(exprs) | Expr::Alt(exprs) => {
+                let alt = matches!(expr, Expr::Alt(_));
+                let separator = if alt { " | " } else { " " };
+                let parens = !outer && (alt || exprs.len() != 1);
+                if parens {
+                    out.push('(');
+                }
+                for (i, expr) in exprs.iter().enumerate() {
+                    if i > 0 {
+                        out.push_str(separator);
+                    }
+                    self.write_expr(expr, alt, rust, out);
+                }
+                if parens {
+                    out.push(')');
+                }
+            }
+            Expr::Repeat { expr, min, max } => match (min, max) {
+                (0, Some(1)) | (0, None) | (1, None) => {
+                    // Postfix operators can't be stacked, so a repeated repetition needs parentheses
+                    let nested = matches!(**expr, Expr::Repeat { .. });
+                    if nested {
+                        out.push('(');
+                    }
+                    self.write_expr(expr, false, rust, out);
+                    if nested {
+                        out.push(')');
+                    }
+                    out.push(match max {
+                        Some(_) => '?',
+                        None if *min == 0 => '*',
+                        None => '+',
+                    });
+                }
+                _ => {
+                    // Other counts are only written out as repeated terms
+                    let mut terms = vec![];
+                    terms.extend(std::iter::repeat_n((**expr).clone(), *min));
+                    let rest = match max {
+                        Some(max) => max.saturating_sub(*min),
+                        None => 1,
+                    };
+                    let optional = Expr::Repeat {
+                        expr: expr.clone(),
+                        min: 0,
+                        max: max.map(|_| 1),
+                    };
+                    terms.extend(std::iter::repeat_n(optional, rest));
+                    self.write_expr(&Expr::Seq(terms), outer, rust, out);
+                }
+            },
+        }
+    }
+
+    /// Get the rules which the expression refers to, in the order they first appear
+    fn references(expr: &Expr, refs: &mut Vec<usize>) {
+        match expr {
+            Expr::Literal(_) | Expr::Range(..) => {}
+            Expr::Rule(index) if !refs.contains(index) => refs.push(*index),
+            Expr::Rule(_) => {}
+            Expr::Seq(exprs) | Expr::Alt(exprs) => {
+                exprs.iter().for_each(|expr| Self::references(expr, refs))
+            }
+            Expr::Repeat { expr, .. } => Self::references(expr, refs),
+        }
+    }
+
+    /// Write the grammar back out in the notation accepted by [Grammar::new], with one rule per line
+    pub fn to_ebnf(&self) -> String {
+        let mut out = String::new();
+        for (name, body) in self.names.iter().zip(&self.bodies) {
+            out.push_str(name);
+            out.push_str(" = ");
+            self.write_expr(body, true, None, &mut out);
+            out.push_str(";\n");
+        }
+        out
+    }
+
+    /// Generate Rust source defining each rule as a public parsing function with [parser!](crate::parser!), returning
+    /// the slice it matched, so that a grammar file can be compiled by a build script, see [compile_file]
+    ///
+    /// Rule names which are Rust keywords are written as raw identifiers, such as `r#type`, and names which can't be
+    /// identifiers at all, such as `self`, fail with [GrammarError::InvalidIdentifier].
+    pub fn to_rust(&self) -> Result<String, GrammarError> {
+        self.to_rust_with_actions(&[])
+    }
+
+    /// Generate Rust source like [Grammar::to_rust], where each rule given an [Action] is instead a public function
+    /// returning the action's output, and the function returning its slice is a private one named `{rule}_text`
+    /// Example:
+    /// ```
+    /// use crochet::ebnf::{Action, Grammar};
+    /// let grammar = Grammar::new("number = '0'..'9'+; sum = number {'+' number};").unwrap();
+    /// let number = Action {
+    ///     output: "u64",
+    ///     map: "|s: &str| s.parse().unwrap()",
+    /// };
+    /// let rust = grammar.to_rust_with_actions(&[("number", number)]).unwrap();
+    /// assert!(rust.contains("pub fn number(input: &str) -> crochet::ParserResult<'_, u64"));
+    /// ```
+    pub fn to_rust_with_actions(&self, actions: &[(&str, Action)]) -> Result<String, GrammarError> {
+        let mut typed = vec![None; self.names.len()];
+        for (rule, action) in actions {
+            let index = self
+                .names
+                .iter()
+                .position(|name| name == rule)
+                .ok_or_else(|| GrammarError::UndefinedRule(rule.to_string()))?;
+            typed[index] = Some(action);
+        }
+        let mut names = vec![];
+        for (name, action) in self.names.iter().zip(&typed) {
+            let ident = rust_ident(name)?;
+            names.push(match action {
+                Some(_) => format!("{name}_text"),
+                None => ident.into_owned(),
+            });
+        }
+        for name in names
+            .iter()
+            .zip(&typed)
+            .filter(|(_, a)| a.is_some())
+            .map(|(n, _)| n)
+        {
+            if self.names.contains(name) {
+                return Err(GrammarError::DuplicateRule(name.clone()));
+            }
+        }
+        let mut out = String::from("crochet::parser! {\n");
+        for ((name, body), action) in names.iter().zip(&self.bodies).zip(&typed) {
+            let vis = if action.is_some() { "" } else { "pub " };
+            out.push_str(&format!("    {vis}rule {name} = "));
+            self.write_expr(body, true, Some(&names), &mut out);
+            out.push_str(";\n");
+        }
+        out.push_str("}\n");
+        for ((name, text), action) in self.names.iter().zip(&names).zip(&typed) {
+            let Some(Action { output, map }) = action else {
+                continue;
+            };
+            out.push_str(&format!(
+                "\npub fn {}(input: &str) -> crochet::ParserResult<'_, {output}, crochet::error::ParserError> {{\n",
+                rust_ident(name)?
+            ));
+            out.push_str(&format!("    {text}(input).map({map})\n}}\n"));
+        }
+        Ok(out)
+    }
+
+    /// Export the rule graph as a GraphViz `dot` digraph, with a node for each rule showing its body as a tooltip and
+    /// an edge to each rule it refers to
+    ///
+    /// This can be rendered with `dot -Tsvg`, including for a grammar written with [parser!](crate::parser!) by
+    /// reading the source it exports with `grammar`.
+    pub fn to_dot(&self) -> String {
+        let quote = |s: &str| format!("\"{}\"", s.replace('\\', "\\\\").replace('"', "\\\""));
+        let mut out = String::from("digraph grammar {\n    node [shape=box];\n");
+        for (name, body) in self.names.iter().zip(&self.bodies) {
+            let mut source = String::new();
+            self.write_expr(body, true, None, &mut source);
+            out.push_str(&format!(
+                "    {} [tooltip={}];\n",
+                quote(name),
+                quote(&source)
+            ));
+        }
+        for (name, body) in self.names.iter().zip(&self.bodies) {
+            let mut refs = vec![];
+            Self::references(body, &mut refs);
+            for index in refs {
+                out.push_str(&format!(
+                    "    {} -> {};\n",
+                    quote(name),
+                    quote(&self.names[index])
+                ));
+            }
+        }
+        out.push_str("}\n");
+        out
+    }
+
+    fn parse_rule<'g, 'a>(
+        &'g self,
+        index: usize,
+        input: &'a str,
+    ) -> ParserResult<'a, Tree<'g, 'a>, ParserError> {
+        let mut children = vec![];
+        let (_, s) = try_parse!(self.parse_expr(&self.bodies[index], &mut children, input));
+        let tree = Tree {
+            rule: &self.names[index],
+            text: &input[..input.len() - s.len()],
+            children,
+        };
+        ParserResult::from_val(tree, s)
+    }
+
+    fn parse_expr<'g, 'a>(
+        &'g self,
+        expr: &Expr,
+        children: &mut Vec<Tree<'g, 'a>>,
+        input: &'a str,
+    ) -> ParserResult<'a, (), ParserError> {
+        match expr {
+            Expr::Literal(lit) => match input.strip_prefix(lit.as_str()) {
+                Some(s) => ParserResult::from_val((), s),
+                None => ParserResult::from_err(
+                    ParserError::ExpectedToken(format!("{lit:?}").into()),
+                    input,
+                ),
+            },
+            Expr::Range(lo, hi) => match input.chars().next() {
+                Some(c) if (*lo..=*hi).contains(&c) => {
+                    ParserResult::from_val((), &input[c.len_utf8()..])
+                }
+                _ => ParserResult::from_err(
+                    ParserError::ExpectedCharInRange(Bound::Included(*lo), Bound::Included(*hi)),
+                    input,
+                ),
+            },
+            Expr::Rule(index) => {
+                let (tree, s) = try_parse!(self.parse_rule(*index, input));
+                children.push(tree);
+                ParserResult::from_val((), s)
+            }
+            Expr::Seq(exprs) => {
+                let mut s = input;
+                for expr in exprs {
+                    s = try_parse!(self.parse_expr(expr, children, s)).1;
+                }
+                ParserResult::from_val((), s)
+            }
+            Expr::Alt(alts) => {
+                let len = children.len();
+                let mut res = ParserResult::from_err(ParserError::UnexpectedEndOfFile, input);
+                for alt in alts {
+                    children.truncate(len);
+                    res = self.parse_expr(alt, children, input);
+                    if res.is_ok() {
+                        break;
+                    }
+                }
+                res
+            }
+            Expr::Repeat { expr, min, max } => {
+                let mut s = input;
+                let mut count = 0;
+                while max.is_none_or(|max| count < max) {
+                    let len = children.len();
+                    let res = self.parse_expr(expr, children, s);
+                    match res.typ {
+                        ParserResultType::Ok(()) if res.source.len() < s.len() => {}
+                        ParserResultType::Ok(()) => break,
+                        _ if count < *min => return res,
+                        _ => {
+                            children.truncate(len);
+                            break;
+                        }
+                    }
+                    s = res.source;
+                    count += 1;
+                }
+                ParserResult::from_val((), s)
+            }
+        }
+    }
+}
+
+/// A parser for a single rule of a [Grammar], created with [Grammar::rule]
+#[derive(Debug, Clone, Copy)]
+pub struct Rule<'g> {
+    grammar: &'g Grammar,
+    index: usize,
+}
+
+impl<'g, 'a> Parser<'a, Tree<'g, 'a>, ParserError> for Rule<'g> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, Tree<'g, 'a>, ParserError> {
+        self.grammar.parse_rule(self.index, input)
+    }
+}
+
+/// A generator of random inputs for the rules of a [Grammar], created with [Grammar::generator], for fuzzing and
+/// property tests of a format
+///
+/// Below the maximum depth, alternatives are picked at random and repetitions are repeated a random number of times.
+/// Beyond it, the choices which reach the fewest rules are taken so that generation ends. Since alternatives are
+/// ordered and repetition is greedy, a generated input isn't always accepted, so each one is checked against the
+/// rule and rejected ones are retried.
+#[derive(Debug, Clone)]
+pub struct Generator<'g> {
+    grammar: &'g Grammar,
+    /// For each rule, the fewest nested rules needed to finish generating it
+    costs: Vec<usize>,
+    state: u64,
+    max_depth: usize,
+    max_repeat: usize,
+    attempts: usize,
+}
+
+impl<'g> Generator<'g> {
+    fn new(grammar: &'g Grammar, seed: u64) -> Self {
+        let mut costs = vec![usize::MAX; grammar.bodies.len()];
+        loop {
+            let mut changed = false;
+            for i in 0..costs.len() {
+                let cost = Self::cost(&costs, &grammar.bodies[i]);
+                if cost < costs[i] {
+                    costs[i] = cost;
+                    changed = true;
+                }
+            }
+            if !changed {
+                break;
+            }
+        }
+        Self {
+            grammar,
+            costs,
+            state: seed,
+            max_depth: 8,
+            max_repeat: 3,
+            attempts: 100,
+        }
+    }
+
+    /// Set how deeply rules can be nested before choices are made to end generation, which is 8 by default
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = depth;
+        self
+    }
+
+    /// Set how many more times than its minimum an unbounded repetition can repeat, which is 3 by default
+    pub fn max_repeat(mut self, count: usize) -> Self {
+        self.max_repeat = count;
+        self
+    }
+
+    /// Set how many inputs to generate before giving up on finding one the rule accepts, which is 100 by default
+    pub fn attempts(mut self, attempts: usize) -> Self {
+        self.attempts = attempts;
+        self
+    }
+
+    /// The fewest nested rules needed to finish generating an expression
+    fn cost(costs: &[usize], expr: &Expr) -> usize {
+        match expr {
+            Expr::Literal(_) | Expr::Range(..) => 0,
+            Expr::Rule(index) => costs[*index].saturating_add(1),
+            Expr::Seq(exprs) => exprs
+                .iter()
+                .map(|e| Self::cost(costs, e))
+                .max()
+                .unwrap_or(0),
+            Expr::Alt(alts) => alts.iter().map(|e| Self::cost(costs, e)).min().unwrap_or(0),
+            Expr::Repeat { min: 0, .. } => 0,
+            Expr::Repeat { expr, .. } => Self::cost(costs, expr),
+        }
+    }
+
+    /// Get a random number below `bound` using splitmix64
+    fn random(&mut self, bound: usize) -> usize {
+        self.state = self.state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        ((z ^ (z >> 31)) % bound.max(1) as u64) as usize
+    }
+
+    /// Generate text for an expression, returning false if it ran too deep because a rule can't finish
+    fn expr(&mut self, expr: &Expr, depth: usize, out: &mut String) -> bool {
+        if depth > self.max_depth.saturating_mul(2).saturating_add(64) {
+            return false;
+        }
+        let shallow = depth < self.max_depth;
+        match expr {
+            Expr::Literal(lit) => out.push_str(lit),
+            Expr::Range(lo, hi) => {
+                let span = *hi as usize - *lo as usize + 1;
+                let c = (0..8)
+                    .find_map(|_| char::from_u32(*lo as u32 + self.random(span) as u32))
+                    .unwrap_or(*lo);
+                out.push(c);
+            }
+            Expr::Rule(index) => return self.expr(&self.grammar.bodies[*index], depth + 1, out),
+            Expr::Seq(exprs) => return exprs.iter().all(|e| self.expr(e, depth, out)),
+            Expr::Alt(alts) if alts.is_empty() => {}
+            Expr::Alt(alts) => {
+                let alt = if shallow {
+                    &alts[self.random(alts.len())]
+                } else {
+                    alts.iter()
+                        .min_by_key(|e| Self::cost(&self.costs, e))
+                        .unwrap()
+                };
+                return self.expr(alt, depth, out);
+            }
+            Expr::Repeat { expr, min, max } => {
+                let extra = match max {
+                    _ if !shallow => 0,
+                    Some(max) => max.saturating_sub(*min).min(self.max_repeat),
+                    None => self.max_repeat,
+                };
+                let count = min + self.random(extra + 1);
+                return (0..count).all(|_| self.expr(expr, depth, out));
+            }
+        }
+        true
+    }
+
+    /// Generate an input which the rule accepts in its entirety, or `None` if the rule doesn't exist or no attempt
+    /// produced one
+    pub fn generate(&mut self, rule: &str) -> Option<String> {
+        let parser = self.grammar.rule(rule)?;
+        for _ in 0..self.attempts {
+            let mut out = String::new();
+            if !self.expr(&Expr::Rule(parser.index), 0, &mut out) {
+                continue;
+            }
+            let res = parser.parse(&out);
+            if res.is_ok() && res.source.is_empty() {
+                return Some(out);
+            }
+        }
+        None
+    }
+}
+
+/// Compile a grammar file into Rust source for a build script, with each rule becoming a public parsing function as
+/// with [Grammar::to_rust], and tell Cargo to rerun the build script when the grammar changes
+///
+/// The generated file can be included with `include!(concat!(env!("OUT_DIR"), "/grammar.rs"))`. Errors in the grammar
+/// are returned as [InvalidData](io::ErrorKind::InvalidData) errors wrapping a [GrammarError].
+/// Example of a build script:
+/// ```no_run
+/// fn main() -> std::io::Result<()> {
+///     let out = std::path::Path::new(&std::env::var("OUT_DIR").unwrap()).join("grammar.rs");
+///     crochet::ebnf::compile_file("src/grammar.ebnf", out)
+/// }
+/// ```
+pub fn compile_file(grammar: impl AsRef<Path>, output: impl AsRef<Path>) -> io::Result<()> {
+    compile_file_with_actions(grammar, output, &[])
+}
+
+/// Compile a grammar file like [compile_file], where the rules given an [Action] produce typed values as with
+/// [Grammar::to_rust_with_actions]
+pub fn compile_file_with_actions(
+    grammar: impl AsRef<Path>,
+    output: impl AsRef<Path>,
+    actions: &[(&str, Action)],
+) -> io::Result<()> {
+    let grammar = grammar.as_ref();
+    println!("cargo:rerun-if-changed={}", grammar.display());
+    let source = std::fs::read_to_string(grammar)?;
+    let invalid = |err| io::Error::new(io::ErrorKind::InvalidData, err);
+    let grammar = Grammar::new(&source).map_err(invalid)?;
+    std::fs::write(
+        output,
+        grammar.to_rust_with_actions(actions).map_err(invalid)?,
+    )
+}
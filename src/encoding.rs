@@ -0,0 +1,192 @@
+use std::borrow::Cow;
+
+use crate::{
+    binary::{be_u16, le_u16, try_binary, BinaryResult},
+    error::ParserError,
+    Needed, ParserResult,
+};
+
+/// A legacy encoding with one byte per character, for decoding input which isn't UTF-8 so that text parsers can be
+/// used on it
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SingleByte {
+    /// ISO-8859-1, where every byte is the code point of the same value
+    Latin1,
+    /// Windows-1252, which is Latin-1 with printable characters such as `€` and curly quotes in place of most of the
+    /// C1 control codes, keeping the five unassigned bytes as controls as web browsers do
+    Windows1252,
+}
+
+/// The characters of Windows-1252 from `0x80` to `0x9F`
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8D}', 'Ž', '\u{8F}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9D}', 'ž', 'Ÿ',
+];
+
+impl SingleByte {
+    /// Decode a single byte
+    pub fn decode_byte(self, byte: u8) -> char {
+        match (self, byte) {
+            (SingleByte::Windows1252, 0x80..=0x9F) => WINDOWS_1252[usize::from(byte - 0x80)],
+            _ => char::from(byte),
+        }
+    }
+
+    /// Decode the bytes into text which can be parsed, borrowing them if they are all ASCII
+    pub fn decode(self, bytes: &[u8]) -> Cow<'_, str> {
+        if bytes.is_ascii() {
+            return Cow::Borrowed(std::str::from_utf8(bytes).unwrap());
+        }
+        Cow::Owned(bytes.iter().map(|&byte| self.decode_byte(byte)).collect())
+    }
+
+    /// Decode the bytes one line at a time, as each line is needed, with the line endings removed like
+    /// [lines](crate::lines::lines) does
+    pub fn decode_lines(self, bytes: &[u8]) -> impl Iterator<Item = Cow<'_, str>> {
+        let bytes = bytes.strip_suffix(b"\n").unwrap_or(bytes);
+        bytes
+            .split(|&byte| byte == b'\n')
+            .take_while(move |_| !bytes.is_empty())
+            .map(move |line| self.decode(line.strip_suffix(b"\r").unwrap_or(line)))
+    }
+
+    /// Find the offset in the original bytes of a byte offset in text they were decoded into, such as to report where
+    /// an error occurred
+    pub fn original_offset(self, decoded: &str, offset: usize) -> usize {
+        decoded[..offset].chars().count()
+    }
+}
+
+/// The byte order of UTF-16 input, such as from Windows registry exports or logs
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Utf16 {
+    Le,
+    Be,
+}
+
+impl Utf16 {
+    fn unit<E>(self, input: &[u8]) -> BinaryResult<'_, u16, E> {
+        match self {
+            Utf16::Le => le_u16(input),
+            Utf16::Be => be_u16(input),
+        }
+    }
+
+    /// Parse a single character, combining a surrogate pair into one and failing with
+    /// [InvalidCodePoint](ParserError::InvalidCodePoint) on a surrogate without its other half
+    pub fn advance<E: From<ParserError>>(self, input: &[u8]) -> BinaryResult<'_, char, E> {
+        let (unit, s) = try_binary!(self.unit::<E>(input));
+        match unit {
+            0xD800..=0xDBFF => {
+                if s.len() < 2 {
+                    return BinaryResult::incomplete_needing(Needed::new(2 - s.len()), input);
+                }
+                let (low, s) = try_binary!(self.unit::<E>(s));
+                if !(0xDC00..=0xDFFF).contains(&low) {
+                    return BinaryResult::from_err(
+                        ParserError::InvalidCodePoint(u32::from(unit)).into(),
+                        input,
+                    );
+                }
+                let c = 0x10000 + ((u32::from(unit) - 0xD800) << 10) + (u32::from(low) - 0xDC00);
+                BinaryResult::from_val(char::from_u32(c).unwrap(), s)
+            }
+            0xDC00..=0xDFFF => {
+                BinaryResult::from_err(ParserError::InvalidCodePoint(u32::from(unit)).into(), input)
+            }
+            _ => BinaryResult::from_val(char::from_u32(u32::from(unit)).unwrap(), s),
+        }
+    }
+
+    /// Parse a single character matching a predicate
+    pub fn matching_char<'a, E: From<ParserError>>(
+        self,
+        token_name: impl Into<Cow<'static, str>>,
+        mut filter: impl FnMut(char) -> bool,
+        input: &'a [u8],
+    ) -> BinaryResult<'a, char, E> {
+        let (c, s) = try_binary!(self.advance::<E>(input));
+        if !filter(c) {
+            return BinaryResult::from_err(
+                ParserError::ExpectedToken(token_name.into()).into(),
+                input,
+            );
+        }
+        BinaryResult::from_val(c, s)
+    }
+
+    /// Consume characters as long as they match a predicate, returning them decoded
+    pub fn take_while<'a, E: From<ParserError>>(
+        self,
+        token_name: impl Into<Cow<'static, str>>,
+        mut filter: impl FnMut(char) -> bool,
+        input: &'a [u8],
+    ) -> BinaryResult<'a, String, E> {
+        let mut text = String::new();
+        let mut s = input;
+        while let Ok((c, rest)) = self.advance::<E>(s).into_result::<()>() {
+            if !filter(c) {
+                break;
+            }
+            text.push(c);
+            s = rest;
+        }
+        if text.is_empty() {
+            return BinaryResult::from_err(
+                ParserError::ExpectedToken(token_name.into()).into(),
+                input,
+            );
+        }
+        BinaryResult::from_val(text, s)
+    }
+
+    /// Decode all of the input into text which can be parsed, failing at a lone surrogate or a trailing odd byte
+    pub fn decode<E: From<ParserError>>(self, input: &[u8]) -> BinaryResult<'_, String, E> {
+        let mut text = String::with_capacity(input.len() / 2);
+        let mut s = input;
+        while !s.is_empty() {
+            let res = self.advance::<E>(s);
+            if res.is_incomplete() {
+                return BinaryResult::from_err(ParserError::UnexpectedEndOfFile.into(), s);
+            }
+            let (c, rest) = try_binary!(res);
+            text.push(c);
+            s = rest;
+        }
+        BinaryResult::from_val(text, s)
+    }
+
+    /// Find the offset in the original bytes of a byte offset in text they were decoded into, such as to report where
+    /// an error occurred
+    pub fn original_offset(self, decoded: &str, offset: usize) -> usize {
+        decoded[..offset].encode_utf16().count() * 2
+    }
+}
+
+/// An encoding identified by the byte order mark at the start of some input
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Bom {
+    Utf8,
+    Utf16(Utf16),
+}
+
+/// Detect and consume a UTF-8 or UTF-16 byte order mark, returning the encoding it identifies, or `None` without
+/// consuming anything if there isn't one
+pub fn bom<E>(input: &[u8]) -> BinaryResult<'_, Option<Bom>, E> {
+    let (bom, len) = match input {
+        [0xEF, 0xBB, 0xBF, ..] => (Bom::Utf8, 3),
+        [0xFF, 0xFE, ..] => (Bom::Utf16(Utf16::Le), 2),
+        [0xFE, 0xFF, ..] => (Bom::Utf16(Utf16::Be), 2),
+        _ => return BinaryResult::from_val(None, input),
+    };
+    BinaryResult::from_val(Some(bom), &input[len..])
+}
+
+/// Consume a byte order mark at the start of text if there is one, returning whether there was, so that it isn't
+/// mistaken for part of the first token
+pub fn skip_bom(input: &str) -> ParserResult<'_, bool, ParserError> {
+    match input.strip_prefix('\u{FEFF}') {
+        Some(rest) => ParserResult::from_val(true, rest),
+        None => ParserResult::from_val(false, input),
+    }
+}
@@ -1,10 +1,146 @@
-use std::{error::Error, fmt::Display};
+use std::{borrow::Cow, error::Error, fmt::Display, ops::Bound, sync::Arc};
 
-#[derive(Debug)]
+#[derive(Debug, Clone, PartialEq)]
 pub enum ParserError {
     ExpectedLiteral(&'static str),
-    ExpectedToken(&'static str),
+    ExpectedToken(Cow<'static, str>),
     UnexpectedEndOfFile,
+    IntegerOverflow,
+    InvalidEscape(char),
+    InvalidCodePoint(u32),
+    /// A character outside of the range given to [char_range](crate::char_range)
+    ExpectedCharInRange(Bound<char>, Bound<char>),
+    /// A word which isn't one of the expected keywords, along with the closest keywords to suggest instead
+    UnknownKeyword(String, Vec<&'static str>),
+    /// The fuel given to [with_fuel](crate::fuel::with_fuel) ran out
+    OutOfFuel,
+    /// The deadline given to [with_deadline](crate::deadline::with_deadline) passed
+    DeadlineExceeded,
+    /// The token given to [with_cancellation](crate::deadline::with_cancellation) was cancelled
+    Cancelled,
+    /// A parser repeated without an upper bound succeeded without consuming any input, so it would repeat forever
+    NoProgress,
+    /// The checksum of a frame parsed with [checksummed](crate::binary::checksummed) didn't match its checksum field
+    ChecksumMismatch,
+    /// An error from outside of the parser, such as from a conversion in [map_res](crate::Parser::map_res)
+    External(ExternalError),
+}
+
+impl ParserError {
+    /// Wrap an arbitrary error, which the resulting error displays as and shares its [source](Error::source) with
+    pub fn external(err: impl Error + Send + Sync + 'static) -> Self {
+        ParserError::External(ExternalError(Arc::new(err)))
+    }
+
+    /// Get the category of this error, to branch on without matching its message
+    pub fn code(&self) -> ErrorCode {
+        match self {
+            ParserError::ExpectedLiteral(_) => ErrorCode::ExpectedLiteral,
+            ParserError::ExpectedToken(_) => ErrorCode::ExpectedToken,
+            ParserError::UnexpectedEndOfFile => ErrorCode::UnexpectedEndOfFile,
+            ParserError::IntegerOverflow => ErrorCode::IntegerOverflow,
+            ParserError::InvalidEscape(_) => ErrorCode::InvalidEscape,
+            ParserError::InvalidCodePoint(_) => ErrorCode::InvalidCodePoint,
+            ParserError::ExpectedCharInRange(..) => ErrorCode::ExpectedCharInRange,
+            ParserError::UnknownKeyword(..) => ErrorCode::UnknownKeyword,
+            ParserError::OutOfFuel => ErrorCode::OutOfFuel,
+            ParserError::DeadlineExceeded => ErrorCode::DeadlineExceeded,
+            ParserError::Cancelled => ErrorCode::Cancelled,
+            ParserError::NoProgress => ErrorCode::NoProgress,
+            ParserError::ChecksumMismatch => ErrorCode::ChecksumMismatch,
+            ParserError::External(_) => ErrorCode::External,
+        }
+    }
+}
+
+/// The category of a [ParserError], which has a stable name for tools such as language servers and CI annotations
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ErrorCode {
+    ExpectedLiteral,
+    ExpectedToken,
+    UnexpectedEndOfFile,
+    IntegerOverflow,
+    InvalidEscape,
+    InvalidCodePoint,
+    ExpectedCharInRange,
+    UnknownKeyword,
+    OutOfFuel,
+    DeadlineExceeded,
+    Cancelled,
+    NoProgress,
+    External,
+    ChecksumMismatch,
+}
+
+impl ErrorCode {
+    /// Every error code, in the order they were added
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::ExpectedLiteral,
+        ErrorCode::ExpectedToken,
+        ErrorCode::UnexpectedEndOfFile,
+        ErrorCode::IntegerOverflow,
+        ErrorCode::InvalidEscape,
+        ErrorCode::InvalidCodePoint,
+        ErrorCode::ExpectedCharInRange,
+        ErrorCode::UnknownKeyword,
+        ErrorCode::OutOfFuel,
+        ErrorCode::DeadlineExceeded,
+        ErrorCode::Cancelled,
+        ErrorCode::NoProgress,
+        ErrorCode::External,
+        ErrorCode::ChecksumMismatch,
+    ];
+
+    /// Get the stable name of the code, such as `expected-literal`, which is never changed once added
+    pub fn as_str(self) -> &'static str {
+        match self {
+            ErrorCode::ExpectedLiteral => "expected-literal",
+            ErrorCode::ExpectedToken => "expected-token",
+            ErrorCode::UnexpectedEndOfFile => "unexpected-eof",
+            ErrorCode::IntegerOverflow => "integer-overflow",
+            ErrorCode::InvalidEscape => "invalid-escape",
+            ErrorCode::InvalidCodePoint => "invalid-code-point",
+            ErrorCode::ExpectedCharInRange => "expected-char-in-range",
+            ErrorCode::UnknownKeyword => "unknown-keyword",
+            ErrorCode::OutOfFuel => "out-of-fuel",
+            ErrorCode::DeadlineExceeded => "deadline-exceeded",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::NoProgress => "no-progress",
+            ErrorCode::External => "external",
+            ErrorCode::ChecksumMismatch => "checksum-mismatch",
+        }
+    }
+
+    /// Get the code with the given stable name
+    pub fn from_name(name: &str) -> Option<ErrorCode> {
+        ErrorCode::ALL
+            .iter()
+            .copied()
+            .find(|code| code.as_str() == name)
+    }
+}
+
+impl Display for ErrorCode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// An arbitrary error wrapped by [ParserError::External], which is shared so that [ParserError] can still be cloned,
+/// and compares equal only to clones of itself
+#[derive(Debug, Clone)]
+pub struct ExternalError(pub Arc<dyn Error + Send + Sync>);
+
+impl PartialEq for ExternalError {
+    fn eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.0, &other.0)
+    }
+}
+
+impl Display for ExternalError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.0.fmt(f)
+    }
 }
 
 impl Display for ParserError {
@@ -13,8 +149,77 @@ impl Display for ParserError {
             ParserError::ExpectedLiteral(s) => write!(f, "Expected literal: '{s}'"),
             ParserError::ExpectedToken(s) => write!(f, "Expected {s}"),
             ParserError::UnexpectedEndOfFile => write!(f, "Unexpected end of file"),
+            ParserError::IntegerOverflow => write!(f, "Integer overflow"),
+            ParserError::InvalidEscape(c) => write!(f, "Invalid escape sequence: '\\{c}'"),
+            ParserError::InvalidCodePoint(n) => write!(f, "Invalid code point: U+{n:04X}"),
+            ParserError::ExpectedCharInRange(start, end) => {
+                write!(f, "Expected character in range ")?;
+                match start {
+                    Bound::Included(c) => write!(f, "{c:?}")?,
+                    Bound::Excluded(c) => write!(f, "{c:?} (exclusive)")?,
+                    Bound::Unbounded => {}
+                }
+                match end {
+                    Bound::Included(c) => write!(f, "..={c:?}"),
+                    Bound::Excluded(c) => write!(f, "..{c:?}"),
+                    Bound::Unbounded => write!(f, ".."),
+                }
+            }
+            ParserError::NoProgress => {
+                write!(f, "Repeated parser succeeded without consuming any input")
+            }
+            ParserError::ChecksumMismatch => write!(f, "Checksum mismatch"),
+            ParserError::OutOfFuel => write!(f, "Parser ran out of fuel"),
+            ParserError::DeadlineExceeded => write!(f, "Parser deadline passed"),
+            ParserError::Cancelled => write!(f, "Parser was cancelled"),
+            ParserError::External(err) => err.fmt(f),
+            ParserError::UnknownKeyword(word, suggestions) => {
+                write!(f, "Unknown keyword '{word}'")?;
+                for (i, suggestion) in suggestions.iter().enumerate() {
+                    match i {
+                        0 => write!(f, ", did you mean '{suggestion}'")?,
+                        i if i == suggestions.len() - 1 => write!(f, " or '{suggestion}'")?,
+                        _ => write!(f, ", '{suggestion}'")?,
+                    }
+                }
+                if !suggestions.is_empty() {
+                    write!(f, "?")?;
+                }
+                Ok(())
+            }
         }
     }
 }
 
-impl Error for ParserError {}
+impl Error for ParserError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParserError::External(err) => err.0.source(),
+            _ => None,
+        }
+    }
+}
+
+/// Serializes errors as a struct of their stable [code](ParserError::code) and their message, and codes as their
+/// stable names
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use serde::{ser::SerializeStruct, Serialize, Serializer};
+
+    use super::{ErrorCode, ParserError};
+
+    impl Serialize for ErrorCode {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            serializer.serialize_str(self.as_str())
+        }
+    }
+
+    impl Serialize for ParserError {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut state = serializer.serialize_struct("ParserError", 2)?;
+            state.serialize_field("code", &self.code())?;
+            state.serialize_field("message", &self.to_string())?;
+            state.end()
+        }
+    }
+}
@@ -0,0 +1,60 @@
+use crate::{
+    advance, error::ParserError, literal, matching_char, repeating, try_parse, ParserResult,
+};
+
+/// Simple escapes shared by most C-like languages
+pub const COMMON_ESCAPES: &[(char, char)] = &[
+    ('n', '\n'),
+    ('t', '\t'),
+    ('r', '\r'),
+    ('\\', '\\'),
+    ('\'', '\''),
+    ('"', '"'),
+];
+
+fn hex_digits(min: usize, max: usize, input: &str) -> ParserResult<'_, u32, ParserError> {
+    repeating(
+        |s| matching_char("hex digit", |c| c.is_ascii_hexdigit(), s),
+        min..=max,
+        input,
+    )
+    .map(|digits| {
+        digits
+            .into_iter()
+            .fold(0, |n, d| n * 16 + d.to_digit(16).unwrap())
+    })
+}
+
+/// Convert a code point, reporting invalid ones at the start of the escape
+fn code_point<'a>(n: u32, start: &'a str, rest: &'a str) -> ParserResult<'a, char, ParserError> {
+    match char::from_u32(n) {
+        Some(c) => ParserResult::from_val(c, rest),
+        None => ParserResult::from_err(ParserError::InvalidCodePoint(n), start),
+    }
+}
+
+/// Parse a backslash escape sequence into the character it represents
+///
+/// The `simple` pairs map the character after the backslash to its replacement and are checked first.
+/// Otherwise `\0`, `\xNN` (any code point up to `0xFF`) and `\u{N}` (one to six hex digits) are supported.
+pub fn escape<'a>(simple: &[(char, char)], input: &'a str) -> ParserResult<'a, char, ParserError> {
+    let (_, s) = try_parse!(literal("\\", input));
+    let (c, s) = try_parse!(advance(s));
+    if let Some(&(_, replacement)) = simple.iter().find(|(from, _)| *from == c) {
+        return ParserResult::from_val(replacement, s);
+    }
+    match c {
+        '0' => ParserResult::from_val('\0', s),
+        'x' => {
+            let (n, rest) = try_parse!(hex_digits(2, 2, s));
+            code_point(n, input, rest)
+        }
+        'u' => {
+            let (_, s) = try_parse!(literal("{", s));
+            let (n, s) = try_parse!(hex_digits(1, 6, s));
+            let (_, rest) = try_parse!(literal("}", s));
+            code_point(n, input, rest)
+        }
+        c => ParserResult::from_err(ParserError::InvalidEscape(c), input),
+    }
+}
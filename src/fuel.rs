@@ -0,0 +1,74 @@
+use std::cell::Cell;
+
+use crate::{error::ParserError, Parser, ParserResult};
+
+thread_local! {
+    /// The fuel left for the active [with_fuel] call on this thread, if any
+    static FUEL: Cell<Option<usize>> = const { Cell::new(None) };
+    /// Whether a fueled parser has been refused since the active [with_fuel] call started
+    static EXHAUSTED: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Run a function with a budget of `fuel` fueled parser invocations on this thread, returning `None` if it ran out
+///
+/// Once the fuel runs out every fueled parser fails with [ParserError::OutOfFuel], and the result is discarded even if
+/// an alternative which isn't fueled succeeded afterwards, so an untrusted input can't take longer than the budget
+/// allows to be rejected. Nested calls can't raise an enclosing budget: they get the smaller of the two, the fuel they
+/// spend is taken from the enclosing budget, and running out inside them also counts as running out outside them.
+pub fn with_fuel<R>(fuel: usize, f: impl FnOnce() -> R) -> Option<R> {
+    let outer = FUEL.with(Cell::get);
+    let fuel = outer.map_or(fuel, |outer| outer.min(fuel));
+    let scope = Scope {
+        outer,
+        fuel,
+        outer_exhausted: EXHAUSTED.with(|c| c.replace(false)),
+    };
+    FUEL.with(|c| c.set(Some(fuel)));
+    let res = f();
+    let exhausted = EXHAUSTED.with(Cell::get);
+    drop(scope);
+    (!exhausted).then_some(res)
+}
+
+/// The state of the [with_fuel] call enclosing an active one, which is restored when it ends, even by unwinding
+struct Scope {
+    outer: Option<usize>,
+    /// The fuel the active call started with
+    fuel: usize,
+    outer_exhausted: bool,
+}
+
+impl Drop for Scope {
+    fn drop(&mut self) {
+        let left = FUEL.with(Cell::get).unwrap_or(0);
+        let spent = self.fuel - left;
+        FUEL.with(|c| c.set(self.outer.map(|outer| outer - spent)));
+        EXHAUSTED.with(|c| c.set(self.outer_exhausted || c.get()));
+    }
+}
+
+/// The fuel left for the active [with_fuel] call on this thread, or `None` if there isn't one
+pub fn remaining() -> Option<usize> {
+    FUEL.with(Cell::get)
+}
+
+/// Run a parser, spending a unit of fuel if a [with_fuel] call is active and failing with [ParserError::OutOfFuel]
+/// if there is none left
+pub fn fueled<'a, T, E: From<ParserError>>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let refused = FUEL.with(|c| match c.get() {
+        Some(0) => true,
+        Some(n) => {
+            c.set(Some(n - 1));
+            false
+        }
+        None => false,
+    });
+    if refused {
+        EXHAUSTED.with(|c| c.set(true));
+        return ParserResult::from_err(ParserError::OutOfFuel.into(), input);
+    }
+    parser.parse(input)
+}
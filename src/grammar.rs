@@ -0,0 +1,187 @@
+use crate::{error::ParserError, literal, matching_char, ParserResult};
+
+/// A terminal which can appear in a [parser!](crate::parser!) grammar
+pub trait Atom {
+    /// Parse this terminal, using `name` to describe it in errors
+    fn parse_atom<'a>(
+        &self,
+        name: &'static str,
+        input: &'a str,
+    ) -> ParserResult<'a, (), ParserError>;
+}
+
+impl Atom for &'static str {
+    fn parse_atom<'a>(
+        &self,
+        _name: &'static str,
+        input: &'a str,
+    ) -> ParserResult<'a, (), ParserError> {
+        literal(*self, input).map(|_| ())
+    }
+}
+
+impl Atom for char {
+    fn parse_atom<'a>(
+        &self,
+        name: &'static str,
+        input: &'a str,
+    ) -> ParserResult<'a, (), ParserError> {
+        matching_char(name, |c| c == *self, input).map(|_| ())
+    }
+}
+
+/// Define parsing functions using an EBNF-like grammar, each of which returns the slice matched by its rule
+///
+/// Rules are sequences of terms, separated into alternatives with `|`. A term is a string or char literal, an inclusive
+/// char range like `'a'..='z'`, the name of another rule or parsing function using [ParserError], or a parenthesized
+/// group, optionally followed by `*`, `+` or `?`. Alternatives are tried in order, backtracking to the start of the rule.
+/// Example:
+/// ```
+/// use crochet::*;
+/// parser! {
+///     rule number = ('0'..='9')+;
+///     rule term = number | '(' expr ')';
+///     pub rule expr = term (('+' | '-') term)*;
+/// }
+/// assert_eq!(expr("1+(2-3)").unwrap(), "1+(2-3)");
+/// ```
+/// A `grammar` line before the rules defines a constant holding their source, which can be read with
+/// [Grammar::new](crate::ebnf::Grammar::new) to export the rule graph with
+/// [Grammar::to_dot](crate::ebnf::Grammar::to_dot). This only works when every rule refers to rules of the same
+/// macro rather than to other parsing functions.
+/// ```
+/// use crochet::{ebnf::Grammar, *};
+/// parser! {
+///     grammar SUM;
+///     rule digit = '0'..='9';
+///     pub rule sum = digit ('+' digit)*;
+/// }
+/// let dot = Grammar::new(SUM).unwrap().to_dot();
+/// assert!(dot.contains("\"sum\" -> \"digit\""));
+/// ```
+#[macro_export]
+macro_rules! parser {
+    () => {};
+    (@rule [$(#[$meta:meta])* $vis:vis $name:ident] [$($body:tt)*] ; $($rest:tt)*) => {
+        $(#[$meta])*
+        $vis fn $name(input: &str) -> $crate::ParserResult<'_, &str, $crate::error::ParserError> {
+            let res = $crate::parser!(@alt input; [] [] $($body)*);
+            res.parsed_slice(input)
+        }
+        $crate::parser! { $($rest)* }
+    };
+    (@rule [$($head:tt)*] [$($body:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::parser! { @rule [$($head)*] [$($body)* $t] $($rest)* }
+    };
+    (@alt $s:ident; [$($cur:tt)*] [$($done:tt)*] | $($rest:tt)*) => {
+        $crate::parser!(@alt $s; [] [$($done)* [$($cur)*]] $($rest)*)
+    };
+    (@alt $s:ident; [$($cur:tt)*] [$($done:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::parser!(@alt $s; [$($cur)* $t] [$($done)*] $($rest)*)
+    };
+    (@alt $s:ident; [$($cur:tt)*] [$($done:tt)*]) => {
+        $crate::parser!(@or $s; $($done)* [$($cur)*])
+    };
+    (@or $s:ident; [$($first:tt)*]) => {
+        $crate::parser!(@seq $s; $($first)*)
+    };
+    (@or $s:ident; [$($first:tt)*] $($rest:tt)+) => {{
+        let res = $crate::parser!(@seq $s; $($first)*);
+        if res.is_ok() {
+            res
+        } else {
+            $crate::parser!(@or $s; $($rest)+)
+        }
+    }};
+    (@seq $s:ident;) => {
+        $crate::ParserResult::<(), $crate::error::ParserError>::from_val((), $s)
+    };
+    (@seq $s:ident; $a:literal ..= $b:literal $($rest:tt)*) => {
+        $crate::parser!(@post $s; [range $a $b] $($rest)*)
+    };
+    (@seq $s:ident; $atom:tt $($rest:tt)*) => {
+        $crate::parser!(@post $s; [atom $atom] $($rest)*)
+    };
+    (@post $s:ident; [$($atom:tt)*] * $($rest:tt)*) => {
+        $crate::parser!(@then $s; (@many $s; [$($atom)*]) $($rest)*)
+    };
+    (@post $s:ident; [$($atom:tt)*] + $($rest:tt)*) => {
+        $crate::parser!(@then $s; (@some $s; [$($atom)*]) $($rest)*)
+    };
+    (@post $s:ident; [$($atom:tt)*] ? $($rest:tt)*) => {
+        $crate::parser!(@then $s; (@maybe $s; [$($atom)*]) $($rest)*)
+    };
+    (@post $s:ident; [$($atom:tt)*] $($rest:tt)*) => {
+        $crate::parser!(@then $s; (@one $s; [$($atom)*]) $($rest)*)
+    };
+    (@then $s:ident; ($($term:tt)*) $($rest:tt)*) => {{
+        let res = $crate::parser!($($term)*);
+        if res.is_ok() {
+            let $s = res.source;
+            $crate::parser!(@seq $s; $($rest)*)
+        } else {
+            res
+        }
+    }};
+    (@many $s:ident; [$($atom:tt)*]) => {{
+        let mut s = $s;
+        loop {
+            let res = $crate::parser!(@one s; [$($atom)*]);
+            if res.is_ok() && res.source.len() < s.len() {
+                s = res.source;
+            } else {
+                break;
+            }
+        }
+        $crate::ParserResult::<(), $crate::error::ParserError>::from_val((), s)
+    }};
+    (@some $s:ident; [$($atom:tt)*]) => {{
+        let res = $crate::parser!(@one $s; [$($atom)*]);
+        if res.is_ok() {
+            let s = res.source;
+            $crate::parser!(@many s; [$($atom)*])
+        } else {
+            res
+        }
+    }};
+    (@maybe $s:ident; [$($atom:tt)*]) => {{
+        let res = $crate::parser!(@one $s; [$($atom)*]);
+        if res.is_ok() {
+            res
+        } else {
+            $crate::ParserResult::<(), $crate::error::ParserError>::from_val((), $s)
+        }
+    }};
+    (@one $s:ident; [range $a:literal $b:literal]) => {
+        $crate::matching_char(stringify!($a..=$b), |c| ($a..=$b).contains(&c), $s).map(|_| ())
+    };
+    (@one $s:ident; [atom ($($group:tt)*)]) => {
+        $crate::parser!(@alt $s; [] [] $($group)*)
+    };
+    (@one $s:ident; [atom $lit:literal]) => {
+        $crate::grammar::Atom::parse_atom(&$lit, stringify!($lit), $s)
+    };
+    (@one $s:ident; [atom $rule:ident]) => {
+        $rule($s).map(|_| ())
+    };
+    (@source [$($out:tt)*]) => {
+        stringify!($($out)*)
+    };
+    (@source [$($out:tt)*] # [$($meta:tt)*] $($rest:tt)*) => {
+        $crate::parser!(@source [$($out)*] $($rest)*)
+    };
+    (@source [$($out:tt)*] $vis:vis rule $($rest:tt)*) => {
+        $crate::parser!(@source [$($out)*] $($rest)*)
+    };
+    (@source [$($out:tt)*] $t:tt $($rest:tt)*) => {
+        $crate::parser!(@source [$($out)* $t] $($rest)*)
+    };
+    ($(#[$meta:meta])* $vis:vis grammar $name:ident; $($rest:tt)*) => {
+        $(#[$meta])*
+        $vis const $name: &str = $crate::parser!(@source [] $($rest)*);
+        $crate::parser! { $($rest)* }
+    };
+    ($(#[$meta:meta])* $vis:vis rule $name:ident = $($rest:tt)*) => {
+        $crate::parser! { @rule [$(#[$meta])* $vis $name] [] $($rest)* }
+    };
+}
@@ -0,0 +1,29 @@
+use unicode_segmentation::UnicodeSegmentation;
+
+use crate::{error::ParserError, ParserResult};
+
+/// Parse a single extended grapheme cluster, which is what a user sees as one character, such as an emoji with
+/// modifiers or a letter with combining accents
+pub fn advance_grapheme(input: &str) -> ParserResult<'_, &str, ParserError> {
+    match input.graphemes(true).next() {
+        Some(grapheme) => ParserResult::from_val(grapheme, &input[grapheme.len()..]),
+        None => ParserResult::from_err(ParserError::UnexpectedEndOfFile, input),
+    }
+}
+
+/// Take exactly `n` extended grapheme clusters
+pub fn take_graphemes(n: usize, input: &str) -> ParserResult<'_, &str, ParserError> {
+    let mut graphemes = input.grapheme_indices(true);
+    match graphemes.nth(n) {
+        Some((end, _)) => ParserResult::from_val(&input[..end], &input[end..]),
+        None if input.graphemes(true).count() == n => {
+            ParserResult::from_val(input, &input[input.len()..])
+        }
+        None => ParserResult::from_err(ParserError::UnexpectedEndOfFile, &input[input.len()..]),
+    }
+}
+
+/// Count the extended grapheme clusters in a string, which is its length as a user would see it
+pub fn grapheme_len(s: &str) -> usize {
+    s.graphemes(true).count()
+}
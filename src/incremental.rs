@@ -0,0 +1,149 @@
+use std::{any::Any, cell::RefCell, collections::HashMap};
+
+use crate::{Needed, Parser, ParserResult, ParserResultType};
+
+/// How a memoized rule ended, with its value or error stored without its type
+enum Memoized {
+    Ok(Box<dyn Any>),
+    Err(Box<dyn Any>),
+    Incomplete(Needed),
+}
+
+/// A memoized attempt to run a rule
+struct Entry {
+    result: Memoized,
+    /// The byte offset the rule stopped at
+    end: usize,
+    /// The byte offset up to which the rule may have looked at the input, which is one past the end of the input if it
+    /// looked for more
+    examined: usize,
+}
+
+/// A table of results for memoized rules, keyed by rule name and starting offset, which can be kept across edits to
+/// the input so that only the rules overlapping an edit are parsed again
+///
+/// Values and errors are stored owned, so memoized rules must produce types which don't borrow from the input. A rule
+/// is assumed to look at most one character past where it stopped; rules which look further ahead should be split so
+/// that the lookahead happens inside a nested memoized rule.
+#[derive(Default)]
+pub struct Memo {
+    entries: RefCell<HashMap<(&'static str, usize), Entry>>,
+    /// The furthest offset examined by each memoized rule currently running, innermost last
+    running: RefCell<Vec<usize>>,
+}
+
+impl Memo {
+    /// Create an empty memo table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The number of memoized results
+    pub fn len(&self) -> usize {
+        self.entries.borrow().len()
+    }
+
+    /// Whether there are no memoized results
+    pub fn is_empty(&self) -> bool {
+        self.entries.borrow().is_empty()
+    }
+
+    /// Discard every memoized result
+    pub fn clear(&mut self) {
+        self.entries.get_mut().clear();
+    }
+
+    /// Update the table after `old_len` bytes at `offset` were replaced with `new_len` bytes, discarding the results
+    /// which examined the replaced bytes and shifting the results after them
+    pub fn edit(&mut self, offset: usize, old_len: usize, new_len: usize) {
+        let old_end = offset + old_len;
+        let shift = |n: usize| n - old_len + new_len;
+        let entries = std::mem::take(self.entries.get_mut());
+        *self.entries.get_mut() = entries
+            .into_iter()
+            .filter_map(|((name, start), entry)| {
+                if start >= old_end && (start > offset || old_len == 0) {
+                    let entry = Entry {
+                        end: shift(entry.end),
+                        examined: shift(entry.examined),
+                        ..entry
+                    };
+                    Some(((name, shift(start)), entry))
+                } else if start < offset
+                    && entry.examined <= offset
+                    && matches!(entry.result, Memoized::Ok(_))
+                {
+                    Some(((name, start), entry))
+                } else {
+                    None
+                }
+            })
+            .collect();
+    }
+
+    /// Run a parser under a rule name, reusing its result if it was already run from the same offset of `original`
+    pub fn memoized<'a, T, E>(
+        &self,
+        name: &'static str,
+        parser: impl Parser<'a, T, E>,
+        original: &'a str,
+        input: &'a str,
+    ) -> ParserResult<'a, T, E>
+    where
+        T: Clone + 'static,
+        E: Clone + 'static,
+    {
+        let start = original.len() - input.len();
+        if let Some(res) = self.cached(name, start, original) {
+            return res;
+        }
+        self.running.borrow_mut().push(start);
+        let res = parser.parse(input);
+        let nested = self.running.borrow_mut().pop().unwrap_or(start);
+        let end = original.len() - res.source.len();
+        // A rule which stopped at the end of the input saw that it ended, which appending text would change
+        let lookahead = original[end..].chars().next().map_or(1, char::len_utf8);
+        let result = match &res.typ {
+            ParserResultType::Ok(val) => Memoized::Ok(Box::new(val.clone())),
+            ParserResultType::Err(err) => Memoized::Err(Box::new(err.clone())),
+            ParserResultType::Incomplete(needed) => Memoized::Incomplete(*needed),
+        };
+        let examined = nested.max(end + lookahead);
+        self.record(examined);
+        self.entries.borrow_mut().insert(
+            (name, start),
+            Entry {
+                result,
+                end,
+                examined,
+            },
+        );
+        res
+    }
+
+    /// Get a memoized result, recording how far it examined the input for the rule running it
+    fn cached<'a, T: Clone + 'static, E: Clone + 'static>(
+        &self,
+        name: &'static str,
+        start: usize,
+        original: &'a str,
+    ) -> Option<ParserResult<'a, T, E>> {
+        let entries = self.entries.borrow();
+        let entry = entries.get(&(name, start))?;
+        let source = original.get(entry.end..)?;
+        let res = match &entry.result {
+            Memoized::Ok(val) => ParserResult::from_val(val.downcast_ref::<T>()?.clone(), source),
+            Memoized::Err(err) => ParserResult::from_err(err.downcast_ref::<E>()?.clone(), source),
+            Memoized::Incomplete(needed) => ParserResult::incomplete_needing(*needed, source),
+        };
+        self.record(entry.examined);
+        Some(res)
+    }
+
+    /// Extend the offset examined by the innermost running rule
+    fn record(&self, examined: usize) {
+        if let Some(outer) = self.running.borrow_mut().last_mut() {
+            *outer = (*outer).max(examined);
+        }
+    }
+}
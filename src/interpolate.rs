@@ -0,0 +1,96 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, literal, try_parse, Parser, ParserResult};
+
+/// A part of an interpolated string
+#[derive(Debug, Clone, PartialEq)]
+pub enum Segment<'a, T> {
+    /// Literal text, which is only owned if it contained escaped markers
+    Literal(Cow<'a, str>),
+    /// An embedded expression
+    Expr(T),
+}
+
+/// Splits a string body such as `Hello ${name}!` into literal text and embedded expressions between markers
+#[derive(Debug, Clone, Copy)]
+pub struct Interpolation {
+    open: &'static str,
+    close: &'static str,
+    doubled: bool,
+}
+
+impl Interpolation {
+    /// Create an interpolation with expressions between the given markers, such as `${` and `}`
+    pub fn new(open: &'static str, close: &'static str) -> Self {
+        Self {
+            open,
+            close,
+            doubled: false,
+        }
+    }
+
+    /// Treat markers written twice in literal text, such as `{{` and `}}`, as escapes for a single marker
+    pub fn doubled_escapes(mut self) -> Self {
+        self.doubled = true;
+        self
+    }
+
+    /// Parse the entire input into segments, using `expr` to parse each embedded expression
+    ///
+    /// An expression which isn't followed by the closing marker is reported where the expression started.
+    pub fn parse<'a, T, E: From<ParserError>>(
+        &self,
+        expr: impl Parser<'a, T, E>,
+        input: &'a str,
+    ) -> ParserResult<'a, Vec<Segment<'a, T>>, E> {
+        let mut segments = vec![];
+        let mut text = Cow::Borrowed("");
+        let mut start = 0;
+        let mut s = input;
+        while !s.is_empty() {
+            let pos = input.len() - s.len();
+            let escaped = [self.open, self.close]
+                .into_iter()
+                .find(|marker| self.doubled && s.starts_with(&marker.repeat(2)));
+            if let Some(marker) = escaped {
+                text.to_mut().push_str(&input[start..pos]);
+                text.to_mut().push_str(marker);
+                s = &s[marker.len() * 2..];
+                start = input.len() - s.len();
+            } else if let Some(body) = s.strip_prefix(self.open) {
+                push_literal(&mut segments, text, &input[start..pos]);
+                text = Cow::Borrowed("");
+                let (val, rest) = try_parse!(expr.parse(body));
+                let closed = literal(self.close, rest);
+                if !closed.is_ok() {
+                    return ParserResult::from_err(
+                        ParserError::ExpectedLiteral(self.close).into(),
+                        body,
+                    );
+                }
+                segments.push(Segment::Expr(val));
+                s = closed.source;
+                start = input.len() - s.len();
+            } else {
+                let c = s.chars().next().map_or(0, char::len_utf8);
+                s = &s[c..];
+            }
+        }
+        push_literal(&mut segments, text, &input[start..]);
+        ParserResult::from_val(segments, s)
+    }
+}
+
+/// Add the literal text before an expression or the end of the input, if there is any
+fn push_literal<'a, T>(segments: &mut Vec<Segment<'a, T>>, text: Cow<'a, str>, rest: &'a str) {
+    let text = match text {
+        Cow::Borrowed("") => Cow::Borrowed(rest),
+        mut text => {
+            text.to_mut().push_str(rest);
+            text
+        }
+    };
+    if !text.is_empty() {
+        segments.push(Segment::Literal(text));
+    }
+}
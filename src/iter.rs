@@ -1,8 +1,15 @@
 use std::marker::PhantomData;
 
-use crate::{Parser, ParserResult};
+use std::ops::Bound;
 
-pub(crate) struct ParsIter<'a, 'b, T, E, P>
+use crate::{
+    container::Container, error::ParserError, no_progress, try_parse, Parser, ParserResult,
+    ParserResultType,
+};
+
+/// A [ParsingIterator] which repeatedly parses elements using a single parser, ending with
+/// [NoProgress](ParserError::NoProgress) at any element which consumes no input
+pub struct ParsIter<'a, 'b, T, E, P>
 where
     P: Parser<'a, T, E>,
 {
@@ -10,9 +17,12 @@ where
     pub(crate) source: &'b mut &'a str,
     pub(crate) parser: P,
     pub(crate) err: bool,
+    /// Whether iteration ended on an element which consumed no input
+    pub(crate) stalled: bool,
 }
 
-pub(crate) struct ParsIterDelim<'a, 'b, Elem, Delim, Error, PElem, PDelim>
+/// A [ParsingIterator] which parses elements separated by delimiters
+pub struct ParsIterDelim<'a, 'b, Elem, Delim, Error, PElem, PDelim>
 where
     PElem: Parser<'a, Elem, Error>,
     PDelim: Parser<'a, Delim, Error>,
@@ -22,10 +32,22 @@ where
     pub(crate) elem_parser: PElem,
     pub(crate) delim_parser: PDelim,
     pub(crate) err: bool,
+    /// Whether iteration ended on an element which consumed no input
+    pub(crate) stalled: bool,
     pub(crate) first: bool,
 }
 
-impl<'a, 'b, T, E, P: Parser<'a, T, E>> Iterator for ParsIter<'a, 'b, T, E, P> {
+impl<'a, 'b, T, E, P: Parser<'a, T, E>> ParsIter<'a, 'b, T, E, P> {
+    /// Parse the next element without advancing the source, returning `None` once iteration has ended
+    pub fn peek(&self) -> Option<ParserResult<'a, T, E>> {
+        if self.err {
+            return None;
+        }
+        Some(self.parser.parse(self.source))
+    }
+}
+
+impl<'a, 'b, T, E: From<ParserError>, P: Parser<'a, T, E>> Iterator for ParsIter<'a, 'b, T, E, P> {
     type Item = ParserResult<'a, T, E>;
 
     fn next(&mut self) -> Option<Self::Item> {
@@ -33,18 +55,50 @@ impl<'a, 'b, T, E, P: Parser<'a, T, E>> Iterator for ParsIter<'a, 'b, T, E, P> {
             return None;
         }
         let res = self.parser.parse(self.source);
-        if res.is_ok() {
-            *self.source = res.source;
-        } else {
+        if !res.is_ok() {
             self.err = true;
+        } else if no_progress(self.source, res.source, Bound::Unbounded) {
+            // An element which consumed nothing would be parsed again forever
+            self.err = true;
+            self.stalled = true;
+            return Some(ParserResult::from_err(
+                ParserError::NoProgress.into(),
+                self.source,
+            ));
+        } else {
+            *self.source = res.source;
         }
         Some(res)
     }
 }
 
+impl<'a, 'b, Elem, Delim, Error, PElem, PDelim>
+    ParsIterDelim<'a, 'b, Elem, Delim, Error, PElem, PDelim>
+where
+    PElem: Parser<'a, Elem, Error>,
+    PDelim: Parser<'a, Delim, Error>,
+{
+    /// Parse the next element, along with its preceding delimiter, without advancing the source, returning `None` once iteration has ended
+    pub fn peek(&self) -> Option<ParserResult<'a, Elem, Error>> {
+        if self.err {
+            return None;
+        }
+        if self.first {
+            return Some(self.elem_parser.parse(self.source));
+        }
+        let res = self
+            .delim_parser
+            .parse(self.source)
+            .and(|s| self.elem_parser.parse(s))
+            .map(|(_, elem)| elem);
+        Some(res)
+    }
+}
+
 impl<'a, 'b, Elem, Delim, Error, PElem, PDelim> Iterator
     for ParsIterDelim<'a, 'b, Elem, Delim, Error, PElem, PDelim>
 where
+    Error: From<ParserError>,
     PElem: Parser<'a, Elem, Error>,
     PDelim: Parser<'a, Delim, Error>,
 {
@@ -54,8 +108,8 @@ where
         if self.err {
             return None;
         }
-        let res = if self.first {
-            self.first = false;
+        let first = std::mem::replace(&mut self.first, false);
+        let res = if first {
             self.elem_parser.parse(self.source)
         } else {
             self.delim_parser
@@ -63,9 +117,75 @@ where
                 .and(|s| self.elem_parser.parse(s))
                 .map(|(_, elem)| elem)
         };
-        *self.source = res.source;
-        if res.is_err() {
+        if !res.is_ok() {
             self.err = true;
+        } else if !first && no_progress(self.source, res.source, Bound::Unbounded) {
+            self.err = true;
+            self.stalled = true;
+            return Some(ParserResult::from_err(
+                ParserError::NoProgress.into(),
+                self.source,
+            ));
+        } else {
+            *self.source = res.source;
+        }
+        Some(res)
+    }
+}
+
+/// A [ParsingIterator] which owns its position in the source, rather than advancing a borrowed slice
+pub struct OwnedParsIter<'a, T, E, P>
+where
+    P: Parser<'a, T, E>,
+{
+    pub(crate) phantom: PhantomData<(T, E)>,
+    pub(crate) original: &'a str,
+    pub(crate) offset: usize,
+    pub(crate) parser: P,
+    pub(crate) err: bool,
+    /// Whether iteration ended on an element which consumed no input
+    pub(crate) stalled: bool,
+}
+
+impl<'a, T, E, P: Parser<'a, T, E>> OwnedParsIter<'a, T, E, P> {
+    /// The input which has not yet been parsed
+    pub fn remaining(&self) -> &'a str {
+        &self.original[self.offset..]
+    }
+
+    /// The byte offset of the remaining input within the original source
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Parse the next element without advancing, returning `None` once iteration has ended
+    pub fn peek(&self) -> Option<ParserResult<'a, T, E>> {
+        if self.err {
+            return None;
+        }
+        Some(self.parser.parse(self.remaining()))
+    }
+}
+
+impl<'a, T, E: From<ParserError>, P: Parser<'a, T, E>> Iterator for OwnedParsIter<'a, T, E, P> {
+    type Item = ParserResult<'a, T, E>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.err {
+            return None;
+        }
+        let res = self.parser.parse(self.remaining());
+        if !res.is_ok() {
+            self.err = true;
+        } else if no_progress(self.remaining(), res.source, Bound::Unbounded) {
+            self.err = true;
+            self.stalled = true;
+            return Some(ParserResult::from_err(
+                ParserError::NoProgress.into(),
+                self.remaining(),
+            ));
+        } else {
+            self.offset = self.original.len() - res.source.len();
         }
         Some(res)
     }
@@ -76,7 +196,10 @@ pub trait ParsingIterator<'a, T: 'a, E: 'a>: Iterator<Item = ParserResult<'a, T,
     fn new<'b>(
         parser: impl Parser<'a, T, E> + 'b,
         source: &'b mut &'a str,
-    ) -> impl ParsingIterator<'a, T, E> {
+    ) -> impl ParsingIterator<'a, T, E>
+    where
+        E: From<ParserError>,
+    {
         crate::iter(parser, source)
     }
 
@@ -93,9 +216,9 @@ pub trait ParsingIterator<'a, T: 'a, E: 'a>: Iterator<Item = ParserResult<'a, T,
     where
         Self: Sized,
     {
-        let (first, source) = self
+        let (first, source) = try_parse!(self
             .next()
-            .expect("parsing iterator must contain either an element or an error")?;
+            .expect("parsing iterator must contain either an element or an error"));
         let chain = std::iter::once(first).chain(self.ok());
         ParserResult::from_val(chain, source)
     }
@@ -108,7 +231,7 @@ pub trait ParsingIterator<'a, T: 'a, E: 'a>: Iterator<Item = ParserResult<'a, T,
     where
         Self: Sized,
     {
-        self.map(move |e| e.map(|inner| f(inner)))
+        self.map(move |e| e.map(&mut f))
     }
 }
 
@@ -116,3 +239,151 @@ impl<'a, T: 'a, E: 'a, I> ParsingIterator<'a, T, E> for I where
     I: Iterator<Item = ParserResult<'a, T, E>>
 {
 }
+
+/// A [ParsingIterator] which knows the input it has yet to parse, so it can tell running out of input apart from
+/// failing to parse it
+pub trait SourceIterator<'a, T: 'a, E: 'a>: ParsingIterator<'a, T, E> {
+    /// The input which has not yet been parsed
+    fn remaining(&self) -> &'a str;
+
+    /// Whether iteration ended with [NoProgress](ParserError::NoProgress) on an element which consumed no input
+    fn stalled(&self) -> bool;
+
+    /// Consume the rest of the iterator, returning its terminal state along with the remaining input
+    ///
+    /// Iteration which stopped at the end of the input, or on an element which failed without consuming any input,
+    /// ended cleanly and gives [Ok]. Otherwise this gives the error which ended it.
+    /// Example:
+    /// ```
+    /// use crochet::{iter::SourceIterator, *};
+    /// let mut source = "aa";
+    /// let finished = iter(|s| literal("a", s), &mut source).finish();
+    /// assert!(finished.is_ok());
+    /// assert_eq!(finished.source, "");
+    ///
+    /// let mut source = "a,a;";
+    /// let finished = iter_delimited(|s| literal("a", s), |s| literal(",", s), &mut source).finish();
+    /// assert!(finished.is_ok());
+    /// assert_eq!(finished.source, ";");
+    ///
+    /// let mut source = "a,b";
+    /// let finished = iter_delimited(|s| literal("a", s), |s| literal(",", s), &mut source).finish();
+    /// assert!(finished.is_err());
+    /// assert_eq!(finished.source, "b");
+    /// ```
+    fn finish(mut self) -> ParserResult<'a, (), E>
+    where
+        Self: Sized,
+    {
+        loop {
+            let before = self.remaining();
+            let Some(res) = self.next() else {
+                return ParserResult::from_val((), before);
+            };
+            if res.is_ok() {
+                continue;
+            }
+            let clean = before.is_empty() || (res.is_err() && res.source.len() == before.len());
+            if clean && !self.stalled() {
+                return ParserResult::from_val((), before);
+            }
+            return res.map(|_| ());
+        }
+    }
+
+    /// Collect the elements into a [Container], failing with the error which ended iteration unless no input remained to be parsed
+    ///
+    /// An iterator which had already ended with input remaining fails with an [ExpectedToken](ParserError::ExpectedToken)
+    /// for the end of input, rather than leaving it unparsed.
+    fn try_collect<C: Container<T>>(mut self) -> ParserResult<'a, C, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        let mut elems = C::default();
+        while !self.remaining().is_empty() {
+            let Some(res) = self.next() else {
+                return ParserResult::from_err(ended_early().into(), self.remaining());
+            };
+            match res.typ {
+                ParserResultType::Ok(elem) => elems.add(elem),
+                _ => return res.map(|_| unreachable!()),
+            }
+        }
+        ParserResult::from_val(elems, self.remaining())
+    }
+
+    /// Turn `self` into an iterator over the result of each element, which ends without an error once no input remains
+    /// to be parsed, and otherwise ends after the error which stopped it, so it can be collected into a [Result]
+    ///
+    /// Running out of input partway through an element is reported as [UnexpectedEndOfFile](ParserError::UnexpectedEndOfFile),
+    /// and an iterator which had already ended with input remaining as in [try_collect](SourceIterator::try_collect).
+    fn results(mut self) -> impl Iterator<Item = Result<T, E>>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        let mut ended = false;
+        std::iter::from_fn(move || {
+            if ended || self.remaining().is_empty() {
+                return None;
+            }
+            let res = match self.next() {
+                Some(res) => match res.typ {
+                    ParserResultType::Ok(elem) => Ok(elem),
+                    ParserResultType::Err(err) => Err(err),
+                    ParserResultType::Incomplete(_) => Err(ParserError::UnexpectedEndOfFile.into()),
+                },
+                None => Err(ended_early().into()),
+            };
+            ended = res.is_err();
+            Some(res)
+        })
+    }
+}
+
+/// The error for a [SourceIterator] which ended without parsing all of its input
+fn ended_early() -> ParserError {
+    ParserError::ExpectedToken("end of input".into())
+}
+
+impl<'a, 'b, T: 'a, E: From<ParserError> + 'a, P: Parser<'a, T, E>> SourceIterator<'a, T, E>
+    for ParsIter<'a, 'b, T, E, P>
+{
+    fn remaining(&self) -> &'a str {
+        self.source
+    }
+
+    fn stalled(&self) -> bool {
+        self.stalled
+    }
+}
+
+impl<'a, 'b, Elem, Delim, Error, PElem, PDelim> SourceIterator<'a, Elem, Error>
+    for ParsIterDelim<'a, 'b, Elem, Delim, Error, PElem, PDelim>
+where
+    Elem: 'a,
+    Error: From<ParserError> + 'a,
+    PElem: Parser<'a, Elem, Error>,
+    PDelim: Parser<'a, Delim, Error>,
+{
+    fn remaining(&self) -> &'a str {
+        self.source
+    }
+
+    fn stalled(&self) -> bool {
+        self.stalled
+    }
+}
+
+impl<'a, T: 'a, E: From<ParserError> + 'a, P: Parser<'a, T, E>> SourceIterator<'a, T, E>
+    for OwnedParsIter<'a, T, E, P>
+{
+    fn remaining(&self) -> &'a str {
+        &self.original[self.offset..]
+    }
+
+    fn stalled(&self) -> bool {
+        self.stalled
+    }
+}
@@ -1,3 +1,5 @@
+pub mod events;
+
 use std::{collections::HashMap, num::ParseIntError};
 
 use crate::*;
@@ -28,37 +30,60 @@ impl From<ParserError> for JSONError {
     }
 }
 
-fn parse_num(s: &str) -> Result<JSONValue> {
-    let (neg, s) = literal("-", s).optional(s)?;
-    let neg = if neg.is_some() { -1 } else { 1 };
-    let (num, s) =
-        take_while("digit", |c| c.is_ascii_digit(), s).map(|s| s.parse::<i64>().unwrap())?;
-    if peek(s).ok().is_some_and(|c| c == '.') {
-        let (decimal, s) = literal(".", s)
-            .and(cur!(take_while <= "digit", |c| c.is_ascii_digit()))
-            .map_slice(|s| s.parse::<f64>().unwrap(), s)?;
-        let num = num as f64 + decimal;
-        ParserResult::from_val(JSONValue::Float(num * neg as f64), s)
-    } else {
-        ParserResult::from_val(JSONValue::Integer(num * neg), s)
+impl From<ParseIntError> for JSONError {
+    fn from(value: ParseIntError) -> Self {
+        JSONError::ParseInt(value)
     }
 }
 
-fn parse_str(s: &str) -> Result<String> {
-    let (_, mut s) = literal("\"", s)?;
+/// Recognize a number as RFC 8259 defines it, returning its slice: an optional `-`, an integer part without leading
+/// zeros, and an optional fraction and exponent which each need at least one digit
+fn number_slice(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let digits = |s| take_while("digit", |c| c.is_ascii_digit(), s);
+    let (_, s) = try_parse!(literal("-", input).optional(input));
+    let (_, s) = try_parse!(literal("0", s).map(|_| ()).or(
+        |s| {
+            matching_char("digit", |c| matches!(c, '1'..='9'), s)
+                .and(|s| digits(s).optional(s))
+                .map(|_| ())
+        },
+        s
+    ));
+    let (_, s) = try_parse!(literal(".", s).and(digits).optional(s));
+    let (_, s) = try_parse!(matching_char("exponent", |c| c == 'e' || c == 'E', s)
+        .and(|s| matching_char("sign", |c| c == '+' || c == '-', s).optional(s))
+        .and(digits)
+        .optional(s));
+    ParserResult::from_val(&input[..input.len() - s.len()], s)
+}
+
+fn parse_num(s: &str) -> Result<'_, JSONValue> {
+    let (num, rest) = try_parse!(number_slice(s));
+    let value = if num.contains(['.', 'e', 'E']) {
+        num::float(num).map(JSONValue::Float)
+    } else {
+        num::integer(num).map(JSONValue::Integer)
+    };
+    // The slice is exactly one number, so converting it consumes all of it
+    let source = if value.is_ok() { rest } else { s };
+    ParserResult { source, ..value }.err_into()
+}
+
+fn parse_str(s: &str) -> Result<'_, String> {
+    let (_, mut s) = try_parse!(literal("\"", s));
     let string: String = iter(
         |s| parse_esc(s).or(|s| matching_char("char", |c| c != '"', s), s),
         &mut s,
     )
     .ok()
     .collect();
-    let (_, s) = literal("\"", s)?;
+    let (_, s) = try_parse!(literal("\"", s));
     ParserResult::from_val(string, s)
 }
 
-fn parse_esc(s: &str) -> Result<char> {
-    let (_, s) = literal("\\", s)?;
-    let (c, s) = advance(s)?;
+fn parse_esc(s: &str) -> Result<'_, char> {
+    let (_, s) = try_parse!(literal("\\", s));
+    let (c, s) = try_parse!(advance(s));
     ParserResult::from_val(
         match c {
             'n' => '\n',
@@ -69,8 +94,8 @@ fn parse_esc(s: &str) -> Result<char> {
     )
 }
 
-fn parse_bool(s: &str) -> Result<JSONValue> {
-    match peek(s)?.0 {
+fn parse_bool(s: &str) -> Result<'_, JSONValue> {
+    match try_parse!(peek(s)).0 {
         't' => literal("true", s).is(true).err_into(),
         'f' => literal("false", s).is(false).err_into(),
         c => Result::from_err(JSONError::InvalidToken(c), s),
@@ -78,8 +103,8 @@ fn parse_bool(s: &str) -> Result<JSONValue> {
     .map(JSONValue::Bool)
 }
 
-fn parse_list(mut s: &str) -> Result<JSONValue> {
-    ignore(("[", opt_whitespace), &mut s)?;
+fn parse_list(mut s: &str) -> Result<'_, JSONValue> {
+    try_parse!(ignore(("[", opt_whitespace), &mut s));
 
     let list = iter_delimited(
         parse_value.and_ignore(opt_whitespace),
@@ -89,18 +114,18 @@ fn parse_list(mut s: &str) -> Result<JSONValue> {
     .ok()
     .collect();
 
-    ignore(opt_whitespace.and("]"), &mut s)?;
+    try_parse!(ignore(opt_whitespace.and("]"), &mut s));
     ParserResult::from_val(JSONValue::List(list), s)
 }
 
-fn parse_map(mut s: &str) -> Result<JSONValue> {
-    ignore(("{", opt_whitespace), &mut s)?;
+fn parse_map(mut s: &str) -> Result<'_, JSONValue> {
+    try_parse!(ignore(("{", opt_whitespace), &mut s));
 
     let map = iter_delimited(
         |s| {
-            let (key, mut s) = parse_str(s).and_ignore(opt_whitespace)?;
-            ignore((opt_whitespace, ":", opt_whitespace), &mut s)?;
-            let (value, s) = parse_value(s).and_ignore(opt_whitespace)?;
+            let (key, mut s) = try_parse!(parse_str(s).and_ignore(opt_whitespace));
+            try_parse!(ignore((opt_whitespace, ":", opt_whitespace), &mut s));
+            let (value, s) = try_parse!(parse_value(s).and_ignore(opt_whitespace));
             ParserResult::<'_, _, JSONError>::from_val((key, value), s)
         },
         ",".and(opt_whitespace),
@@ -109,12 +134,12 @@ fn parse_map(mut s: &str) -> Result<JSONValue> {
     .ok()
     .collect();
 
-    ignore("}", &mut s)?;
+    try_parse!(ignore("}", &mut s));
     ParserResult::from_val(JSONValue::Map(map), s)
 }
 
-pub fn parse_value(s: &str) -> Result<JSONValue> {
-    let (c, s) = peek(s)?;
+pub fn parse_value(s: &str) -> Result<'_, JSONValue> {
+    let (c, s) = try_parse!(peek(s));
     match c {
         '"' => parse_str(s).map(JSONValue::String),
         '-' | '0'..='9' => parse_num(s),
@@ -125,3 +150,179 @@ pub fn parse_value(s: &str) -> Result<JSONValue> {
         _ => ParserResult::from_err(JSONError::InvalidToken(c), s),
     }
 }
+
+/// Serializes as plain JSON data in human-readable formats, and as an enum of the variants in binary formats such as
+/// bincode, which can't deserialize a value without knowing its type
+#[cfg(feature = "serde")]
+mod serde_impls {
+    use std::{collections::HashMap, fmt};
+
+    use serde::{
+        de::{self, EnumAccess, MapAccess, SeqAccess, VariantAccess, Visitor},
+        Deserialize, Deserializer, Serialize, Serializer,
+    };
+
+    use super::JSONValue;
+
+    const VARIANTS: &[&str] = &["String", "Null", "Bool", "Integer", "Float", "List", "Map"];
+
+    impl Serialize for JSONValue {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            if serializer.is_human_readable() {
+                return match self {
+                    JSONValue::String(s) => serializer.serialize_str(s),
+                    JSONValue::Null => serializer.serialize_unit(),
+                    JSONValue::Bool(b) => serializer.serialize_bool(*b),
+                    JSONValue::Integer(n) => serializer.serialize_i64(*n),
+                    JSONValue::Float(n) => serializer.serialize_f64(*n),
+                    JSONValue::List(list) => serializer.collect_seq(list),
+                    JSONValue::Map(map) => serializer.collect_map(map),
+                };
+            }
+            match self {
+                JSONValue::String(s) => {
+                    serializer.serialize_newtype_variant("JSONValue", 0, "String", s)
+                }
+                JSONValue::Null => serializer.serialize_unit_variant("JSONValue", 1, "Null"),
+                JSONValue::Bool(b) => {
+                    serializer.serialize_newtype_variant("JSONValue", 2, "Bool", b)
+                }
+                JSONValue::Integer(n) => {
+                    serializer.serialize_newtype_variant("JSONValue", 3, "Integer", n)
+                }
+                JSONValue::Float(n) => {
+                    serializer.serialize_newtype_variant("JSONValue", 4, "Float", n)
+                }
+                JSONValue::List(list) => {
+                    serializer.serialize_newtype_variant("JSONValue", 5, "List", list)
+                }
+                JSONValue::Map(map) => {
+                    serializer.serialize_newtype_variant("JSONValue", 6, "Map", map)
+                }
+            }
+        }
+    }
+
+    impl<'de> Deserialize<'de> for JSONValue {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(ValueVisitor)
+            } else {
+                deserializer.deserialize_enum("JSONValue", VARIANTS, ValueVisitor)
+            }
+        }
+    }
+
+    struct ValueVisitor;
+
+    impl<'de> Visitor<'de> for ValueVisitor {
+        type Value = JSONValue;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a JSON value")
+        }
+
+        fn visit_bool<E>(self, b: bool) -> Result<JSONValue, E> {
+            Ok(JSONValue::Bool(b))
+        }
+
+        fn visit_i64<E>(self, n: i64) -> Result<JSONValue, E> {
+            Ok(JSONValue::Integer(n))
+        }
+
+        /// Integers too large for an `i64` become floats, since [JSONValue::Integer] can't hold them, although parsing
+        /// them from JSON text fails instead
+        fn visit_u64<E>(self, n: u64) -> Result<JSONValue, E> {
+            Ok(i64::try_from(n).map_or(JSONValue::Float(n as f64), JSONValue::Integer))
+        }
+
+        fn visit_f64<E>(self, n: f64) -> Result<JSONValue, E> {
+            Ok(JSONValue::Float(n))
+        }
+
+        fn visit_str<E>(self, s: &str) -> Result<JSONValue, E> {
+            Ok(JSONValue::String(s.to_string()))
+        }
+
+        fn visit_string<E>(self, s: String) -> Result<JSONValue, E> {
+            Ok(JSONValue::String(s))
+        }
+
+        fn visit_unit<E>(self) -> Result<JSONValue, E> {
+            Ok(JSONValue::Null)
+        }
+
+        fn visit_none<E>(self) -> Result<JSONValue, E> {
+            Ok(JSONValue::Null)
+        }
+
+        fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<JSONValue, D::Error> {
+            JSONValue::deserialize(deserializer)
+        }
+
+        fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<JSONValue, A::Error> {
+            let mut list = Vec::with_capacity(seq.size_hint().unwrap_or(0));
+            while let Some(value) = seq.next_element()? {
+                list.push(value);
+            }
+            Ok(JSONValue::List(list))
+        }
+
+        fn visit_map<A: MapAccess<'de>>(self, mut access: A) -> Result<JSONValue, A::Error> {
+            let mut map = HashMap::with_capacity(access.size_hint().unwrap_or(0));
+            while let Some((key, value)) = access.next_entry()? {
+                map.insert(key, value);
+            }
+            Ok(JSONValue::Map(map))
+        }
+
+        fn visit_enum<A: EnumAccess<'de>>(self, data: A) -> Result<JSONValue, A::Error> {
+            let (variant, access) = data.variant::<Variant>()?;
+            Ok(match variant.0 {
+                0 => JSONValue::String(access.newtype_variant()?),
+                1 => {
+                    access.unit_variant()?;
+                    JSONValue::Null
+                }
+                2 => JSONValue::Bool(access.newtype_variant()?),
+                3 => JSONValue::Integer(access.newtype_variant()?),
+                4 => JSONValue::Float(access.newtype_variant()?),
+                5 => JSONValue::List(access.newtype_variant()?),
+                _ => JSONValue::Map(access.newtype_variant()?),
+            })
+        }
+    }
+
+    /// The index of a [JSONValue] variant, identified by either its index or its name
+    struct Variant(usize);
+
+    impl<'de> Deserialize<'de> for Variant {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            deserializer.deserialize_identifier(VariantVisitor)
+        }
+    }
+
+    struct VariantVisitor;
+
+    impl<'de> Visitor<'de> for VariantVisitor {
+        type Value = Variant;
+
+        fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+            write!(f, "a JSON value variant")
+        }
+
+        fn visit_u64<E: de::Error>(self, n: u64) -> Result<Variant, E> {
+            match usize::try_from(n) {
+                Ok(n) if n < VARIANTS.len() => Ok(Variant(n)),
+                _ => Err(E::invalid_value(de::Unexpected::Unsigned(n), &self)),
+            }
+        }
+
+        fn visit_str<E: de::Error>(self, s: &str) -> Result<Variant, E> {
+            match VARIANTS.iter().position(|v| *v == s) {
+                Some(n) => Ok(Variant(n)),
+                None => Err(E::unknown_variant(s, VARIANTS)),
+            }
+        }
+    }
+}
@@ -0,0 +1,354 @@
+use std::borrow::Cow;
+
+use super::{number_slice, JSONError};
+use crate::{error::ParserError, num, Needed, ParserResultType};
+
+/// An event emitted by an [EventParser]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Event {
+    StartObject,
+    EndObject,
+    StartArray,
+    EndArray,
+    /// The key of the next value in an object
+    Key(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Bool(bool),
+    Null,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Container {
+    Array,
+    Object,
+}
+
+/// What the parser expects next
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Expect {
+    Value,
+    /// A value or `]`, just after `[`
+    FirstValue,
+    Key,
+    /// A key or `}`, just after `{`
+    FirstKey,
+    Colon,
+    /// A `,` or the end of the current container, or the end of the input at the top level
+    Comma,
+    Done,
+}
+
+/// A pull-based JSON parser which emits [Event]s as it reads a document instead of building a
+/// [JSONValue](super::JSONValue), for documents too large to hold in memory
+///
+/// Input is given in chunks with [feed](Self::feed), which may split the document anywhere, even within a token.
+/// [next_event](Self::next_event) is incomplete when it needs another chunk, and [finish](Self::finish) marks the end
+/// of the input. After an error, no more events are emitted.
+#[derive(Debug, Clone)]
+pub struct EventParser {
+    buffer: String,
+    pos: usize,
+    stack: Vec<Container>,
+    expect: Expect,
+    finished: bool,
+}
+
+impl Default for EventParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventParser {
+    pub fn new() -> Self {
+        Self {
+            buffer: String::new(),
+            pos: 0,
+            stack: vec![],
+            expect: Expect::Value,
+            finished: false,
+        }
+    }
+
+    /// Add the next chunk of input
+    pub fn feed(&mut self, chunk: &str) {
+        self.buffer.drain(..self.pos);
+        self.pos = 0;
+        self.buffer.push_str(chunk);
+    }
+
+    /// Mark the end of the input, after which a token at the end of the input is complete rather than waiting for more
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Get the current nesting depth of arrays and objects
+    pub fn depth(&self) -> usize {
+        self.stack.len()
+    }
+
+    /// Read the next event, which is [Ok] with [None] at the end of the document and
+    /// [Incomplete](ParserResultType::Incomplete) if more input is needed
+    pub fn next_event(&mut self) -> ParserResultType<Option<Event>, JSONError> {
+        let res = self.read();
+        if let ParserResultType::Err(_) = res {
+            self.expect = Expect::Done;
+        }
+        res
+    }
+
+    fn read(&mut self) -> ParserResultType<Option<Event>, JSONError> {
+        loop {
+            let rest = &self.buffer[self.pos..];
+            let trimmed = rest.trim_start_matches([' ', '\t', '\n', '\r']);
+            self.pos += rest.len() - trimmed.len();
+            let Some(c) = trimmed.chars().next() else {
+                return match self.expect {
+                    Expect::Done => ParserResultType::Ok(None),
+                    Expect::Comma if self.stack.is_empty() && self.finished => {
+                        self.expect = Expect::Done;
+                        ParserResultType::Ok(None)
+                    }
+                    _ if self.finished => {
+                        ParserResultType::Err(ParserError::UnexpectedEndOfFile.into())
+                    }
+                    _ => ParserResultType::Incomplete(Needed::Unknown),
+                };
+            };
+            let event = match (self.expect, c) {
+                (Expect::Done, _) => return ParserResultType::Ok(None),
+                (Expect::Colon, ':') => {
+                    self.pos += 1;
+                    self.expect = Expect::Value;
+                    continue;
+                }
+                (Expect::Comma, ',') if !self.stack.is_empty() => {
+                    self.pos += 1;
+                    self.expect = match self.stack.last() {
+                        Some(Container::Object) => Expect::Key,
+                        _ => Expect::Value,
+                    };
+                    continue;
+                }
+                (Expect::Comma | Expect::FirstValue, ']')
+                    if self.stack.last() == Some(&Container::Array) =>
+                {
+                    self.close(Event::EndArray)
+                }
+                (Expect::Comma | Expect::FirstKey, '}')
+                    if self.stack.last() == Some(&Container::Object) =>
+                {
+                    self.close(Event::EndObject)
+                }
+                (Expect::Key | Expect::FirstKey, '"') => match self.string() {
+                    ParserResultType::Ok(key) => {
+                        self.expect = Expect::Colon;
+                        Event::Key(key)
+                    }
+                    ParserResultType::Err(err) => return ParserResultType::Err(err),
+                    ParserResultType::Incomplete(needed) => {
+                        return ParserResultType::Incomplete(needed)
+                    }
+                },
+                (Expect::Value | Expect::FirstValue, _) => match self.value(c) {
+                    ParserResultType::Ok(event) => event,
+                    ParserResultType::Err(err) => return ParserResultType::Err(err),
+                    ParserResultType::Incomplete(needed) => {
+                        return ParserResultType::Incomplete(needed)
+                    }
+                },
+                (_, c) => return ParserResultType::Err(JSONError::InvalidToken(c)),
+            };
+            return ParserResultType::Ok(Some(event));
+        }
+    }
+
+    fn close(&mut self, event: Event) -> Event {
+        self.pos += 1;
+        self.stack.pop();
+        self.expect = Expect::Comma;
+        event
+    }
+
+    fn value(&mut self, c: char) -> ParserResultType<Event, JSONError> {
+        let open = |parser: &mut Self, container, expect, event| {
+            parser.pos += 1;
+            parser.stack.push(container);
+            parser.expect = expect;
+            ParserResultType::Ok(event)
+        };
+        let event = match c {
+            '[' => {
+                return open(
+                    self,
+                    Container::Array,
+                    Expect::FirstValue,
+                    Event::StartArray,
+                )
+            }
+            '{' => {
+                return open(
+                    self,
+                    Container::Object,
+                    Expect::FirstKey,
+                    Event::StartObject,
+                )
+            }
+            '"' => self.string().map(Event::String),
+            '-' | '0'..='9' => self.number(),
+            't' => self.word("true", Event::Bool(true)),
+            'f' => self.word("false", Event::Bool(false)),
+            'n' => self.word("null", Event::Null),
+            c => ParserResultType::Err(JSONError::InvalidToken(c)),
+        };
+        if let ParserResultType::Ok(_) = event {
+            self.expect = Expect::Comma;
+        }
+        event
+    }
+
+    /// The result for a token which runs to the end of the input so far, which may continue in the next chunk
+    fn incomplete<T>(&self) -> ParserResultType<T, JSONError> {
+        if self.finished {
+            ParserResultType::Err(ParserError::UnexpectedEndOfFile.into())
+        } else {
+            ParserResultType::Incomplete(Needed::Unknown)
+        }
+    }
+
+    fn word(&mut self, word: &'static str, event: Event) -> ParserResultType<Event, JSONError> {
+        let rest = &self.buffer[self.pos..];
+        if rest.starts_with(word) {
+            self.pos += word.len();
+            return ParserResultType::Ok(event);
+        }
+        if word.starts_with(rest) {
+            return self.incomplete();
+        }
+        ParserResultType::Err(ParserError::ExpectedLiteral(word).into())
+    }
+
+    fn number(&mut self) -> ParserResultType<Event, JSONError> {
+        let rest = &self.buffer[self.pos..];
+        let len = rest
+            .find(|c: char| !matches!(c, '0'..='9' | '-' | '+' | '.' | 'e' | 'E'))
+            .unwrap_or(rest.len());
+        if len == rest.len() && !self.finished {
+            return ParserResultType::Incomplete(Needed::Unknown);
+        }
+        let num = &rest[..len];
+        // The token must be exactly one number, so that this agrees with the DOM parser
+        let slice = number_slice(num);
+        if !slice.is_ok() || !slice.source.is_empty() {
+            return ParserResultType::Err(ParserError::ExpectedToken("number".into()).into());
+        }
+        let event = if num.contains(['.', 'e', 'E']) {
+            num::float(num).map(Event::Float)
+        } else {
+            num::integer(num).map(Event::Integer)
+        };
+        let event = match event.typ {
+            ParserResultType::Ok(event) => event,
+            ParserResultType::Err(err) => return ParserResultType::Err(err.into()),
+            ParserResultType::Incomplete(_) => return self.incomplete(),
+        };
+        self.pos += len;
+        ParserResultType::Ok(event)
+    }
+
+    /// Parse four hex digits of a `\u` escape
+    fn hex4(s: &str) -> Option<u32> {
+        let digits = s.get(..4)?;
+        if !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            return None;
+        }
+        u32::from_str_radix(digits, 16).ok()
+    }
+
+    fn string(&mut self) -> ParserResultType<String, JSONError> {
+        let body = &self.buffer[self.pos + 1..];
+        let mut string = Cow::Borrowed("");
+        let mut start = 0;
+        let mut chars = body.char_indices();
+        while let Some((i, c)) = chars.next() {
+            match c {
+                '"' => {
+                    let string = match string {
+                        Cow::Borrowed(_) => body[..i].to_string(),
+                        Cow::Owned(mut s) => {
+                            s.push_str(&body[start..i]);
+                            s
+                        }
+                    };
+                    self.pos += i + 2;
+                    return ParserResultType::Ok(string);
+                }
+                '\\' => {
+                    string.to_mut().push_str(&body[start..i]);
+                    let Some((_, escape)) = chars.next() else {
+                        return self.incomplete();
+                    };
+                    let c = match escape {
+                        '"' | '\\' | '/' => escape,
+                        'b' => '\u{8}',
+                        'f' => '\u{c}',
+                        'n' => '\n',
+                        'r' => '\r',
+                        't' => '\t',
+                        'u' => {
+                            let hex = &body[i + 2..];
+                            let Some(mut n) = Self::hex4(hex) else {
+                                if hex.len() < 4 {
+                                    return self.incomplete();
+                                }
+                                return ParserResultType::Err(
+                                    ParserError::InvalidEscape('u').into(),
+                                );
+                            };
+                            let mut len = 4;
+                            // A high surrogate must be followed by an escaped low surrogate
+                            if (0xD800..0xDC00).contains(&n) {
+                                let low = &hex[4..];
+                                if low.len() < 6
+                                    && b"\\u".starts_with(&low.as_bytes()[..low.len().min(2)])
+                                {
+                                    return self.incomplete();
+                                }
+                                match low.strip_prefix("\\u").and_then(Self::hex4) {
+                                    Some(lo @ 0xDC00..0xE000) => {
+                                        n = 0x10000 + ((n - 0xD800) << 10) + (lo - 0xDC00);
+                                        len = 10;
+                                    }
+                                    _ => {
+                                        return ParserResultType::Err(
+                                            ParserError::InvalidCodePoint(n).into(),
+                                        )
+                                    }
+                                }
+                            }
+                            for _ in 0..len {
+                                chars.next();
+                            }
+                            match char::from_u32(n) {
+                                Some(c) => c,
+                                None => {
+                                    return ParserResultType::Err(
+                                        ParserError::InvalidCodePoint(n).into(),
+                                    )
+                                }
+                            }
+                        }
+                        c => return ParserResultType::Err(ParserError::InvalidEscape(c).into()),
+                    };
+                    string.to_mut().push(c);
+                    start = chars.offset();
+                }
+                c if c < ' ' => return ParserResultType::Err(JSONError::InvalidToken(c)),
+                _ => {}
+            }
+        }
+        self.incomplete()
+    }
+}
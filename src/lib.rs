@@ -1,23 +1,93 @@
-#![feature(try_trait_v2, slice_index_methods, impl_trait_in_assoc_type)]
+#![cfg_attr(feature = "nightly", feature(try_trait_v2))]
 
 use std::{
+    borrow::Cow,
     fmt::Debug,
-    ops::{Bound, RangeBounds},
+    ops::{Bound, Range, RangeBounds},
 };
 
 use container::Container;
 use error::ParserError;
-use iter::{ParsIter, ParsIterDelim, ParsingIterator};
+use iter::{OwnedParsIter, ParsIter, ParsIterDelim, ParsingIterator, SourceIterator};
+pub use parse::Parse;
 pub use parser::Parser;
-pub use parser_result::{ParserResult, ParserResultType};
+pub use parser_result::{Needed, ParserResult, ParserResultType};
 
+/// Derive [Parse](trait@Parse) for a struct or enum, using `#[parse(...)]` attributes for literals around types and
+/// fields, delimiters and repetition
+///
+/// The derived `Type::parse` is a function from input to a [ParserResult], so it is also a [Parser] which can be used
+/// with any combinator.
+/// Example:
+/// ```
+/// use crochet::{Parse, Parser};
+/// #[derive(Parse, Debug, PartialEq)]
+/// #[parse(prefix = "v")]
+/// struct Version {
+///     major: u32,
+///     #[parse(prefix = ".")]
+///     minor: u32,
+/// }
+///
+/// #[derive(Parse, Debug, PartialEq)]
+/// enum Requirement {
+///     #[parse(prefix = ">=")]
+///     AtLeast(Version),
+///     Exact(Version),
+/// }
+///
+/// assert_eq!(Version::parse("v1.2").unwrap(), Version { major: 1, minor: 2 });
+/// let reqs = Requirement::parse.padded(" ").repeating(1..).parse(">=v1.2 v3.4").unwrap();
+/// assert_eq!(reqs[1], Requirement::Exact(Version { major: 3, minor: 4 }));
+/// ```
+#[cfg(feature = "derive")]
+pub use crochet_derive::Parse;
+
+#[cfg(feature = "bumpalo")]
+pub mod arena;
+pub mod ascii;
+pub mod balanced;
+pub mod binary;
+pub mod bits;
+pub mod comment;
 pub mod container;
+pub mod deadline;
+pub mod diagnostic;
+pub mod dispatch;
+pub mod ebnf;
+pub mod encoding;
 pub mod error;
+pub mod escape;
+pub mod fuel;
+pub mod grammar;
+#[cfg(feature = "unicode-segmentation")]
+pub mod grapheme;
+pub mod incremental;
+pub mod interpolate;
 pub mod iter;
 pub mod json;
+pub mod lines;
+pub mod located;
+#[cfg(feature = "nom")]
+pub mod nom;
+#[cfg(feature = "unicode-normalization")]
+pub mod normalize;
+pub mod num;
+pub mod ops;
+#[cfg(feature = "rayon")]
+pub mod parallel;
+pub mod parse;
 pub mod parser;
 pub mod parser_result;
 pub mod parsers;
+pub mod stateful;
+pub mod streaming;
+pub mod testing;
+pub mod trace;
+pub mod trie;
+pub mod trivia;
+#[cfg(feature = "unicode")]
+pub mod unicode;
 
 /// Curry a parsing function that takes more than one argument to create a parser
 /// Example:
@@ -32,14 +102,146 @@ macro_rules! cur {
     }
 }
 
+/// Get the value and remaining input of a successful [ParserResult], or return its failure early, converting the error
+/// with [Into] - an equivalent of `?` which doesn't need the `nightly` feature
+/// Example:
+/// ```
+/// use crochet::*;
+/// fn two_digits(input: &str) -> ParserResult<'_, (char, char), error::ParserError> {
+///     let (first, s) = try_parse!(matching_char("digit", |c| c.is_ascii_digit(), input));
+///     let (second, s) = try_parse!(matching_char("digit", |c| c.is_ascii_digit(), s));
+///     ParserResult::from_val((first, second), s)
+/// }
+/// ```
+#[macro_export]
+macro_rules! try_parse {
+    ($res:expr) => {
+        match $crate::ParserResult::into_result($res) {
+            Ok(parsed) => parsed,
+            Err(err) => return err.err_into(),
+        }
+    };
+}
+
+/// Parse a sequence of elements, binding each parsed value to a pattern, then build a value from them
+/// Example:
+/// ```
+/// use crochet::*;
+/// fn pair(input: &str) -> ParserResult<'_, (char, char), error::ParserError> {
+///     seq!(input => {
+///         key = cur!(matching_char <= "key", |c| c.is_alphabetic());
+///         _ = "=";
+///         value = cur!(matching_char <= "value", |c| c.is_ascii_digit());
+///     } => (key, value))
+/// }
+/// ```
+#[macro_export]
+macro_rules! seq {
+    ($input:expr => { $($bind:pat = $parser:expr;)* } => $value:expr) => {{
+        let s = $input;
+        $(
+            let ($bind, s) = $crate::try_parse!($crate::Parser::parse(&$parser, s));
+        )*
+        $crate::ParserResult::from_val($value, s)
+    }};
+}
+
+/// Define an enum of keywords with a parser for it, which matches a whole identifier so that `let` doesn't match the
+/// start of `letter`, and suggests the closest keywords like [keyword] when it fails
+///
+/// The enum gets `ALL` and `KEYWORDS` constants listing its variants and their text, along with `as_str`,
+/// `is_keyword` and `parse` functions and a [Display](std::fmt::Display) implementation.
+/// Example:
+/// ```
+/// use crochet::*;
+/// keywords! {
+///     pub enum Keyword {
+///         Let = "let",
+///         Fn = "fn",
+///     }
+/// }
+/// assert_eq!(Keyword::parse("let x").unwrap(), Keyword::Let);
+/// assert!(Keyword::parse("letter").is_err());
+/// assert!(Keyword::is_keyword("fn"));
+/// assert_eq!(identifier_except(Keyword::KEYWORDS, "letter").unwrap(), "letter");
+/// ```
+#[macro_export]
+macro_rules! keywords {
+    ($(#[$meta:meta])* $vis:vis enum $name:ident { $($(#[$vmeta:meta])* $variant:ident = $text:literal),* $(,)? }) => {
+        $(#[$meta])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+        $vis enum $name {
+            $($(#[$vmeta])* $variant),*
+        }
+
+        impl $name {
+            /// Every keyword, in the order they were defined
+            pub const ALL: &'static [$name] = &[$($name::$variant),*];
+            /// The text of every keyword, in the same order as `ALL`
+            pub const KEYWORDS: &'static [&'static str] = &[$($text),*];
+
+            /// Get the text of the keyword
+            pub fn as_str(self) -> &'static str {
+                match self {
+                    $($name::$variant => $text),*
+                }
+            }
+
+            /// Check whether a whole word is one of the keywords
+            pub fn is_keyword(word: &str) -> bool {
+                Self::KEYWORDS.contains(&word)
+            }
+
+            /// Parse an identifier which is one of the keywords
+            pub fn parse(input: &str) -> $crate::ParserResult<'_, Self, $crate::error::ParserError> {
+                $crate::keyword(Self::KEYWORDS, input).map(|text| {
+                    let index = Self::KEYWORDS.iter().position(|k| *k == text).unwrap();
+                    Self::ALL[index]
+                })
+            }
+        }
+
+        impl ::std::fmt::Display for $name {
+            fn fmt(&self, f: &mut ::std::fmt::Formatter<'_>) -> ::std::fmt::Result {
+                f.write_str(self.as_str())
+            }
+        }
+    };
+}
+
+/// The minimum number of elements allowed by a bound, used as a capacity hint
+pub(crate) fn lower_bound(bound: Bound<&usize>) -> usize {
+    match bound {
+        Bound::Included(b) => *b,
+        Bound::Excluded(b) => b.saturating_add(1),
+        Bound::Unbounded => 0,
+    }
+}
+
+/// Whether an iteration of an unbounded repetition succeeded without consuming any input, which would repeat forever
+fn no_progress(before: &str, after: &str, upper: Bound<&usize>) -> bool {
+    upper == Bound::Unbounded && after.len() == before.len()
+}
+
+/// Whether another element can be added to `num` elements without exceeding the bound, so that a repetition bounded by
+/// `..=n` stops after `n` elements and one bounded by `..n` stops after `n - 1`
 fn is_under(num: usize, bound: Bound<&usize>) -> bool {
     match bound {
-        Bound::Included(bound) => num <= *bound,
-        Bound::Excluded(bound) => num < *bound,
+        Bound::Included(bound) => num < *bound,
+        Bound::Excluded(bound) => num + 1 < *bound,
         Bound::Unbounded => true,
     }
 }
 
+/// Panic with a clear message for repetition bounds which no count is within, such as `..0` or `3..2`, rather than
+/// after parsing
+pub(crate) fn check_bounds(bounds: &impl RangeBounds<usize>) {
+    assert!(
+        bounds.contains(&lower_bound(bounds.start_bound())),
+        "repetition bounds must contain at least one count"
+    );
+}
+
 pub struct Literal(pub &'static str);
 
 impl From<&'static str> for Literal {
@@ -63,6 +265,8 @@ pub fn literal<'a>(
 }
 
 /// Parse a delimited list of elements using two parsers
+///
+/// The list ends before a delimiter and element which together consume no input, since they would repeat forever.
 pub fn delimited_list<
     'a,
     Elem,
@@ -77,41 +281,182 @@ pub fn delimited_list<
 ) -> ParserResult<'a, (ElemContainer, DelimContainer), Error> {
     let mut elems = ElemContainer::default();
     let mut delims = DelimContainer::default();
+    let add = |item| match item {
+        ListItem::Elem(elem) => elems.add(elem),
+        ListItem::Delim(delim) => delims.add(delim),
+    };
+    let (_, input) = try_parse!(delimited_with(elem_parser, delim_parser, add, input));
+    ParserResult::from_val((elems, delims), input)
+}
 
-    let (first, mut input) = elem_parser.parse(input)?;
-    elems.add(first);
+/// An element or delimiter of a list parsed with [delimited_list_interleaved]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ListItem<Elem, Delim> {
+    Elem(Elem),
+    Delim(Delim),
+}
+
+/// Parse a delimited list like [delimited_list], but keep the elements and delimiters in a single container in the
+/// order they appeared, starting and ending with an element, so that the source can be reproduced from them
+pub fn delimited_list_interleaved<'a, Elem, Delim, Error, C: Container<ListItem<Elem, Delim>>>(
+    elem_parser: impl Parser<'a, Elem, Error>,
+    delim_parser: impl Parser<'a, Delim, Error>,
+    input: &'a str,
+) -> ParserResult<'a, C, Error> {
+    let mut items = C::default();
+    let (_, input) = try_parse!(delimited_with(
+        elem_parser,
+        delim_parser,
+        |item| items.add(item),
+        input
+    ));
+    ParserResult::from_val(items, input)
+}
+
+/// Parse a delimited list, passing each element and delimiter to `add` in the order they appeared
+fn delimited_with<'a, Elem, Delim, Error>(
+    elem_parser: impl Parser<'a, Elem, Error>,
+    delim_parser: impl Parser<'a, Delim, Error>,
+    mut add: impl FnMut(ListItem<Elem, Delim>),
+    input: &'a str,
+) -> ParserResult<'a, (), Error> {
+    let (first, mut input) = try_parse!(elem_parser.parse(input));
+    add(ListItem::Elem(first));
 
     loop {
         let delim = delim_parser.parse(input);
         if !delim.is_ok() {
             break;
         }
-        input = delim.source;
-        delims.add(delim.unwrap());
-        let (elem, new_slice) = elem_parser.parse(input)?;
+        let after_delim = delim.source;
+        let delim = delim.unwrap();
+        let (elem, new_slice) = try_parse!(elem_parser.parse(after_delim));
+        if no_progress(input, new_slice, Bound::Unbounded) {
+            // The same delimiter and element would be parsed again forever, so end the list before them
+            break;
+        }
         input = new_slice;
-        elems.add(elem);
+        add(ListItem::Delim(delim));
+        add(ListItem::Elem(elem));
     }
 
-    ParserResult::from_val((elems, delims), input)
+    ParserResult::from_val((), input)
+}
+
+/// The elements of a list parsed with [delimited_list_recovering], along with the errors it recovered from and the
+/// input remaining where each occurred
+#[derive(Debug, Clone, PartialEq)]
+pub struct Recovered<'a, T, E> {
+    pub elems: Vec<T>,
+    pub errors: Vec<(E, &'a str)>,
+}
+
+/// Parse a delimited list like [delimited_list], but when an element or delimiter fails to parse, record the error and
+/// skip ahead to the next delimiter or closing token and carry on, returning the elements which did parse along with
+/// each error and where it occurred
+///
+/// The closing token is not consumed, and skipping stops at the end of the input if neither is found. Skipping looks
+/// for the delimiter one character at a time, so it doesn't account for nesting or quoting. A list which is closed
+/// straight away, or where the input ends, is empty rather than an error.
+pub fn delimited_list_recovering<'a, Elem, Delim, Close, Error: From<ParserError>>(
+    elem_parser: impl Parser<'a, Elem, Error>,
+    delim_parser: impl Parser<'a, Delim, Error>,
+    close_parser: impl Parser<'a, Close, Error>,
+    mut input: &'a str,
+) -> ParserResult<'a, Recovered<'a, Elem, Error>, Error> {
+    let mut elems = vec![];
+    let mut errors = vec![];
+    if input.is_empty() || close_parser.parse(input).is_ok() {
+        return ParserResult::from_val(Recovered { elems, errors }, input);
+    }
+    loop {
+        let start = input;
+        let res = elem_parser.parse(input);
+        let failed = match res.typ {
+            ParserResultType::Ok(elem) => {
+                elems.push(elem);
+                input = res.source;
+                let delim = delim_parser.parse(input);
+                match delim.typ {
+                    ParserResultType::Ok(_) => {
+                        input = delim.source;
+                        if no_progress(start, input, Bound::Unbounded) {
+                            return ParserResult::from_err(ParserError::NoProgress.into(), start);
+                        }
+                        continue;
+                    }
+                    _ if input.is_empty() || close_parser.parse(input).is_ok() => break,
+                    ParserResultType::Err(err) => (err, delim.source),
+                    ParserResultType::Incomplete(needed) => {
+                        return ParserResult::incomplete_needing(needed, delim.source)
+                    }
+                }
+            }
+            ParserResultType::Err(err) => (err, res.source),
+            ParserResultType::Incomplete(needed) => {
+                return ParserResult::incomplete_needing(needed, res.source)
+            }
+        };
+        errors.push(failed);
+        // Skip to the next delimiter, stopping at the closing token or the end of the input
+        let delim = loop {
+            if close_parser.parse(input).is_ok() {
+                break None;
+            }
+            let delim = delim_parser.parse(input);
+            if delim.is_ok() {
+                break Some(delim.source);
+            }
+            match input.chars().next() {
+                Some(c) => input = &input[c.len_utf8()..],
+                None => break None,
+            }
+        };
+        match delim {
+            Some(rest) if no_progress(start, rest, Bound::Unbounded) => {
+                return ParserResult::from_err(ParserError::NoProgress.into(), start)
+            }
+            Some(rest) => input = rest,
+            None => break,
+        }
+    }
+    ParserResult::from_val(Recovered { elems, errors }, input)
 }
 
 /// Parse a single character matching a predicate
 pub fn matching_char<'a>(
-    token_name: &'static str,
-    filter: impl Fn(char) -> bool,
+    token_name: impl Into<Cow<'static, str>>,
+    mut filter: impl FnMut(char) -> bool,
     input: &'a str,
 ) -> ParserResult<'a, char, ParserError> {
     match input.chars().next() {
         Some(c) if filter(c) => ParserResult::from_val(c, &input[c.len_utf8()..]),
-        _ => ParserResult::from_err(ParserError::ExpectedToken(token_name), input),
+        _ => ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input),
     }
 }
 
-/// Consume characters as long as they match a predicate
+/// Parse a single character within a range, such as `'a'..='z'`
+pub fn char_range(
+    range: impl RangeBounds<char>,
+    input: &str,
+) -> ParserResult<'_, char, ParserError> {
+    match input.chars().next() {
+        Some(c) if range.contains(&c) => ParserResult::from_val(c, &input[c.len_utf8()..]),
+        _ => ParserResult::from_err(
+            ParserError::ExpectedCharInRange(
+                range.start_bound().cloned(),
+                range.end_bound().cloned(),
+            ),
+            input,
+        ),
+    }
+}
+
+/// Consume characters as long as they match a predicate, which is called on each character in order and so can keep
+/// state such as the previous character
 pub fn take_while<'a>(
-    token_name: &'static str,
-    filter: impl Fn(char) -> bool,
+    token_name: impl Into<Cow<'static, str>>,
+    mut filter: impl FnMut(char) -> bool,
     input: &'a str,
 ) -> ParserResult<'a, &'a str, ParserError> {
     let len: usize = input
@@ -120,37 +465,270 @@ pub fn take_while<'a>(
         .map(|c| c.len_utf8())
         .sum();
     if len == 0 {
-        ParserResult::from_err(ParserError::ExpectedToken(token_name), input)
+        ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input)
     } else {
         let (parsed, rest) = input.split_at(len);
         ParserResult::from_val(parsed, rest)
     }
 }
 
+/// Whether a character can start an identifier: `XID_Start` or `_` with the `unicode` feature, otherwise ASCII letters or `_`
+pub fn is_ident_start(c: char) -> bool {
+    #[cfg(feature = "unicode")]
+    {
+        c == '_' || unicode_ident::is_xid_start(c)
+    }
+    #[cfg(not(feature = "unicode"))]
+    {
+        c == '_' || c.is_ascii_alphabetic()
+    }
+}
+
+/// Whether a character can continue an identifier: `XID_Continue` with the `unicode` feature, otherwise ASCII alphanumerics or `_`
+pub fn is_ident_continue(c: char) -> bool {
+    #[cfg(feature = "unicode")]
+    {
+        unicode_ident::is_xid_continue(c)
+    }
+    #[cfg(not(feature = "unicode"))]
+    {
+        c == '_' || c.is_ascii_alphanumeric()
+    }
+}
+
+/// Parse an identifier as defined by UAX #31
+pub fn identifier(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let (_, s) = try_parse!(matching_char("identifier", is_ident_start, input));
+    let (_, s) = try_parse!(take_while("identifier", is_ident_continue, s).optional(s));
+    ParserResult::from_val(&input[..input.len() - s.len()], s)
+}
+
+/// Parse an identifier which is not one of the given keywords
+pub fn identifier_except<'a>(
+    keywords: &[&str],
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let (ident, s) = try_parse!(identifier(input));
+    if keywords.contains(&ident) {
+        ParserResult::from_err(
+            ParserError::ExpectedToken("non-keyword identifier".into()),
+            input,
+        )
+    } else {
+        ParserResult::from_val(ident, s)
+    }
+}
+
+/// Parse an identifier which is one of the given keywords, suggesting the closest keywords if it isn't
+pub fn keyword<'a>(
+    keywords: &[&'static str],
+    input: &'a str,
+) -> ParserResult<'a, &'static str, ParserError> {
+    let (ident, s) = try_parse!(identifier(input));
+    if let Some(keyword) = keywords.iter().find(|keyword| **keyword == ident) {
+        return ParserResult::from_val(*keyword, s);
+    }
+    let distances: Vec<_> = keywords
+        .iter()
+        .map(|keyword| (*keyword, edit_distance(ident, keyword)))
+        .filter(|(keyword, distance)| *distance <= (keyword.chars().count() / 3).max(1))
+        .collect();
+    let closest = distances.iter().map(|(_, distance)| *distance).min();
+    let suggestions = distances
+        .iter()
+        .filter(|(_, distance)| Some(*distance) == closest)
+        .map(|(keyword, _)| *keyword)
+        .collect();
+    ParserResult::from_err(
+        ParserError::UnknownKeyword(ident.to_string(), suggestions),
+        input,
+    )
+}
+
+/// The number of single-character insertions, deletions and substitutions needed to turn one string into another
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.chars().enumerate() {
+        let mut diagonal = row[0];
+        row[0] = i + 1;
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = diagonal + usize::from(ca != *cb);
+            diagonal = row[j + 1];
+            row[j + 1] = substitution.min(row[j] + 1).min(diagonal + 1);
+        }
+    }
+    row[b.len()]
+}
+
 /// Parse whitespace
-pub fn whitespace(input: &str) -> ParserResult<&str, ParserError> {
+pub fn whitespace(input: &str) -> ParserResult<'_, &str, ParserError> {
     take_while("whitespace", char::is_whitespace, input)
 }
 
-pub fn opt_whitespace(input: &str) -> ParserResult<Option<&str>, ParserError> {
+pub fn opt_whitespace(input: &str) -> ParserResult<'_, Option<&str>, ParserError> {
     whitespace(input).optional(input)
 }
 
+fn is_space(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+/// Parse any number of spaces and tabs, but not newlines
+pub fn space0(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("space", is_space, input)
+        .optional(input)
+        .map(Option::unwrap_or_default)
+}
+
+/// Parse at least one space or tab, but not newlines
+pub fn space1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("space", is_space, input)
+}
+
+/// Parse any amount of whitespace, including newlines
+pub fn multispace0(input: &str) -> ParserResult<'_, &str, ParserError> {
+    whitespace(input)
+        .optional(input)
+        .map(Option::unwrap_or_default)
+}
+
+/// Parse at least one whitespace character, including newlines
+pub fn multispace1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    whitespace(input)
+}
+
+/// Parse a single `\n` or `\r\n` line ending
+pub fn line_ending(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let len = if input.starts_with("\r\n") {
+        2
+    } else if input.starts_with('\n') {
+        1
+    } else {
+        return ParserResult::from_err(ParserError::ExpectedToken("line ending".into()), input);
+    };
+    let (ending, rest) = input.split_at(len);
+    ParserResult::from_val(ending, rest)
+}
+
+/// Consume everything up to, but not including, the next line ending or the end of the input
+pub fn not_line_ending(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let end = input
+        .match_indices('\n')
+        .map(|(i, _)| if input[..i].ends_with('\r') { i - 1 } else { i })
+        .next()
+        .unwrap_or(input.len());
+    let (line, rest) = input.split_at(end);
+    ParserResult::from_val(line, rest)
+}
+
+/// Consume the rest of the current line along with its line ending, if any, returning the line without its ending
+pub fn rest_of_line(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let (line, s) = try_parse!(not_line_ending(input));
+    let (_, s) = try_parse!(line_ending(s).optional(s));
+    ParserResult::from_val(line, s)
+}
+
+/// Parse a line like [rest_of_line], failing at the end of the input instead of returning an empty line
+pub fn line(input: &str) -> ParserResult<'_, &str, ParserError> {
+    if input.is_empty() {
+        return ParserResult::from_err(ParserError::UnexpectedEndOfFile, input);
+    }
+    rest_of_line(input)
+}
+
 /// Parse any number of elements, but at least one
-pub fn repeating<'a, T, E>(
+pub fn repeating<'a, T, E: From<ParserError>>(
+    parser: impl Parser<'a, T, E>,
+    bounds: impl RangeBounds<usize> + 'a,
+    source: &'a str,
+) -> ParserResult<'a, Vec<T>, E> {
+    repeating_into(parser, bounds, source)
+}
+
+/// Parse a number of elements within a range into a [Container]
+///
+/// Panics if no count is within `bounds`, such as `..0` or `3..2`.
+pub fn repeating_into<'a, T, E: From<ParserError>, C: Container<T>>(
     parser: impl Parser<'a, T, E>,
     bounds: impl RangeBounds<usize> + 'a,
+    source: &'a str,
+) -> ParserResult<'a, C, E> {
+    let elems = C::with_capacity(lower_bound(bounds.start_bound()));
+    repeating_with(elems, C::add, parser, bounds, source)
+}
+
+/// Parse a number of elements within a range, adding them to an existing collection with a function, for collections
+/// which can't implement [Container] such as ones allocated in an arena
+pub(crate) fn repeating_with<'a, T, E: From<ParserError>, C>(
+    mut elems: C,
+    add: impl Fn(&mut C, T),
+    parser: impl Parser<'a, T, E>,
+    bounds: impl RangeBounds<usize> + 'a,
+    mut source: &'a str,
+) -> ParserResult<'a, C, E> {
+    check_bounds(&bounds);
+    let mut count = 0;
+    let mut err = None;
+    while is_under(count, bounds.end_bound()) {
+        let parsed = parser.parse(source);
+        if let ParserResultType::Ok(v) = parsed.typ {
+            if no_progress(source, parsed.source, bounds.end_bound()) {
+                return ParserResult::from_err(ParserError::NoProgress.into(), source);
+            }
+            add(&mut elems, v);
+            count += 1;
+            source = parsed.source;
+        } else {
+            err = Some(parsed);
+            break;
+        }
+    }
+    if !bounds.contains(&count) {
+        err.expect("error must be present if not enough matches were found")
+            .map(|_| unreachable!())
+    } else {
+        ParserResult::from_val(elems, source)
+    }
+}
+
+/// Parse a number of elements within a range separated by delimiters, such as `sep_by(segment, ".", 2..=4, s)` for
+/// two to four dotted segments, leaving a delimiter unconsumed if no element follows it
+///
+/// An element which fails after consuming some input, or runs out of input, is an error rather than the end of the list.
+/// [Parser::sep_by] is the same with no bounds. Panics if no count is within `bounds`, like [repeating_into].
+pub fn sep_by<'a, T, D, E: From<ParserError>>(
+    elem: impl Parser<'a, T, E>,
+    delim: impl Parser<'a, D, E>,
+    bounds: impl RangeBounds<usize> + 'a,
     mut source: &'a str,
 ) -> ParserResult<'a, Vec<T>, E> {
-    let mut elems = vec![];
+    check_bounds(&bounds);
+    let mut elems = Vec::with_capacity(lower_bound(bounds.start_bound()));
     let mut err = None;
     while is_under(elems.len(), bounds.end_bound()) {
-        let parsed = parser.parse(source);
+        let start = source;
+        let s = if elems.is_empty() {
+            source
+        } else {
+            let delimited = delim.parse(source);
+            if !delimited.is_ok() {
+                err = Some(delimited.map(|_| ()));
+                break;
+            }
+            delimited.source
+        };
+        let parsed = elem.parse(s);
         if let ParserResultType::Ok(v) = parsed.typ {
+            if no_progress(start, parsed.source, bounds.end_bound()) {
+                return ParserResult::from_err(ParserError::NoProgress.into(), start);
+            }
             elems.push(v);
             source = parsed.source;
+        } else if parsed.is_incomplete() || parsed.source.len() < s.len() {
+            return parsed.map(|_| unreachable!());
         } else {
-            err = Some(parsed);
+            err = Some(parsed.map(|_| ()));
             break;
         }
     }
@@ -162,35 +740,223 @@ pub fn repeating<'a, T, E>(
     }
 }
 
+/// Parse exactly `N` elements into an array
+pub fn array<'a, const N: usize, T, E>(
+    parser: impl Parser<'a, T, E>,
+    mut source: &'a str,
+) -> ParserResult<'a, [T; N], E> {
+    let mut err = None;
+    let elems = [(); N].map(|_| {
+        if err.is_some() {
+            return None;
+        }
+        let parsed = parser.parse(source);
+        match parsed.typ {
+            ParserResultType::Ok(v) => {
+                source = parsed.source;
+                Some(v)
+            }
+            _ => {
+                err = Some(parsed.map(|_| unreachable!()));
+                None
+            }
+        }
+    });
+    match err {
+        Some(err) => err,
+        None => ParserResult::from_val(elems.map(|v| v.expect("all elements were parsed")), source),
+    }
+}
+
+/// Try each parser in turn, returning the result of the first to succeed, or of the last if none do, so that
+/// alternatives can be assembled at runtime such as from a slice of [BoxedParser](parser::BoxedParser)s
+pub fn choice_of<'a, T, E: From<ParserError>>(
+    parsers: &[impl Parser<'a, T, E>],
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let mut res = ParserResult::from_err(
+        ParserError::ExpectedToken("any alternative".into()).into(),
+        input,
+    );
+    for parser in parsers {
+        res = parser.parse(input);
+        if res.is_ok() {
+            break;
+        }
+    }
+    res
+}
+
+/// Run every parser, returning the successful result which consumed the most input, preferring earlier parsers on
+/// ties, or the result of the last parser if none succeed
+pub fn choice_longest<'a, T, E: From<ParserError>>(
+    parsers: &[impl Parser<'a, T, E>],
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let mut longest: Option<ParserResult<'a, T, E>> = None;
+    let mut last = None;
+    for parser in parsers {
+        let res = parser.parse(input);
+        if !res.is_ok() {
+            last = Some(res);
+        } else if longest
+            .as_ref()
+            .is_none_or(|longest| res.source.len() < longest.source.len())
+        {
+            longest = Some(res);
+        }
+    }
+    longest.or(last).unwrap_or_else(|| {
+        ParserResult::from_err(
+            ParserError::ExpectedToken("any alternative".into()).into(),
+            input,
+        )
+    })
+}
+
+/// Skip any number of elements, such as whitespace or comments, until the parser fails or stops consuming input
+pub fn skip_many<'a, T, E>(
+    parser: impl Parser<'a, T, E>,
+    mut input: &'a str,
+) -> ParserResult<'a, (), E> {
+    loop {
+        let res = parser.parse(input);
+        if !res.is_ok() || res.source.len() == input.len() {
+            return ParserResult::from_val((), input);
+        }
+        input = res.source;
+    }
+}
+
+/// Run a parser on input which is known to be final, turning an incomplete result into an
+/// [UnexpectedEndOfFile](ParserError::UnexpectedEndOfFile) error where more input was needed
+pub fn complete<'a, T, E: From<ParserError>>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let res = parser.parse(input);
+    match res.typ {
+        ParserResultType::Incomplete(_) => {
+            ParserResult::from_err(ParserError::UnexpectedEndOfFile.into(), res.source)
+        }
+        _ => res,
+    }
+}
+
 /// Create a [ParsingIterator] from a parser and source slice
 pub fn iter<'a, 'b, T: 'a, E: 'a>(
     parser: impl Parser<'a, T, E> + 'b,
     source: &'b mut &'a str,
-) -> impl ParsingIterator<'a, T, E> + 'b {
+) -> ParsIter<'a, 'b, T, E, impl Parser<'a, T, E> + 'b> {
     ParsIter {
         phantom: Default::default(),
         source,
         parser,
         err: false,
+        stalled: false,
+    }
+}
+
+/// Create a [ParsingIterator] from a parser and source slice, which tracks its own position instead of borrowing the slice mutably
+pub fn iter_owned<'a, T: 'a, E: 'a>(
+    parser: impl Parser<'a, T, E>,
+    source: &'a str,
+) -> OwnedParsIter<'a, T, E, impl Parser<'a, T, E>> {
+    OwnedParsIter {
+        phantom: Default::default(),
+        original: source,
+        offset: 0,
+        parser,
+        err: false,
+        stalled: false,
     }
 }
 
 /// Create a [ParsingIterator] that parses delimited lists of values from an element parser, delimiter parser, and source slice
+///
+/// Elements are parsed lazily, and the source is only advanced past a delimiter once the element following it has been parsed
 pub fn iter_delimited<'a, 'b, Elem: 'a, Delim: 'a, Error: 'a, DelimError: Into<Error> + 'a>(
     elem_parser: impl Parser<'a, Elem, Error> + 'b,
     delim_parser: impl Parser<'a, Delim, DelimError> + 'b,
     source: &'b mut &'a str,
-) -> impl ParsingIterator<'a, Elem, Error> + 'b {
+) -> ParsIterDelim<
+    'a,
+    'b,
+    Elem,
+    Delim,
+    Error,
+    impl Parser<'a, Elem, Error> + 'b,
+    impl Parser<'a, Delim, Error> + 'b,
+> {
     ParsIterDelim {
         phantom: Default::default(),
         source,
         elem_parser,
         delim_parser: move |s| delim_parser.parse(s).err_into(),
         err: false,
+        stalled: false,
         first: true,
     }
 }
 
+/// Create a [ParsingIterator] which also yields the byte range of each element, relative to the source slice it was created with
+pub fn iter_spanned<'a, 'b, T: 'a, E: From<ParserError> + 'a>(
+    parser: impl Parser<'a, T, E> + 'b,
+    source: &'b mut &'a str,
+) -> impl SourceIterator<'a, (T, Range<usize>), E> + 'b {
+    let original = *source;
+    iter(
+        move |s| {
+            spanned(|s| parser.parse(s), original, s).map(|spanned| (spanned.value, spanned.range))
+        },
+        source,
+    )
+}
+
+/// A parsed value along with the byte range it was parsed from in the original input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Spanned<T> {
+    pub value: T,
+    pub range: Range<usize>,
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for Spanned<T> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("Spanned", 3)?;
+        state.serialize_field("value", &self.value)?;
+        state.serialize_field("start", &self.range.start)?;
+        state.serialize_field("end", &self.range.end)?;
+        state.end()
+    }
+}
+
+/// Parse a value along with the byte range it occupies in the original input
+pub fn spanned<'a, T, E>(
+    parser: impl Parser<'a, T, E>,
+    original: &'a str,
+    input: &'a str,
+) -> ParserResult<'a, Spanned<T>, E> {
+    let start = original.len() - input.len();
+    let res = parser.parse(input);
+    let end = original.len() - res.source.len();
+    res.map(|value| Spanned {
+        value,
+        range: start..end,
+    })
+}
+
+/// Parse a value along with the exact slice of input it consumed, for keeping the original text alongside it
+pub fn consumed<'a, T, E>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, (T, &'a str), E> {
+    let res = parser.parse(input);
+    let slice = res.slice(input);
+    res.map(|value| (value, slice))
+}
+
 /// Explicitly ignore the output of a parser, advancing the parsing head using a mutable reference
 pub fn ignore<'a, 'b, T, E>(
     parser: impl Parser<'a, T, E>,
@@ -202,15 +968,21 @@ pub fn ignore<'a, 'b, T, E>(
 }
 
 /// Check a single character of the input without consuming it
-pub fn peek(input: &str) -> ParserResult<char, ParserError> {
+pub fn peek(input: &str) -> ParserResult<'_, char, ParserError> {
     match input.chars().next() {
         Some(c) => ParserResult::from_val(c, input),
         None => ParserResult::from_err(ParserError::UnexpectedEndOfFile, input),
     }
 }
 
+/// Check up to `n` characters of the input without consuming them, returning fewer only at the end of the input
+pub fn lookahead(n: usize, input: &str) -> ParserResult<'_, &str, ParserError> {
+    let end = input.char_indices().nth(n).map_or(input.len(), |(i, _)| i);
+    ParserResult::from_val(&input[..end], input)
+}
+
 /// Consume a single character from the input
-pub fn advance(input: &str) -> ParserResult<char, ParserError> {
+pub fn advance(input: &str) -> ParserResult<'_, char, ParserError> {
     match input.chars().next() {
         Some(c) => ParserResult::from_val(c, &input[c.len_utf8()..]),
         None => ParserResult::from_err(ParserError::UnexpectedEndOfFile, input),
@@ -0,0 +1,167 @@
+use std::{error::Error, fmt::Display, io::BufRead};
+
+use crate::{error::ParserError, line, Parser, ParserResult, ParserResultType};
+
+/// An error from parsing a single line with [lines] or [read_lines], along with the line number it occurred on, starting from 1
+#[derive(Debug, Clone, PartialEq)]
+pub struct LineError<E> {
+    pub line: usize,
+    pub error: E,
+}
+
+impl<E: Display> Display for LineError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Line {}: {}", self.line, self.error)
+    }
+}
+
+impl<E: Error + 'static> Error for LineError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<E: serde::Serialize> serde::Serialize for LineError<E> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeStruct;
+        let mut state = serializer.serialize_struct("LineError", 2)?;
+        state.serialize_field("line", &self.line)?;
+        state.serialize_field("error", &self.error)?;
+        state.end()
+    }
+}
+
+/// An iterator applying a parser to each line of the input, created with [lines]
+pub struct Lines<'a, T, E, P>
+where
+    P: Parser<'a, T, E>,
+{
+    parser: P,
+    source: &'a str,
+    line: usize,
+    phantom: std::marker::PhantomData<(T, E)>,
+}
+
+impl<'a, T, E: From<ParserError>, P: Parser<'a, T, E>> Iterator for Lines<'a, T, E, P> {
+    type Item = Result<T, LineError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (text, rest) = line(self.source).into_result::<()>().ok()?;
+        self.source = rest;
+        self.line += 1;
+        Some(line_result(parse_line(&self.parser, text), self.line))
+    }
+}
+
+/// Convert the result of parsing a line, treating it running out of input as an error since the line has ended
+pub(crate) fn line_result<T, E: From<ParserError>>(
+    res: ParserResult<'_, T, E>,
+    line: usize,
+) -> Result<T, LineError<E>> {
+    match res.typ {
+        ParserResultType::Ok(val) => Ok(val),
+        ParserResultType::Err(error) => Err(LineError { line, error }),
+        ParserResultType::Incomplete(_) => Err(LineError {
+            line,
+            error: ParserError::UnexpectedEndOfFile.into(),
+        }),
+    }
+}
+
+/// Parse an entire line, failing if the parser doesn't consume all of it
+pub(crate) fn parse_line<'a, T, E: From<ParserError>>(
+    parser: &impl Parser<'a, T, E>,
+    text: &'a str,
+) -> ParserResult<'a, T, E> {
+    let res = parser.parse(text);
+    if res.is_ok() && !res.source.is_empty() {
+        return ParserResult::from_err(
+            ParserError::ExpectedToken("end of line".into()).into(),
+            res.source,
+        );
+    }
+    res
+}
+
+/// Apply a parser to each line of the input, which must consume the entire line, attaching line numbers to errors
+pub fn lines<'a, T, E: From<ParserError>, P: Parser<'a, T, E>>(
+    parser: P,
+    input: &'a str,
+) -> Lines<'a, T, E, P> {
+    Lines {
+        parser,
+        source: input,
+        line: 0,
+        phantom: Default::default(),
+    }
+}
+
+/// An iterator applying a parser to each line read from a [BufRead], created with [read_lines]
+pub struct ReadLines<R, T, E, P> {
+    parser: P,
+    reader: R,
+    buffer: String,
+    line: usize,
+    done: bool,
+    phantom: std::marker::PhantomData<(T, E)>,
+}
+
+impl<R: BufRead, T, E: From<ParserError>, P: for<'a> Parser<'a, T, E>> Iterator
+    for ReadLines<R, T, E, P>
+{
+    type Item = Result<(usize, T), LineError<E>>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        self.buffer.clear();
+        self.line += 1;
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(0) => {
+                self.done = true;
+                return None;
+            }
+            Ok(_) => {}
+            Err(err) => {
+                self.done = true;
+                return Some(Err(LineError {
+                    line: self.line,
+                    error: ParserError::external(err).into(),
+                }));
+            }
+        }
+        let text = self.buffer.strip_suffix('\n').unwrap_or(&self.buffer);
+        let text = text.strip_suffix('\r').unwrap_or(text);
+        let res = parse_line(&self.parser, text);
+        let error = match res.typ {
+            ParserResultType::Ok(val) => return Some(Ok((self.line, val))),
+            ParserResultType::Err(error) => error,
+            ParserResultType::Incomplete(_) => ParserError::UnexpectedEndOfFile.into(),
+        };
+        Some(Err(LineError {
+            line: self.line,
+            error,
+        }))
+    }
+}
+
+/// Apply a parser to each line read from a reader, such as a large log file, yielding each record with its line
+/// number without reading the whole input into memory
+///
+/// The parser must consume the entire line, and produce values which don't borrow from it. A read error ends the
+/// iterator after being reported as an [External](ParserError::External) error on the line it occurred on.
+pub fn read_lines<R: BufRead, T, E: From<ParserError>, P: for<'a> Parser<'a, T, E>>(
+    parser: P,
+    reader: R,
+) -> ReadLines<R, T, E, P> {
+    ReadLines {
+        parser,
+        reader,
+        buffer: String::new(),
+        line: 0,
+        done: false,
+        phantom: Default::default(),
+    }
+}
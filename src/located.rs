@@ -0,0 +1,91 @@
+use std::ops::Range;
+
+use crate::{diagnostic::Position, spanned, Parser, ParserResult, Spanned};
+
+/// A position in an input which keeps the whole input alongside it, so that spans and slices can be found without
+/// passing the original input to every call
+///
+/// Parsers still take plain string slices, so this is used by running them with [parse](Located::parse), which
+/// advances past whatever they consume.
+/// Example:
+/// ```
+/// use crochet::{located::Located, num::integer, *};
+/// let mut input = Located::new("12 345");
+/// let first = input.parse(integer::<u32>).unwrap();
+/// input.parse(|s| literal(" ", s)).unwrap();
+/// let second = input.parse(integer::<u32>).unwrap();
+/// assert_eq!((first.range, second.range), (0..2, 3..6));
+/// assert!(input.is_empty());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Located<'a> {
+    original: &'a str,
+    offset: usize,
+}
+
+impl<'a> Located<'a> {
+    /// Start at the beginning of an input
+    pub fn new(original: &'a str) -> Self {
+        Self {
+            original,
+            offset: 0,
+        }
+    }
+
+    /// Locate a slice of the remaining input of a parser, which must be a suffix of the same original input
+    pub fn at(original: &'a str, remaining: &'a str) -> Self {
+        debug_assert!(original.ends_with(remaining));
+        Self {
+            original,
+            offset: original.len() - remaining.len(),
+        }
+    }
+
+    /// The whole input
+    pub fn original(&self) -> &'a str {
+        self.original
+    }
+
+    /// The input which has not yet been parsed
+    pub fn remaining(&self) -> &'a str {
+        &self.original[self.offset..]
+    }
+
+    /// The byte offset of the remaining input within the original input
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+
+    /// Whether all of the input has been parsed
+    pub fn is_empty(&self) -> bool {
+        self.offset == self.original.len()
+    }
+
+    /// The line and UTF-16 character offset of this position, see [Position]
+    pub fn position(&self) -> Position {
+        Position::at(self.original, self.offset)
+    }
+
+    /// The byte range from this position to a later one
+    pub fn span_to(&self, end: Located<'a>) -> Range<usize> {
+        self.offset..end.offset
+    }
+
+    /// The input between this position and a later one
+    pub fn slice_to(&self, end: Located<'a>) -> &'a str {
+        &self.original[self.offset..end.offset]
+    }
+
+    /// Run a parser on the remaining input, returning its value along with the byte range it was parsed from and
+    /// advancing past it if it succeeded
+    pub fn parse<T, E>(
+        &mut self,
+        parser: impl Parser<'a, T, E>,
+    ) -> ParserResult<'a, Spanned<T>, E> {
+        let res = spanned(parser, self.original, self.remaining());
+        if res.is_ok() {
+            self.offset = self.original.len() - res.source.len();
+        }
+        res
+    }
+}
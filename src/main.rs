@@ -1,5 +1,3 @@
-#![feature(try_trait_v2)]
-
 // use std::{
 //     cell::LazyCell,
 //     sync::{Arc, RwLock},
@@ -0,0 +1,86 @@
+use std::{cell::RefCell, error::Error};
+
+use ::nom::{
+    error::{ErrorKind, FromExternalError, ParseError},
+    IResult,
+};
+
+use crate::{error::ParserError, Needed, Parser, ParserResult, ParserResultType};
+
+impl From<::nom::Needed> for Needed {
+    fn from(needed: ::nom::Needed) -> Self {
+        match needed {
+            ::nom::Needed::Unknown => Needed::Unknown,
+            ::nom::Needed::Size(n) => Needed::Size(n),
+        }
+    }
+}
+
+impl From<Needed> for ::nom::Needed {
+    fn from(needed: Needed) -> Self {
+        match needed {
+            Needed::Unknown => ::nom::Needed::Unknown,
+            Needed::Size(n) => ::nom::Needed::Size(n),
+        }
+    }
+}
+
+/// Lets [ParserError] be used as the error type of nom parsers, so that grammars mixing both don't need to convert
+/// errors at each boundary
+impl ParseError<&str> for ParserError {
+    fn from_error_kind(_input: &str, kind: ErrorKind) -> Self {
+        ParserError::ExpectedToken(kind.description().to_string().into())
+    }
+
+    fn append(_input: &str, _kind: ErrorKind, other: Self) -> Self {
+        other
+    }
+}
+
+impl<E: Error + Send + Sync + 'static> FromExternalError<&str, E> for ParserError {
+    fn from_external_error(_input: &str, _kind: ErrorKind, err: E) -> Self {
+        ParserError::external(err)
+    }
+}
+
+/// A nom parser wrapped as a [Parser], created with [from_nom]
+pub struct FromNom<P>(RefCell<P>);
+
+impl<'a, T, E, P: ::nom::Parser<&'a str, Output = T, Error = E>> Parser<'a, T, E> for FromNom<P> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        match self.0.borrow_mut().parse(input) {
+            Ok((rest, val)) => ParserResult::from_val(val, rest),
+            Err(::nom::Err::Incomplete(needed)) => {
+                ParserResult::incomplete_needing(needed.into(), input)
+            }
+            Err(::nom::Err::Error(err) | ::nom::Err::Failure(err)) => {
+                ParserResult::from_err(err, input)
+            }
+        }
+    }
+}
+
+/// Wrap a nom parser as a [Parser], so that nom grammars can be used from crochet while migrating
+///
+/// Errors are reported where the nom parser started, since nom errors carry their own position. nom parsers take
+/// `&mut self`, so the wrapped parser must not be run again from within itself.
+pub fn from_nom<'a, P: ::nom::Parser<&'a str>>(parser: P) -> FromNom<P> {
+    FromNom(RefCell::new(parser))
+}
+
+/// Wrap a [Parser] as a nom parser, so that crochet grammars can be used from nom while migrating
+///
+/// Errors become recoverable [Error](::nom::Err::Error)s, and incomplete results become
+/// [Incomplete](::nom::Err::Incomplete) with the same [Needed].
+pub fn to_nom<'a, T, E>(
+    parser: impl Parser<'a, T, E>,
+) -> impl FnMut(&'a str) -> IResult<&'a str, T, E> {
+    move |input| {
+        let res = parser.parse(input);
+        match res.typ {
+            ParserResultType::Ok(val) => Ok((res.source, val)),
+            ParserResultType::Err(err) => Err(::nom::Err::Error(err)),
+            ParserResultType::Incomplete(needed) => Err(::nom::Err::Incomplete(needed.into())),
+        }
+    }
+}
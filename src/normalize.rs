@@ -0,0 +1,59 @@
+use std::borrow::Cow;
+
+use unicode_normalization::{char::canonical_combining_class, is_nfc, UnicodeNormalization};
+
+use crate::{error::ParserError, identifier, try_parse, Literal, ParserResult};
+
+/// Normalize a string to NFC, borrowing it if it already is
+pub fn to_nfc(s: &str) -> Cow<'_, str> {
+    if is_nfc(s) {
+        Cow::Borrowed(s)
+    } else {
+        Cow::Owned(s.nfc().collect())
+    }
+}
+
+/// Split off the next starter character along with the combining characters following it, which normalization can't
+/// move across
+fn segment(input: &str) -> Option<(&str, &str)> {
+    let mut chars = input.char_indices();
+    chars.next()?;
+    let end = chars
+        .find(|(_, c)| canonical_combining_class(*c) == 0)
+        .map_or(input.len(), |(i, _)| i);
+    Some(input.split_at(end))
+}
+
+/// Parse a literal string token, matching any input which is canonically equivalent to it, so that precomposed and
+/// decomposed accents both match, returning the slice of input which matched
+///
+/// The match must end where a new character starts, so `e` doesn't match the start of `e` followed by a combining
+/// accent.
+pub fn literal_nfc<'a>(
+    literal: impl Into<Literal>,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    let Literal(literal) = literal.into();
+    let target: String = literal.nfd().collect();
+    let mut remaining = target.as_str();
+    let mut s = input;
+    while !remaining.is_empty() {
+        let matched = segment(s).and_then(|(segment, rest)| {
+            let decomposed: String = segment.nfd().collect();
+            remaining = remaining.strip_prefix(decomposed.as_str())?;
+            Some(rest)
+        });
+        match matched {
+            Some(rest) => s = rest,
+            None => return ParserResult::from_err(ParserError::ExpectedLiteral(literal), input),
+        }
+    }
+    ParserResult::from_val(&input[..input.len() - s.len()], s)
+}
+
+/// Parse an identifier like [identifier](crate::identifier), normalized to NFC so that canonically equivalent
+/// identifiers compare equal
+pub fn identifier_nfc(input: &str) -> ParserResult<'_, Cow<'_, str>, ParserError> {
+    let (ident, s) = try_parse!(identifier(input));
+    ParserResult::from_val(to_nfc(ident), s)
+}
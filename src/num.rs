@@ -0,0 +1,283 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{error::ParserError, literal, matching_char, take_while, try_parse, ParserResult};
+
+/// A primitive integer type which digits can be accumulated into without overflowing
+pub trait Integer: Copy {
+    const ZERO: Self;
+
+    /// Shift in one more digit of the given radix, subtracting it instead for negative numbers
+    fn push_digit(self, radix: u32, digit: u32, negative: bool) -> Option<Self>;
+}
+
+macro_rules! impl_integer {
+    ($($t:ty),+) => {
+        $(
+            impl Integer for $t {
+                const ZERO: Self = 0;
+
+                fn push_digit(self, radix: u32, digit: u32, negative: bool) -> Option<Self> {
+                    let shifted = self.checked_mul(radix as Self)?;
+                    if negative {
+                        shifted.checked_sub(digit as Self)
+                    } else {
+                        shifted.checked_add(digit as Self)
+                    }
+                }
+            }
+        )+
+    };
+}
+
+impl_integer!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+
+/// Panic with a clear message for a radix which [char::to_digit] doesn't support, rather than in the middle of parsing
+fn check_radix(radix: u32) {
+    assert!(
+        (2..=36).contains(&radix),
+        "radix must be between 2 and 36, got {radix}"
+    );
+}
+
+/// Accumulate digits, negating as it goes so that the minimum value of signed types can be parsed
+fn signed_digits<T: Integer>(
+    radix: u32,
+    negative: bool,
+    input: &str,
+) -> ParserResult<'_, T, ParserError> {
+    check_radix(radix);
+    let mut value = T::ZERO;
+    let mut end = 0;
+    for (i, c) in input.char_indices() {
+        if c != '_' || end == 0 {
+            let Some(digit) = c.to_digit(radix) else {
+                break;
+            };
+            let Some(next) = value.push_digit(radix, digit, negative) else {
+                return ParserResult::from_err(ParserError::IntegerOverflow, input);
+            };
+            value = next;
+        }
+        end = i + c.len_utf8();
+    }
+    if end == 0 {
+        return ParserResult::from_err(ParserError::ExpectedToken("digit".into()), input);
+    }
+    ParserResult::from_val(value, &input[end..])
+}
+
+/// Parse unsigned digits in the given radix, allowing `_` separators after the first digit
+///
+/// Panics if `radix` isn't between 2 and 36.
+pub fn digits_radix<T: Integer>(radix: u32, input: &str) -> ParserResult<'_, T, ParserError> {
+    signed_digits(radix, false, input)
+}
+
+/// Parse a single digit in the given radix, returning its value
+///
+/// Panics if `radix` isn't between 2 and 36.
+pub fn digit(radix: u32, input: &str) -> ParserResult<'_, u32, ParserError> {
+    check_radix(radix);
+    match input.chars().next().and_then(|c| c.to_digit(radix)) {
+        Some(digit) => ParserResult::from_val(digit, &input[1..]),
+        None => ParserResult::from_err(ParserError::ExpectedToken("digit".into()), input),
+    }
+}
+
+/// Accumulate at least one digit without separators, negating as it goes like [signed_digits]
+fn accumulate<T: Integer>(
+    radix: u32,
+    negative: bool,
+    input: &str,
+) -> ParserResult<'_, T, ParserError> {
+    let (first, mut s) = try_parse!(digit(radix, input));
+    let mut value = T::ZERO.push_digit(radix, first, negative);
+    while let (Some(prev), Some(digit)) = (value, s.chars().next().and_then(|c| c.to_digit(radix)))
+    {
+        value = prev.push_digit(radix, digit, negative);
+        s = &s[1..];
+    }
+    match value {
+        Some(value) => ParserResult::from_val(value, s),
+        None => ParserResult::from_err(ParserError::IntegerOverflow, input),
+    }
+}
+
+/// Parse at least one digit in the given radix, without separators, accumulating them into an integer
+///
+/// Panics if `radix` isn't between 2 and 36.
+pub fn digits1<T: Integer>(radix: u32, input: &str) -> ParserResult<'_, T, ParserError> {
+    accumulate(radix, false, input)
+}
+
+/// Parse a decimal integer with an optional sign into any primitive integer type, reporting overflow as an error
+pub fn integer<T: Integer>(input: &str) -> ParserResult<'_, T, ParserError> {
+    let (sign, s) = try_parse!(sign(input));
+    accumulate(10, sign == Some('-'), s)
+}
+
+/// Parse an integer literal with an optional sign, an optional `0x`, `0o` or `0b` radix prefix, and `_` digit separators
+pub fn int_literal<T: Integer>(input: &str) -> ParserResult<'_, T, ParserError> {
+    let (sign, s) =
+        try_parse!(matching_char("sign", |c| c == '+' || c == '-', input).optional(input));
+    let (radix, s) = match s.get(..2) {
+        Some("0x" | "0X") => (16, &s[2..]),
+        Some("0o" | "0O") => (8, &s[2..]),
+        Some("0b" | "0B") => (2, &s[2..]),
+        _ => (10, s),
+    };
+    signed_digits(radix, sign == Some('-'), s)
+}
+
+/// A primitive floating-point type
+pub trait Float: FromStr {}
+
+impl Float for f32 {}
+impl Float for f64 {}
+
+fn digits(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("digit", |c| c.is_ascii_digit(), input)
+}
+
+fn sign(input: &str) -> ParserResult<'_, Option<char>, ParserError> {
+    matching_char("sign", |c| c == '+' || c == '-', input).optional(input)
+}
+
+/// Recognize a decimal floating-point number such as `-1.5e10`, `.5` or `1.`, returning its slice without converting it
+pub fn float_slice(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let (_, s) = try_parse!(sign(input));
+    let (int, s) = try_parse!(digits(s).optional(s));
+    let (frac, s) = try_parse!(literal(".", s)
+        .and(|s| digits(s).optional(s))
+        .map(|(_, frac)| frac)
+        .optional(s));
+    if int.is_none() && frac.flatten().is_none() {
+        return ParserResult::from_err(ParserError::ExpectedToken("float".into()), input);
+    }
+    let (_, s) = try_parse!(matching_char("exponent", |c| c == 'e' || c == 'E', s)
+        .and(sign)
+        .and(digits)
+        .optional(s));
+    ParserResult::from_val(&input[..input.len() - s.len()], s)
+}
+
+fn parse_float<'a, T: Float>(slice: &'a str, rest: &'a str) -> ParserResult<'a, T, ParserError> {
+    match slice.parse() {
+        Ok(f) => ParserResult::from_val(f, rest),
+        Err(_) => ParserResult::from_err(ParserError::ExpectedToken("float".into()), slice),
+    }
+}
+
+/// Parse a decimal floating-point number, delegating to the standard library for correct rounding
+pub fn float<'a, T: Float>(input: &'a str) -> ParserResult<'a, T, ParserError> {
+    let (slice, rest) = try_parse!(float_slice(input));
+    parse_float(slice, rest)
+}
+
+/// Parse a floating-point number like [float], additionally accepting `inf`, `infinity` and `NaN` in any case
+pub fn float_with_special<'a, T: Float>(input: &'a str) -> ParserResult<'a, T, ParserError> {
+    let (_, s) = try_parse!(sign(input));
+    let special = ["infinity", "inf", "nan"].into_iter().find(|word| {
+        s.get(..word.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(word))
+    });
+    match special {
+        Some(word) => {
+            let end = input.len() - s.len() + word.len();
+            parse_float(&input[..end], &input[end..])
+        }
+        None => float(input),
+    }
+}
+
+/// An exact decimal number, equal to `digits` divided by ten to the power of `scale` and negated if `negative`, which
+/// keeps trailing zeros in the fraction since they are significant in formats such as prices
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Decimal {
+    pub negative: bool,
+    /// The digits without leading zeros, or `0` for zero
+    pub digits: String,
+    pub scale: u32,
+}
+
+impl Decimal {
+    /// Get the digits as a signed integer, if they fit in an `i128`
+    pub fn mantissa(&self) -> Option<i128> {
+        let n: i128 = self.digits.parse().ok()?;
+        Some(if self.negative { -n } else { n })
+    }
+}
+
+impl Display for Decimal {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        if self.negative {
+            f.write_str("-")?;
+        }
+        let scale = self.scale as usize;
+        if scale == 0 {
+            return f.write_str(&self.digits);
+        }
+        let digits = format!("{:0>width$}", self.digits, width = scale + 1);
+        let (int, frac) = digits.split_at(digits.len() - scale);
+        write!(f, "{int}.{frac}")
+    }
+}
+
+/// Parse a decimal number such as `-12.50` exactly rather than rounding it to a float, without accepting an exponent
+pub fn decimal(input: &str) -> ParserResult<'_, Decimal, ParserError> {
+    let (sign, s) = try_parse!(sign(input));
+    let (int, s) = try_parse!(digits(s).optional(s));
+    let (frac, s) = try_parse!(literal(".", s)
+        .and(|s| digits(s).optional(s))
+        .map(|(_, frac)| frac)
+        .optional(s));
+    let (int, frac) = (int.unwrap_or(""), frac.flatten().unwrap_or(""));
+    if int.is_empty() && frac.is_empty() {
+        return ParserResult::from_err(ParserError::ExpectedToken("decimal".into()), input);
+    }
+    let Ok(scale) = u32::try_from(frac.len()) else {
+        return ParserResult::from_err(ParserError::IntegerOverflow, input);
+    };
+    let digits = format!("{int}{frac}");
+    let digits = match digits.trim_start_matches('0') {
+        "" => "0".to_string(),
+        trimmed => trimmed.to_string(),
+    };
+    let decimal = Decimal {
+        negative: sign == Some('-'),
+        digits,
+        scale,
+    };
+    ParserResult::from_val(decimal, s)
+}
+
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal_impls {
+    use super::*;
+
+    impl TryFrom<Decimal> for rust_decimal::Decimal {
+        type Error = ParserError;
+
+        fn try_from(d: Decimal) -> Result<Self, Self::Error> {
+            let mantissa = d.mantissa().ok_or(ParserError::IntegerOverflow)?;
+            rust_decimal::Decimal::try_from_i128_with_scale(mantissa, d.scale)
+                .map_err(|_| ParserError::IntegerOverflow)
+        }
+    }
+}
+
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal_impls {
+    use super::*;
+    use bigdecimal::num_bigint::{BigInt, Sign};
+
+    impl From<Decimal> for bigdecimal::BigDecimal {
+        fn from(d: Decimal) -> Self {
+            // The digits are always ASCII digits, so they always parse
+            let digits = BigInt::parse_bytes(d.digits.as_bytes(), 10).unwrap();
+            let sign = if d.negative { Sign::Minus } else { Sign::Plus };
+            let (_, magnitude) = digits.into_parts();
+            bigdecimal::BigDecimal::new(BigInt::from_biguint(sign, magnitude), i64::from(d.scale))
+        }
+    }
+}
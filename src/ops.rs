@@ -0,0 +1,77 @@
+use std::ops::{Add, BitOr, Mul, RangeBounds};
+
+use crate::{error::ParserError, repeating, Parser, ParserResult};
+
+/// A wrapper around a parser which allows composing it with operators: `|` for alternation, `+` for sequencing into a
+/// tuple, and `*` with a range for repetition
+///
+/// There is no `>>` for sequencing which discards a value, or for mapping, since the output type of a [Parser] is a
+/// parameter rather than an associated type, so an operator could never name the type it discards or maps from. Use
+/// [Parser::then] and [Parser::map] instead.
+#[derive(Debug, Clone, Copy)]
+pub struct P<A>(pub A);
+
+/// Parsers combined with `|`, trying the second if the first fails
+#[derive(Debug, Clone, Copy)]
+pub struct Or<A, B>(A, B);
+
+/// Parsers combined with `+`, parsing both and returning their values in a tuple
+#[derive(Debug, Clone, Copy)]
+pub struct And<A, B>(A, B);
+
+/// A parser combined with a range using `*`, parsing it a number of times within the range
+#[derive(Debug, Clone, Copy)]
+pub struct Repeat<A, R>(A, R);
+
+impl<'a, T, E, A: Parser<'a, T, E>> Parser<'a, T, E> for P<A> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        self.0.parse(input)
+    }
+}
+
+impl<'a, T, E, A: Parser<'a, T, E>, B: Parser<'a, T, E>> Parser<'a, T, E> for Or<A, B> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        self.0.parse(input).or(|s| self.1.parse(s), input)
+    }
+}
+
+impl<'a, T, V, E, A: Parser<'a, T, E>, B: Parser<'a, V, E>> Parser<'a, (T, V), E> for And<A, B> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, (T, V), E> {
+        self.0.parse(input).and(|s| self.1.parse(s))
+    }
+}
+
+impl<'a, T, E, A, R> Parser<'a, Vec<T>, E> for Repeat<A, R>
+where
+    A: Parser<'a, T, E>,
+    R: RangeBounds<usize> + Clone + 'a,
+    E: From<ParserError>,
+{
+    fn parse(&self, input: &'a str) -> ParserResult<'a, Vec<T>, E> {
+        repeating(|s| self.0.parse(s), self.1.clone(), input)
+    }
+}
+
+impl<A, B> BitOr<B> for P<A> {
+    type Output = P<Or<A, B>>;
+
+    fn bitor(self, rhs: B) -> Self::Output {
+        P(Or(self.0, rhs))
+    }
+}
+
+impl<A, B> Add<B> for P<A> {
+    type Output = P<And<A, B>>;
+
+    fn add(self, rhs: B) -> Self::Output {
+        P(And(self.0, rhs))
+    }
+}
+
+impl<A, R: RangeBounds<usize>> Mul<R> for P<A> {
+    type Output = P<Repeat<A, R>>;
+
+    fn mul(self, rhs: R) -> Self::Output {
+        P(Repeat(self.0, rhs))
+    }
+}
@@ -0,0 +1,44 @@
+use rayon::prelude::*;
+
+use crate::{
+    error::ParserError,
+    lines::{line_result, parse_line, LineError},
+    Parser,
+};
+
+/// Split the input into records on a delimiter and parse them in parallel, returning the results in the order the
+/// records appeared, with errors numbered by record starting from 1
+///
+/// The parser must consume each entire record. A delimiter at the very end of the input doesn't start another record,
+/// and when the delimiter is `'\n'` a `'\r'` before it is removed so that CRLF line endings work like [lines](crate::lines::lines).
+pub fn par_records<'a, T: Send, E: Send + From<ParserError>>(
+    parser: impl Parser<'a, T, E> + Sync,
+    delimiter: char,
+    input: &'a str,
+) -> Vec<Result<T, LineError<E>>> {
+    let input = input.strip_suffix(delimiter).unwrap_or(input);
+    if input.is_empty() {
+        return vec![];
+    }
+    let records: Vec<&str> = input
+        .split(delimiter)
+        .map(|record| match delimiter {
+            '\n' => record.strip_suffix('\r').unwrap_or(record),
+            _ => record,
+        })
+        .collect();
+    records
+        .into_par_iter()
+        .enumerate()
+        .map(|(i, record)| line_result(parse_line(&parser, record), i + 1))
+        .collect()
+}
+
+/// Parse the records of the input in parallel like [par_records], stopping at the first error in record order
+pub fn try_par_records<'a, T: Send, E: Send + From<ParserError>>(
+    parser: impl Parser<'a, T, E> + Sync,
+    delimiter: char,
+    input: &'a str,
+) -> Result<Vec<T>, LineError<E>> {
+    par_records(parser, delimiter, input).into_iter().collect()
+}
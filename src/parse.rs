@@ -0,0 +1,49 @@
+use crate::{
+    error::ParserError,
+    matching_char,
+    num::{float, int_literal},
+    ParserResult,
+};
+
+/// A type which knows how to parse itself, which can be derived with the `derive` feature
+pub trait Parse<'a>: Sized {
+    type Error;
+
+    /// Parse a value from the input
+    fn parse(input: &'a str) -> ParserResult<'a, Self, Self::Error>;
+}
+
+macro_rules! impl_parse {
+    ($parser:ident => $($t:ty),+) => {
+        $(
+            impl<'a> Parse<'a> for $t {
+                type Error = ParserError;
+
+                fn parse(input: &'a str) -> ParserResult<'a, Self, ParserError> {
+                    $parser(input)
+                }
+            }
+        )+
+    };
+}
+
+impl_parse!(int_literal => u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize);
+impl_parse!(float => f32, f64);
+
+impl<'a> Parse<'a> for char {
+    type Error = ParserError;
+
+    fn parse(input: &'a str) -> ParserResult<'a, Self, ParserError> {
+        matching_char("char", |_| true, input)
+    }
+}
+
+impl<'a> Parse<'a> for bool {
+    type Error = ParserError;
+
+    fn parse(input: &'a str) -> ParserResult<'a, Self, ParserError> {
+        crate::literal("true", input)
+            .is(true)
+            .or(|s| crate::literal("false", s).is(false), input)
+    }
+}
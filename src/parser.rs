@@ -1,50 +1,344 @@
-use std::ops::RangeBounds;
+use std::{borrow::Cow, ops::RangeBounds, rc::Rc, str::FromStr, sync::Arc};
 
-use crate::{repeating, ParserResult};
+use crate::{
+    array, error::ParserError, repeating, skip_many, spanned, try_parse, ParserResult,
+    ParserResultType, Spanned,
+};
 
+/// A parser stored on the heap, for when its type can't be named or differs between branches
+pub type BoxedParser<'a, T, E> = Box<dyn Parser<'a, T, E> + 'a>;
+
+/// A parser producing values of type `T` or errors of type `E`, which can be used as a trait object since its
+/// combinators all require `Self: Sized`
 pub trait Parser<'a, T, E> {
     /// Parse a value from the input
     fn parse(&self, input: &'a str) -> ParserResult<'a, T, E>;
 
     /// Map the output type of this parser using a mapping function
-    fn map<V>(&self, f: impl Fn(T) -> V) -> impl Parser<'a, V, E> {
+    fn map<V>(&self, f: impl Fn(T) -> V) -> impl Parser<'a, V, E>
+    where
+        Self: Sized,
+    {
         move |s| self.parse(s).map(&f)
     }
 
+    /// Call a function with each value this parser produces, such as to log it or count it, without changing its
+    /// results
+    fn inspect(&self, f: impl Fn(&T)) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).inspect(&f)
+    }
+
+    /// Call a function with each error this parser fails with and the input it occurred at, without changing its
+    /// results
+    fn inspect_err(&self, f: impl Fn(&E, &'a str)) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).inspect_err(&f)
+    }
+
+    /// Map the output type of this parser using a fallible mapping function, converting its error into the parser's
+    /// error type and reporting it where this parser started
+    fn map_res<V, E2: Into<E>>(&self, f: impl Fn(T) -> Result<V, E2>) -> impl Parser<'a, V, E>
+    where
+        Self: Sized,
+    {
+        move |s| {
+            let (val, rest) = try_parse!(self.parse(s));
+            match f(val) {
+                Ok(val) => ParserResult::from_val(val, rest),
+                Err(err) => ParserResult::from_err(err.into(), s),
+            }
+        }
+    }
+
+    /// Convert the slice consumed by this parser into a value with [FromStr], converting its error into the parser's
+    /// error type and reporting it where this parser started
+    fn parse_to<V: FromStr>(&self) -> impl Parser<'a, V, E>
+    where
+        Self: Sized,
+        V::Err: Into<E>,
+    {
+        move |s| {
+            let (slice, rest) = try_parse!(self.parse(s).parsed_slice(s));
+            match slice.parse() {
+                Ok(val) => ParserResult::from_val(val, rest),
+                Err(err) => ParserResult::from_err(V::Err::into(err), s),
+            }
+        }
+    }
+
     /// Make this parser optional, parsing nothing if it would otherwise fail
-    fn optional(&self) -> impl Parser<'a, Option<T>, E> {
+    fn optional(&self) -> impl Parser<'a, Option<T>, E>
+    where
+        Self: Sized,
+    {
         |s| self.parse(s).optional(s)
     }
 
+    /// Shorthand for [optional](Parser::optional)
+    fn opt(&self) -> impl Parser<'a, Option<T>, E>
+    where
+        Self: Sized,
+    {
+        self.optional()
+    }
+
     /// Make this parser parse an additional value, returned in a tuple
-    fn and<V>(&self, other: impl Parser<'a, V, E>) -> impl Parser<'a, (T, V), E> {
+    fn and<V>(&self, other: impl Parser<'a, V, E>) -> impl Parser<'a, (T, V), E>
+    where
+        Self: Sized,
+    {
         move |s| self.parse(s).and(|s| other.parse(s))
     }
 
     /// Make this parser try another parser if it fails
-    fn or(&self, other: impl Parser<'a, T, E>) -> impl Parser<'a, T, E> {
+    fn or(&self, other: impl Parser<'a, T, E>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
         move |s| self.parse(s).or(|s| other.parse(s), s)
     }
 
+    /// Shorthand for [or](Parser::or)
+    fn or_p(&self, other: impl Parser<'a, T, E>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        self.or(other)
+    }
+
+    /// Make this parser also try another parser, keeping whichever successful result consumed the most input, or this
+    /// parser's on a tie
+    fn or_longest(&self, other: impl Parser<'a, T, E>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| {
+            let first = self.parse(s);
+            let second = other.parse(s);
+            match (first.is_ok(), second.is_ok()) {
+                (true, true) if second.source.len() < first.source.len() => second,
+                (true, _) => first,
+                _ => second,
+            }
+        }
+    }
+
     /// Make this parser repeat a specific number of times within a range
-    fn repeating(
-        &self,
-        bounds: impl RangeBounds<usize> + Clone + 'a,
-    ) -> impl Parser<'a, Vec<T>, E> {
+    fn repeating(&self, bounds: impl RangeBounds<usize> + Clone + 'a) -> impl Parser<'a, Vec<T>, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
         move |s| repeating(|s| self.parse(s), bounds.clone(), s)
     }
 
+    /// Make this parser repeat exactly `N` times, collecting the values into an array
+    fn array<const N: usize>(&self) -> impl Parser<'a, [T; N], E>
+    where
+        Self: Sized,
+    {
+        move |s| array(|s| self.parse(s), s)
+    }
+
     /// Makes this parser parse an additional value, and explicitly ignore it - useful for elements with trailing whitespace
     fn and_ignore<V, E2>(&self, p: impl Parser<'a, V, E2>) -> impl Parser<'a, T, E>
     where
+        Self: Sized,
         E2: Into<E>,
     {
         move |s| self.parse(s).and(p.err_into()).map(|(v, _)| v)
     }
 
+    /// Make this parser skip any amount of trivia, such as whitespace or comments, after its token
+    fn lexeme<V, E2>(&self, trivia: impl Parser<'a, V, E2>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| {
+            let (v, s) = try_parse!(self.parse(s));
+            ParserResult::from_val(v, skip_many(|s| trivia.parse(s), s).source)
+        }
+    }
+
+    /// Make this parser skip any amount of trivia, such as whitespace or comments, both before and after its token
+    fn padded<V, E2>(&self, trivia: impl Parser<'a, V, E2>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| {
+            let s = skip_many(|s| trivia.parse(s), s).source;
+            let (v, s) = try_parse!(self.parse(s));
+            ParserResult::from_val(v, skip_many(|s| trivia.parse(s), s).source)
+        }
+    }
+
+    /// Make this parser also return the byte range of its value within `original`
+    fn spanned(&self, original: &'a str) -> impl Parser<'a, Spanned<T>, E>
+    where
+        Self: Sized,
+    {
+        move |s| spanned(|s| self.parse(s), original, s)
+    }
+
+    /// Make this parser also return the exact slice of input it consumed, see [consumed](crate::consumed)
+    fn consumed(&self) -> impl Parser<'a, (T, &'a str), E>
+    where
+        Self: Sized,
+    {
+        move |s| crate::consumed(|s| self.parse(s), s)
+    }
+
+    /// Make this parser parse an additional value, returning only that value
+    fn then<V>(&self, other: impl Parser<'a, V, E>) -> impl Parser<'a, V, E>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).and(|s| other.parse(s)).map(|(_, v)| v)
+    }
+
+    /// Make this parser repeat any number of times, which may be none
+    fn many0(&self) -> impl Parser<'a, Vec<T>, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| repeating(|s| self.parse(s), .., s)
+    }
+
+    /// Make this parser repeat any number of times, but at least once
+    fn many1(&self) -> impl Parser<'a, Vec<T>, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| repeating(|s| self.parse(s), 1.., s)
+    }
+
+    /// Make this parser parse any number of values separated by a delimiter, which may be none, leaving a delimiter
+    /// unconsumed if no value follows it, see [sep_by](crate::sep_by)
+    fn sep_by<D>(&self, delim: impl Parser<'a, D, E>) -> impl Parser<'a, Vec<T>, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| crate::sep_by(|s| self.parse(s), |s| delim.parse(s), .., s)
+    }
+
+    /// Map the error type of this parser using a mapping function
+    fn map_err<E2>(&self, f: impl Fn(E) -> E2) -> impl Parser<'a, T, E2>
+    where
+        Self: Sized,
+    {
+        move |s| self.parse(s).map_err(&f)
+    }
+
+    /// Replace any error from this parser with an expected token error naming what it parses, reported where it started
+    fn labelled(&self, label: impl Into<Cow<'static, str>>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        let label = label.into();
+        move |s| {
+            let res = self.parse(s);
+            match res.typ {
+                ParserResultType::Err(_) => {
+                    ParserResult::from_err(ParserError::ExpectedToken(label.clone()).into(), s)
+                }
+                _ => res,
+            }
+        }
+    }
+
+    /// Replace any error from this parser with an expected token error describing what it parses, such as
+    /// `"a port number between 1 and 65535"`, reported where the error occurred unlike [labelled](Parser::labelled)
+    fn expect_msg(&self, message: impl Into<Cow<'static, str>>) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        let message = message.into();
+        move |s| {
+            let res = self.parse(s);
+            match res.typ {
+                ParserResultType::Err(_) => ParserResult::from_err(
+                    ParserError::ExpectedToken(message.clone()).into(),
+                    res.source,
+                ),
+                _ => res,
+            }
+        }
+    }
+
+    /// Treat this parser running out of input as an error, for when the input is known to be final, see
+    /// [complete](crate::complete)
+    fn complete(&self) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| crate::complete(|s| self.parse(s), s)
+    }
+
+    /// Move this parser to the heap, erasing its type
+    fn boxed(self) -> BoxedParser<'a, T, E>
+    where
+        Self: Sized + 'a,
+    {
+        Box::new(self)
+    }
+
+    /// Trace each attempt to run this parser under the given rule name, see [traced](crate::trace::traced)
+    fn traced(&self, name: &'static str) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+    {
+        move |s| crate::trace::traced(name, |s| self.parse(s), s)
+    }
+
+    /// Spend a unit of fuel each time this parser is run, see [fueled](crate::fuel::fueled)
+    fn fueled(&self) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| crate::fuel::fueled(|s| self.parse(s), s)
+    }
+
+    /// Check for a passed deadline or cancellation each time this parser is run, see
+    /// [checked](crate::deadline::checked)
+    fn checked(&self) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |s| crate::deadline::checked(|s| self.parse(s), s)
+    }
+
+    /// Memoize this parser under the given rule name, see [Memo::memoized](crate::incremental::Memo::memoized)
+    fn memoized(
+        &self,
+        memo: &crate::incremental::Memo,
+        name: &'static str,
+        original: &'a str,
+    ) -> impl Parser<'a, T, E>
+    where
+        Self: Sized,
+        T: Clone + 'static,
+        E: Clone + 'static,
+    {
+        move |s| memo.memoized(name, |s| self.parse(s), original, s)
+    }
+
     /// Convert the error type of this parser implicitly
     fn err_into<E2>(&self) -> impl Parser<'a, T, E2>
     where
+        Self: Sized,
         E: Into<E2>,
     {
         move |s| self.parse(s).err_into()
@@ -59,3 +353,30 @@ where
         self(input)
     }
 }
+
+impl<'a, 'b, T, E> Parser<'a, T, E> for Box<dyn Parser<'a, T, E> + 'b> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        (**self).parse(input)
+    }
+}
+
+impl<'a, T, E> Parser<'a, T, E> for &dyn Parser<'a, T, E> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        (**self).parse(input)
+    }
+}
+
+/// Shares a parser between rules without cloning it; a blanket implementation for `&P` would conflict with the one for
+/// closures, so borrowed parsers are supported through closures and `&dyn Parser` instead
+impl<'a, T, E, P: Parser<'a, T, E> + ?Sized> Parser<'a, T, E> for Rc<P> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        (**self).parse(input)
+    }
+}
+
+/// Shares a parser between rules and threads, which requires `P: Send + Sync` for the [Arc] to be sent to other threads
+impl<'a, T, E, P: Parser<'a, T, E> + ?Sized> Parser<'a, T, E> for Arc<P> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, T, E> {
+        (**self).parse(input)
+    }
+}
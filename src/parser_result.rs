@@ -1,10 +1,185 @@
+#[cfg(feature = "nightly")]
 use std::{
     convert::Infallible,
-    fmt::Debug,
     ops::{ControlFlow, FromResidual, Try},
 };
+use std::{fmt::Debug, num::NonZeroUsize};
+
+use crate::{try_parse, Parser};
+
+/// Implement [Debug] and the methods which don't depend on the kind of input for a result type with `source` and
+/// `typ` fields, so that [ParserResult] and the byte and bit results stay in step
+macro_rules! result_methods {
+    ($name:ident, $source:ty) => {
+        impl<'a, T: std::fmt::Debug, E: std::fmt::Debug> std::fmt::Debug for $name<'a, T, E> {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                match &self.typ {
+                    $crate::ParserResultType::Ok(v) => f.debug_tuple("Ok").field(v).finish(),
+                    $crate::ParserResultType::Err(v) => f.debug_tuple("Err").field(v).finish(),
+                    $crate::ParserResultType::Incomplete(needed) => {
+                        f.debug_tuple("Incomplete").field(needed).finish()
+                    }
+                }
+            }
+        }
+
+        impl<'a, T, E> $name<'a, T, E> {
+            #[doc = concat!("Create a [", stringify!($name), "] from a value and source location to continue from")]
+            pub fn from_val(val: T, source: $source) -> Self {
+                Self {
+                    source,
+                    typ: $crate::ParserResultType::Ok(val),
+                }
+            }
+
+            #[doc = concat!("Create a [", stringify!($name), "] from an error and source location where the error occurred")]
+            pub fn from_err(err: E, source: $source) -> Self {
+                Self {
+                    source,
+                    typ: $crate::ParserResultType::Err(err),
+                }
+            }
+
+            #[doc = concat!("Create an incomplete [", stringify!($name), "] from a source location where the parsing stopped, without knowing how much more input is needed")]
+            pub fn incomplete(source: $source) -> Self {
+                Self::incomplete_needing($crate::Needed::Unknown, source)
+            }
+
+            #[doc = concat!("Create an incomplete [", stringify!($name), "] which knows how much more input it needs")]
+            pub fn incomplete_needing(needed: $crate::Needed, source: $source) -> Self {
+                Self {
+                    source,
+                    typ: $crate::ParserResultType::Incomplete(needed),
+                }
+            }
+
+            pub fn unwrap(self) -> T {
+                if let $crate::ParserResultType::Ok(t) = self.typ {
+                    t
+                } else {
+                    panic!("unwrap called on erroneous or incomplete parser result")
+                }
+            }
+
+            /// Returns whether this result is incomplete
+            pub fn is_incomplete(&self) -> bool {
+                matches!(self.typ, $crate::ParserResultType::Incomplete(_))
+            }
+
+            /// Get how much more input is needed, if this result is incomplete
+            pub fn needed(&self) -> Option<$crate::Needed> {
+                match self.typ {
+                    $crate::ParserResultType::Incomplete(needed) => Some(needed),
+                    _ => None,
+                }
+            }
 
-use crate::Parser;
+            /// Returns whether this result is an error
+            pub fn is_err(&self) -> bool {
+                matches!(self.typ, $crate::ParserResultType::Err(_))
+            }
+
+            /// Returns whether this result succeeded
+            pub fn is_ok(&self) -> bool {
+                matches!(self.typ, $crate::ParserResultType::Ok(_))
+            }
+
+            #[doc = concat!("Create a new [", stringify!($name), "] borrowing the value or error wrapped by this one")]
+            pub fn as_ref(&self) -> $name<'a, &T, &E> {
+                $name {
+                    source: self.source,
+                    typ: self.typ.as_ref(),
+                }
+            }
+
+            /// Get an [Option] which is present only if the parsing succeeded
+            pub fn ok(self) -> Option<T> {
+                match self.typ {
+                    $crate::ParserResultType::Ok(v) => Some(v),
+                    _ => None,
+                }
+            }
+
+            /// Get an [Option] which is present only if the parsing failed with an error
+            pub fn err(self) -> Option<E> {
+                match self.typ {
+                    $crate::ParserResultType::Err(e) => Some(e),
+                    _ => None,
+                }
+            }
+
+            /// Force this result to be a success, resetting the position and using None if it was erroneous
+            pub fn optional(self, start: $source) -> $name<'a, Option<T>, E> {
+                let position = match self.typ {
+                    $crate::ParserResultType::Ok(_) => self.source,
+                    _ => start,
+                };
+                $name::from_val(self.ok(), position)
+            }
+
+            /// Maps this result's value to another type using a mapping function
+            pub fn map<V>(self, f: impl FnOnce(T) -> V) -> $name<'a, V, E> {
+                $name {
+                    source: self.source,
+                    typ: self.typ.map(f),
+                }
+            }
+
+            /// Replaces this result's value with a given value
+            pub fn is<V>(self, val: V) -> $name<'a, V, E> {
+                self.map(|_| val)
+            }
+
+            /// Maps this result's error to another type using a mapping function
+            pub fn map_err<E2>(self, f: impl FnOnce(E) -> E2) -> $name<'a, T, E2> {
+                $name {
+                    source: self.source,
+                    typ: self.typ.map_err(f),
+                }
+            }
+
+            /// Call a function with this result's value if it succeeded, leaving the result unchanged
+            pub fn inspect(self, f: impl FnOnce(&T)) -> Self {
+                if let $crate::ParserResultType::Ok(v) = &self.typ {
+                    f(v);
+                }
+                self
+            }
+
+            /// Call a function with this result's error and the input it occurred at if it failed, leaving the result
+            /// unchanged
+            pub fn inspect_err(self, f: impl FnOnce(&E, $source)) -> Self {
+                if let $crate::ParserResultType::Err(e) = &self.typ {
+                    f(e, self.source);
+                }
+                self
+            }
+
+            /// Implicitly convert the error type into another
+            pub fn err_into<E2>(self) -> $name<'a, T, E2>
+            where
+                E: Into<E2>,
+            {
+                self.map_err(Into::into)
+            }
+
+            /// Split a successful result into its value and remaining input, or retype the failure so it can be returned
+            /// early, which is how [try_parse!](crate::try_parse!) and its byte and bit counterparts work without the
+            /// `nightly` feature
+            pub fn into_result<V>(self) -> Result<(T, $source), $name<'a, V, E>> {
+                match self.typ {
+                    $crate::ParserResultType::Ok(v) => Ok((v, self.source)),
+                    $crate::ParserResultType::Err(e) => Err($name::from_err(e, self.source)),
+                    $crate::ParserResultType::Incomplete(needed) => {
+                        Err($name::incomplete_needing(needed, self.source))
+                    }
+                }
+            }
+        }
+    };
+}
+
+pub(crate) use result_methods;
 
 /// An output of a parser, contains the string slice to resume parsing from
 #[must_use]
@@ -13,25 +188,7 @@ pub struct ParserResult<'a, T, E> {
     pub typ: ParserResultType<T, E>,
 }
 
-impl<'a, T: Debug, E: Debug> Debug for ParserResult<'a, T, E> {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match &self.typ {
-            ParserResultType::Ok(v) => f.debug_tuple("Ok").field(v).finish(),
-            ParserResultType::Err(v) => f.debug_tuple("Err").field(v).finish(),
-            ParserResultType::Incomplete => write!(f, "Incomplete"),
-        }
-    }
-}
-
-impl<'a, T, E> ParserResult<'a, T, E> {
-    pub fn unwrap(self) -> T {
-        if let ParserResultType::Ok(t) = self.typ {
-            t
-        } else {
-            panic!("unwrap called on erroneous or incomplete parser result")
-        }
-    }
-}
+result_methods!(ParserResult, &'a str);
 
 /// The type of a parser result
 pub enum ParserResultType<T, E> {
@@ -40,9 +197,27 @@ pub enum ParserResultType<T, E> {
     /// Failed to parse, containing an error
     Err(E),
     /// Matched partially, might be recoverable but there was no error
-    Incomplete,
+    Incomplete(Needed),
+}
+
+/// How much more input an incomplete parser needs before it can make progress, so that a streaming driver can read
+/// exactly that much
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Needed {
+    /// More input is needed, but not how much
+    Unknown,
+    /// At least this many more bytes are needed
+    Size(NonZeroUsize),
+}
+
+impl Needed {
+    /// Create a [Needed] for a number of bytes, which is [Unknown](Needed::Unknown) if it is zero
+    pub fn new(bytes: usize) -> Self {
+        NonZeroUsize::new(bytes).map_or(Needed::Unknown, Needed::Size)
+    }
 }
 
+#[cfg(feature = "nightly")]
 impl<'a, T, E> Try for ParserResult<'a, T, E> {
     type Output = (T, &'a str);
 
@@ -56,13 +231,14 @@ impl<'a, T, E> Try for ParserResult<'a, T, E> {
         match self.typ {
             ParserResultType::Ok(v) => ControlFlow::Continue((v, self.source)),
             ParserResultType::Err(e) => ControlFlow::Break(ParserResult::from_err(e, self.source)),
-            ParserResultType::Incomplete => {
-                ControlFlow::Break(ParserResult::incomplete(self.source))
+            ParserResultType::Incomplete(needed) => {
+                ControlFlow::Break(ParserResult::incomplete_needing(needed, self.source))
             }
         }
     }
 }
 
+#[cfg(feature = "nightly")]
 impl<'a, T, E, F: From<E>> FromResidual<ParserResult<'a, Infallible, E>>
     for ParserResult<'a, T, F>
 {
@@ -72,7 +248,7 @@ impl<'a, T, E, F: From<E>> FromResidual<ParserResult<'a, Infallible, E>>
             typ: match residual.typ {
                 ParserResultType::Ok(_) => unreachable!(),
                 ParserResultType::Err(e) => ParserResultType::Err(e.into()),
-                ParserResultType::Incomplete => ParserResultType::Incomplete,
+                ParserResultType::Incomplete(needed) => ParserResultType::Incomplete(needed),
             },
         }
     }
@@ -84,7 +260,7 @@ impl<T, E> ParserResultType<T, E> {
         match self {
             ParserResultType::Ok(v) => ParserResultType::Ok(v),
             ParserResultType::Err(e) => ParserResultType::Err(e),
-            ParserResultType::Incomplete => ParserResultType::Incomplete,
+            ParserResultType::Incomplete(needed) => ParserResultType::Incomplete(*needed),
         }
     }
 
@@ -92,7 +268,7 @@ impl<T, E> ParserResultType<T, E> {
     pub fn map<V>(self, f: impl FnOnce(T) -> V) -> ParserResultType<V, E> {
         match self {
             ParserResultType::Ok(t) => ParserResultType::Ok(f(t)),
-            ParserResultType::Incomplete => ParserResultType::Incomplete,
+            ParserResultType::Incomplete(needed) => ParserResultType::Incomplete(needed),
             ParserResultType::Err(e) => ParserResultType::Err(e),
         }
     }
@@ -102,113 +278,12 @@ impl<T, E> ParserResultType<T, E> {
         match self {
             ParserResultType::Ok(v) => ParserResultType::Ok(v),
             ParserResultType::Err(e) => ParserResultType::Err(f(e)),
-            ParserResultType::Incomplete => ParserResultType::Incomplete,
+            ParserResultType::Incomplete(needed) => ParserResultType::Incomplete(needed),
         }
     }
 }
 
 impl<'a, T, E> ParserResult<'a, T, E> {
-    /// Create a [ParserResult] from a value and source location to continue from
-    pub fn from_val(val: T, source: &'a str) -> Self {
-        Self {
-            source,
-            typ: ParserResultType::Ok(val),
-        }
-    }
-
-    /// Create a [ParserResult] from an error and source location where the error occurred
-    pub fn from_err(err: E, source: &'a str) -> Self {
-        Self {
-            source,
-            typ: ParserResultType::Err(err),
-        }
-    }
-
-    /// Create an incomplete [ParserResult] from a source location where the parsing stopped
-    pub fn incomplete(source: &'a str) -> Self {
-        Self {
-            source,
-            typ: ParserResultType::Incomplete,
-        }
-    }
-
-    /// Returns whether this result is incomplete
-    pub fn is_incomplete(&self) -> bool {
-        matches!(self.typ, ParserResultType::Incomplete)
-    }
-
-    /// Returns whether this result is an error
-    pub fn is_err(&self) -> bool {
-        matches!(self.typ, ParserResultType::Err(_))
-    }
-
-    /// Returns whether this result succeeded
-    pub fn is_ok(&self) -> bool {
-        matches!(self.typ, ParserResultType::Ok(_))
-    }
-
-    /// Create a new [ParserResult] borrowing the value or error wrapped by this one
-    pub fn as_ref(&self) -> ParserResult<'a, &T, &E> {
-        ParserResult {
-            source: self.source,
-            typ: self.typ.as_ref(),
-        }
-    }
-
-    /// Get an [Option] which is present only if the parsing succeeded
-    pub fn ok(self) -> Option<T> {
-        match self.typ {
-            ParserResultType::Ok(v) => Some(v),
-            _ => None,
-        }
-    }
-
-    /// Get an [Option] which is present only if the parsing failed with an error
-    pub fn err(self) -> Option<E> {
-        match self.typ {
-            ParserResultType::Err(e) => Some(e),
-            _ => None,
-        }
-    }
-
-    /// Force this result to be a success, resetting the position and using None if it was erroneous
-    pub fn optional(self, start: &'a str) -> ParserResult<'a, Option<T>, E> {
-        let position = match self.typ {
-            ParserResultType::Ok(_) => self.source,
-            _ => start,
-        };
-        ParserResult::from_val(self.ok(), position)
-    }
-
-    /// Maps this result's value to another type using a mapping function
-    pub fn map<V>(self, f: impl FnOnce(T) -> V) -> ParserResult<'a, V, E> {
-        ParserResult {
-            source: self.source,
-            typ: self.typ.map(f),
-        }
-    }
-
-    /// Replaces this result's value with a given value
-    pub fn is<V>(self, val: V) -> ParserResult<'a, V, E> {
-        self.map(|_| val)
-    }
-
-    /// Maps this result's error to another type using a mapping function
-    pub fn map_err<E2>(self, f: impl FnOnce(E) -> E2) -> ParserResult<'a, T, E2> {
-        ParserResult {
-            source: self.source,
-            typ: self.typ.map_err(f),
-        }
-    }
-
-    /// Implicitly convert the error type into another
-    pub fn err_into<E2>(self) -> ParserResult<'a, T, E2>
-    where
-        E: Into<E2>,
-    {
-        self.map_err(Into::into)
-    }
-
     /// Try another parser if this result failed, from the given position, and return whichever succeeded first, if any
     pub fn or<E2: Into<E>>(
         self,
@@ -224,12 +299,12 @@ impl<'a, T, E> ParserResult<'a, T, E> {
 
     /// Parse another value after this one if this one succeeded, and return it in a tuple
     pub fn and<V, E2: Into<E>>(self, p: impl Parser<'a, V, E2>) -> ParserResult<'a, (T, V), E> {
-        let (e1, s) = self?;
+        let (e1, s) = try_parse!(self);
         let mut res2 = p.parse(s);
         if !res2.is_ok() {
             res2.source = s;
         }
-        let (e2, s) = res2.err_into()?;
+        let (e2, s) = try_parse!(res2.err_into());
         ParserResult::from_val((e1, e2), s)
     }
 
@@ -243,7 +318,7 @@ impl<'a, T, E> ParserResult<'a, T, E> {
         self,
         p: impl FnOnce(T, &'a str) -> ParserResult<'a, V, E>,
     ) -> ParserResult<'a, V, E> {
-        let (val, s) = self?;
+        let (val, s) = try_parse!(self);
         p(val, s)
     }
 
@@ -252,7 +327,7 @@ impl<'a, T, E> ParserResult<'a, T, E> {
     where
         T: Identity<I = ParserResult<'a, V, E>>,
     {
-        let (res, _) = self?;
+        let (res, _) = try_parse!(self);
         let res: ParserResult<'a, V, E> = res.ident();
         res
     }
@@ -1,4 +1,40 @@
-use crate::{error::ParserError, literal, Literal, Parser, ParserResult};
+pub mod base64;
+pub mod bencode;
+pub mod byte_size;
+pub mod c_lit;
+pub mod cbor;
+pub mod cmdline;
+pub mod color;
+pub mod css_selector;
+pub mod csv;
+pub mod datetime;
+pub mod duration;
+pub mod env;
+pub mod expr;
+pub mod glob;
+pub mod hex;
+pub mod http;
+pub mod ical;
+pub mod ini;
+pub mod ip;
+pub mod log;
+pub mod markdown;
+pub mod mime;
+pub mod msgpack;
+pub mod netstring;
+pub mod pem;
+pub mod protobuf;
+pub mod quantity;
+pub mod query;
+pub mod regex;
+pub mod rust_lit;
+pub mod semver;
+pub mod sexpr;
+pub mod shell;
+pub mod sql;
+pub mod wkt;
+
+use crate::{error::ParserError, literal, try_parse, Literal, Parser, ParserResult};
 
 impl<'a> Parser<'a, Literal, ParserError> for &'static str {
     fn parse(&self, input: &'a str) -> crate::ParserResult<'a, Literal, ParserError> {
@@ -25,7 +61,7 @@ macro_rules! impl_parser_tuple {
                 let tuple = (
                     $(
                         {
-                            let (val, s) = $p.parse(input)?;
+                            let (val, s) = try_parse!($p.parse(input));
                             input = s;
                             val
                         }
@@ -0,0 +1,116 @@
+use crate::{error::ParserError, Parser, ParserResult};
+
+/// A base64 decoder for the standard or URL-safe alphabet, which parses as much of its input as is base64 and returns
+/// the decoded bytes
+///
+/// Padding is optional, but if present it must complete the last group of four characters. Unused bits in the last
+/// character must be zero.
+#[derive(Debug, Clone, Copy)]
+pub struct Base64 {
+    url_safe: bool,
+    whitespace: bool,
+}
+
+impl Base64 {
+    /// Decode the standard alphabet, which uses `+` and `/`
+    pub fn standard() -> Self {
+        Self {
+            url_safe: false,
+            whitespace: false,
+        }
+    }
+
+    /// Decode the URL-safe alphabet, which uses `-` and `_`
+    pub fn url_safe() -> Self {
+        Self {
+            url_safe: true,
+            whitespace: false,
+        }
+    }
+
+    /// Skip whitespace between characters, such as the line breaks in a PEM body
+    pub fn ignore_whitespace(mut self) -> Self {
+        self.whitespace = true;
+        self
+    }
+
+    fn decode(&self, c: char) -> Option<u32> {
+        let value = match c {
+            'A'..='Z' => c as u32 - 'A' as u32,
+            'a'..='z' => c as u32 - 'a' as u32 + 26,
+            '0'..='9' => c as u32 - '0' as u32 + 52,
+            '+' if !self.url_safe => 62,
+            '/' if !self.url_safe => 63,
+            '-' if self.url_safe => 62,
+            '_' if self.url_safe => 63,
+            _ => return None,
+        };
+        Some(value)
+    }
+
+    /// Skip whitespace if it is being ignored
+    fn skip<'a>(&self, s: &'a str) -> &'a str {
+        if self.whitespace {
+            s.trim_start()
+        } else {
+            s
+        }
+    }
+}
+
+impl<'a> Parser<'a, Vec<u8>, ParserError> for Base64 {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, Vec<u8>, ParserError> {
+        let mut bytes = vec![];
+        let mut acc = 0u32;
+        let mut count = 0;
+        let mut s = input;
+        let mut last = input;
+        loop {
+            let next = self.skip(s);
+            let Some(value) = next.chars().next().and_then(|c| self.decode(c)) else {
+                break;
+            };
+            acc = acc << 6 | value;
+            count += 1;
+            if count % 4 == 0 {
+                bytes.extend_from_slice(&acc.to_be_bytes()[1..]);
+                acc = 0;
+            }
+            last = next;
+            s = &next[1..];
+        }
+        let (padding, unused) = match count % 4 {
+            0 => return ParserResult::from_val(bytes, s),
+            1 => {
+                return ParserResult::from_err(
+                    ParserError::ExpectedToken("base64 character".into()),
+                    self.skip(s),
+                )
+            }
+            2 => (2, 4),
+            _ => (1, 2),
+        };
+        if acc & ((1 << unused) - 1) != 0 {
+            return ParserResult::from_err(
+                ParserError::ExpectedToken("canonical base64".into()),
+                last,
+            );
+        }
+        let acc = acc >> unused;
+        bytes.extend_from_slice(&acc.to_be_bytes()[4 - (3 - padding)..]);
+        let mut rest = s;
+        for i in 0..padding {
+            match self.skip(rest).strip_prefix('=') {
+                Some(after) => rest = after,
+                None if i == 0 => return ParserResult::from_val(bytes, s),
+                None => {
+                    return ParserResult::from_err(
+                        ParserError::ExpectedLiteral("="),
+                        self.skip(rest),
+                    )
+                }
+            }
+        }
+        ParserResult::from_val(bytes, rest)
+    }
+}
@@ -0,0 +1,127 @@
+use std::collections::BTreeMap;
+
+use crate::{
+    binary::{take, try_binary, BinaryResult},
+    error::ParserError,
+    Needed,
+};
+
+/// How many lists and dictionaries can be nested inside each other before the input is rejected
+const MAX_DEPTH: usize = 128;
+
+/// A decoded bencode value, borrowing byte strings from the input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value<'a> {
+    Int(i64),
+    /// A byte string, which is often but not always UTF-8
+    Bytes(&'a [u8]),
+    List(Vec<Value<'a>>),
+    Dict(BTreeMap<&'a [u8], Value<'a>>),
+}
+
+impl<'a> Value<'a> {
+    /// Get the value as a string if it is a UTF-8 byte string
+    pub fn as_str(&self) -> Option<&'a str> {
+        match self {
+            Value::Bytes(bytes) => std::str::from_utf8(bytes).ok(),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a dictionary entry
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Value::Dict(dict) => dict.get(key.as_bytes()),
+            _ => None,
+        }
+    }
+}
+
+/// Parse digits up to a terminator, rejecting leading zeros and `-0`
+fn digits<'a, E: From<ParserError>>(
+    terminator: u8,
+    signed: bool,
+    input: &'a [u8],
+) -> BinaryResult<'a, i64, E> {
+    let negative = signed && input.first() == Some(&b'-');
+    let s = &input[usize::from(negative)..];
+    let len = s.iter().take_while(|b| b.is_ascii_digit()).count();
+    let Some(&next) = s.get(len) else {
+        return BinaryResult::incomplete_needing(Needed::new(1), input);
+    };
+    if next != terminator || len == 0 || (s[0] == b'0' && (len > 1 || negative)) {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("canonical integer".into()).into(),
+            input,
+        );
+    }
+    // The digits are ASCII, and a string of them always parses unless it overflows
+    let text = std::str::from_utf8(&input[..len + usize::from(negative)]).unwrap();
+    match text.parse() {
+        Ok(n) => BinaryResult::from_val(n, &s[len + 1..]),
+        Err(_) => BinaryResult::from_err(ParserError::IntegerOverflow.into(), input),
+    }
+}
+
+/// Parse a `<length>:<bytes>` byte string
+pub fn bytes<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, &[u8], E> {
+    let (len, s) = try_binary!(digits::<E>(b':', false, input));
+    let Ok(len) = usize::try_from(len) else {
+        return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input);
+    };
+    take(len, s)
+}
+
+/// Decode a value nested inside `depth` lists and dictionaries
+fn decode_at<E: From<ParserError>>(depth: usize, input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    if depth == MAX_DEPTH {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("less deeply nested value".into()).into(),
+            input,
+        );
+    }
+    let Some((&first, mut s)) = input.split_first() else {
+        return BinaryResult::incomplete_needing(Needed::new(1), input);
+    };
+    match first {
+        b'i' => digits(b'e', true, s).map(Value::Int),
+        b'0'..=b'9' => bytes(input).map(Value::Bytes),
+        b'l' => {
+            let mut list = vec![];
+            while s.first() != Some(&b'e') {
+                let (value, rest) = try_binary!(decode_at::<E>(depth + 1, s));
+                list.push(value);
+                s = rest;
+            }
+            BinaryResult::from_val(Value::List(list), &s[1..])
+        }
+        b'd' => {
+            let mut dict = BTreeMap::new();
+            let mut last: Option<&[u8]> = None;
+            while s.first() != Some(&b'e') {
+                let (key, rest) = try_binary!(bytes::<E>(s));
+                // Keys must be unique and sorted as raw bytes, so that every dictionary has one encoding
+                if last.is_some_and(|last| last >= key) {
+                    return BinaryResult::from_err(
+                        ParserError::ExpectedToken("key in sorted order".into()).into(),
+                        s,
+                    );
+                }
+                let (value, rest) = try_binary!(decode_at::<E>(depth + 1, rest));
+                dict.insert(key, value);
+                last = Some(key);
+                s = rest;
+            }
+            BinaryResult::from_val(Value::Dict(dict), &s[1..])
+        }
+        _ => BinaryResult::from_err(
+            ParserError::ExpectedToken("bencode value".into()).into(),
+            input,
+        ),
+    }
+}
+
+/// Decode a single bencode value, which is incomplete until all of it has arrived
+pub fn decode<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    decode_at(0, input)
+}
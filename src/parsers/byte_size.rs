@@ -0,0 +1,94 @@
+use super::{duration::number, quantity::unit};
+use crate::{error::ParserError, try_parse, Parser, ParserResult};
+
+/// The units of a size, each with the power of its prefix and whether it is always a power of 1024
+const UNITS: &[(&str, (u32, bool))] = &[
+    ("b", (0, false)),
+    ("k", (1, false)),
+    ("kb", (1, false)),
+    ("ki", (1, true)),
+    ("kib", (1, true)),
+    ("m", (2, false)),
+    ("mb", (2, false)),
+    ("mi", (2, true)),
+    ("mib", (2, true)),
+    ("g", (3, false)),
+    ("gb", (3, false)),
+    ("gi", (3, true)),
+    ("gib", (3, true)),
+    ("t", (4, false)),
+    ("tb", (4, false)),
+    ("ti", (4, true)),
+    ("tib", (4, true)),
+    ("p", (5, false)),
+    ("pb", (5, false)),
+    ("pi", (5, true)),
+    ("pib", (5, true)),
+    ("e", (6, false)),
+    ("eb", (6, false)),
+    ("ei", (6, true)),
+    ("eib", (6, true)),
+];
+
+/// A parser for sizes such as `10MiB`, `1.5GB`, `512k` or `4096`, returning a number of bytes
+///
+/// Prefixes ending in `i`, such as `KiB`, are always powers of 1024. Prefixes without it, such as `KB` or `k`, are
+/// powers of 1000 unless [binary](ByteSize::binary) is used. Prefixes and the `B` suffix are matched ignoring case,
+/// and a fraction which doesn't come to a whole number of bytes is rounded down.
+#[derive(Debug, Clone, Copy)]
+pub struct ByteSize {
+    binary: bool,
+}
+
+impl Default for ByteSize {
+    fn default() -> Self {
+        Self::decimal()
+    }
+}
+
+impl ByteSize {
+    /// Treat prefixes without an `i` as powers of 1000, as SI prefixes are
+    pub fn decimal() -> Self {
+        Self { binary: false }
+    }
+
+    /// Treat prefixes without an `i` as powers of 1024, as many tools such as nginx and the JVM do
+    pub fn binary() -> Self {
+        Self { binary: true }
+    }
+
+    /// Parse an optional unit such as `MiB` or `k` into its multiplier, which may be preceded by spaces
+    fn unit<'a>(&self, input: &'a str) -> ParserResult<'a, u128, ParserError> {
+        let s = input.trim_start_matches(' ');
+        let len = s
+            .find(|c: char| !c.is_ascii_alphabetic())
+            .unwrap_or(s.len());
+        if len == 0 {
+            return ParserResult::from_val(1, input);
+        }
+        let ((power, binary), rest) = try_parse!(
+            unit(UNITS, true, s).map_err(|_| ParserError::ExpectedToken("size unit".into()))
+        );
+        let base: u128 = if binary || self.binary { 1024 } else { 1000 };
+        ParserResult::from_val(base.pow(power), rest)
+    }
+}
+
+impl<'a> Parser<'a, u64, ParserError> for ByteSize {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, u64, ParserError> {
+        let ((value, scale), s) = try_parse!(number(input));
+        let (multiplier, s) = try_parse!(self.unit(s));
+        let bytes = value
+            .checked_mul(multiplier)
+            .and_then(|n| u64::try_from(n / scale).ok());
+        match bytes {
+            Some(bytes) => ParserResult::from_val(bytes, s),
+            None => ParserResult::from_err(ParserError::IntegerOverflow, input),
+        }
+    }
+}
+
+/// Parse a size with decimal prefixes, see [ByteSize]
+pub fn byte_size(input: &str) -> ParserResult<'_, u64, ParserError> {
+    ByteSize::decimal().parse(input)
+}
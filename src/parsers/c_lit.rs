@@ -0,0 +1,123 @@
+use crate::{advance, error::ParserError, matching_char, multispace0, try_parse, ParserResult};
+
+/// Parse a C escape sequence after its backslash into the bytes it represents, which are UTF-8 encoded for `\u` and
+/// `\U`
+fn escape(input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (c, s) = try_parse!(advance(input));
+    let byte = match c {
+        'a' => 0x07,
+        'b' => 0x08,
+        'f' => 0x0C,
+        'n' => b'\n',
+        'r' => b'\r',
+        't' => b'\t',
+        'v' => 0x0B,
+        '\\' | '\'' | '"' | '?' => c as u8,
+        '0'..='7' => {
+            let len = input
+                .bytes()
+                .take(3)
+                .take_while(|b| (b'0'..=b'7').contains(b))
+                .count();
+            return match u8::from_str_radix(&input[..len], 8) {
+                Ok(byte) => ParserResult::from_val(vec![byte], &input[len..]),
+                Err(_) => ParserResult::from_err(ParserError::IntegerOverflow, input),
+            };
+        }
+        'x' => {
+            let len = s.bytes().take_while(u8::is_ascii_hexdigit).count();
+            if len == 0 {
+                return ParserResult::from_err(ParserError::InvalidEscape(c), input);
+            }
+            return match u8::from_str_radix(&s[..len], 16) {
+                Ok(byte) => ParserResult::from_val(vec![byte], &s[len..]),
+                Err(_) => ParserResult::from_err(ParserError::IntegerOverflow, input),
+            };
+        }
+        'u' | 'U' => {
+            let len = if c == 'u' { 4 } else { 8 };
+            let digits = s
+                .get(..len)
+                .filter(|d| d.bytes().all(|b| b.is_ascii_hexdigit()));
+            let Some(digits) = digits else {
+                return ParserResult::from_err(ParserError::InvalidEscape(c), input);
+            };
+            let n = u32::from_str_radix(digits, 16).unwrap_or(u32::MAX);
+            return match char::from_u32(n) {
+                Some(c) => ParserResult::from_val(c.to_string().into_bytes(), &s[len..]),
+                None => ParserResult::from_err(ParserError::InvalidCodePoint(n), input),
+            };
+        }
+        c => return ParserResult::from_err(ParserError::InvalidEscape(c), input),
+    };
+    ParserResult::from_val(vec![byte], s)
+}
+
+/// Parse the body of a literal up to its closing quote, decoding escapes and removing line continuations
+fn body(quote: char, input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (_, mut s) = try_parse!(matching_char("quote", |c| c == quote, input));
+    let mut bytes = vec![];
+    loop {
+        let Some(c) = s.chars().next() else {
+            return ParserResult::from_err(ParserError::UnexpectedEndOfFile, input);
+        };
+        let rest = &s[c.len_utf8()..];
+        if c == quote {
+            return ParserResult::from_val(bytes, rest);
+        }
+        match c {
+            '\\' => {
+                if let Some(rest) = rest
+                    .strip_prefix("\r\n")
+                    .or_else(|| rest.strip_prefix('\n'))
+                {
+                    s = rest;
+                    continue;
+                }
+                let (escaped, rest) = try_parse!(escape(rest));
+                bytes.extend(escaped);
+                s = rest;
+            }
+            '\n' => {
+                return ParserResult::from_err(
+                    ParserError::ExpectedToken(format!("closing {quote}").into()),
+                    s,
+                )
+            }
+            c => {
+                bytes.extend(c.to_string().bytes());
+                s = rest;
+            }
+        }
+    }
+}
+
+/// Parse a single C string literal such as `"a\tb\x41\101"`, returning its decoded bytes
+pub fn string_literal(input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    body('"', input)
+}
+
+/// Parse one or more adjacent C string literals separated by whitespace, such as `"a" "b"`, returning their
+/// concatenated bytes
+pub fn string_literals(input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (mut bytes, mut s) = try_parse!(string_literal(input));
+    loop {
+        let (_, next) = try_parse!(multispace0(s));
+        let res = string_literal(next);
+        if !res.is_ok() {
+            return ParserResult::from_val(bytes, s);
+        }
+        let (more, rest) = try_parse!(res);
+        bytes.extend(more);
+        s = rest;
+    }
+}
+
+/// Parse a C character literal such as `'a'` or `'\n'`, returning its byte
+pub fn char_literal(input: &str) -> ParserResult<'_, u8, ParserError> {
+    let (bytes, s) = try_parse!(body('\'', input));
+    match bytes[..] {
+        [byte] => ParserResult::from_val(byte, s),
+        _ => ParserResult::from_err(ParserError::ExpectedToken("single character".into()), input),
+    }
+}
@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+
+use crate::{
+    binary::{be_u16, be_u32, be_u64, f16_to_f32, take, try_binary, u8, BinaryResult},
+    error::ParserError,
+};
+
+/// How many arrays, maps and tags can be nested inside each other before the input is rejected
+const MAX_DEPTH: usize = 128;
+
+/// A decoded CBOR data item, borrowing strings from the input unless they were split into indefinite-length chunks
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    /// An unsigned or negative integer, which can be anywhere from -2^64 to 2^64 - 1
+    Integer(i128),
+    Bytes(Cow<'a, [u8]>),
+    Text(Cow<'a, str>),
+    Array(Vec<Value<'a>>),
+    /// The entries of a map in the order they were encoded, since keys can be of any type
+    Map(Vec<(Value<'a>, Value<'a>)>),
+    /// A tagged value, such as tag 1 for an epoch timestamp
+    Tag(u64, Box<Value<'a>>),
+    Bool(bool),
+    Null,
+    Undefined,
+    /// A simple value without a meaning defined by RFC 8949
+    Simple(u8),
+    /// A half, single or double precision float, widened to an `f64`
+    Float(f64),
+}
+
+/// The head of a data item, holding its major type and argument, which is `None` for an indefinite length
+struct Head {
+    major: u8,
+    info: u8,
+    arg: Option<u64>,
+}
+
+/// Parse the initial byte of a data item and the argument after it
+fn head<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Head, E> {
+    let (initial, s) = try_binary!(u8::<E>(input));
+    let (major, info) = (initial >> 5, initial & 0x1F);
+    let (arg, s) = match info {
+        0..=23 => (Some(u64::from(info)), s),
+        24 => try_binary!(u8::<E>(s).map(|n| Some(u64::from(n)))),
+        25 => try_binary!(be_u16::<E>(s).map(|n| Some(u64::from(n)))),
+        26 => try_binary!(be_u32::<E>(s).map(|n| Some(u64::from(n)))),
+        27 => try_binary!(be_u64::<E>(s).map(Some)),
+        31 if matches!(major, 2..=5 | 7) => (None, s),
+        _ => {
+            return BinaryResult::from_err(
+                ParserError::ExpectedToken("CBOR additional information".into()).into(),
+                input,
+            )
+        }
+    };
+    BinaryResult::from_val(Head { major, info, arg }, s)
+}
+
+/// Convert a length argument to a `usize`
+fn length<E: From<ParserError>>(arg: u64, input: &[u8]) -> BinaryResult<'_, usize, E> {
+    match usize::try_from(arg) {
+        Ok(len) => BinaryResult::from_val(len, input),
+        Err(_) => BinaryResult::from_err(ParserError::IntegerOverflow.into(), input),
+    }
+}
+
+/// Read a byte or text string, joining the chunks of an indefinite-length one, which must all be definite-length
+/// strings of the same major type
+fn string<'a, E: From<ParserError>>(
+    major: u8,
+    arg: Option<u64>,
+    input: &'a [u8],
+) -> BinaryResult<'a, Cow<'a, [u8]>, E> {
+    if let Some(len) = arg {
+        let (len, s) = try_binary!(length::<E>(len, input));
+        return take(len, s).map(Cow::Borrowed);
+    }
+    let mut bytes = vec![];
+    let mut s = input;
+    loop {
+        if let Some((0xFF, rest)) = s.split_first() {
+            return BinaryResult::from_val(Cow::Owned(bytes), rest);
+        }
+        let (chunk, rest) = try_binary!(head::<E>(s));
+        let arg = match chunk.arg {
+            Some(arg) if chunk.major == major => arg,
+            _ => {
+                return BinaryResult::from_err(
+                    ParserError::ExpectedToken("definite-length chunk".into()).into(),
+                    s,
+                )
+            }
+        };
+        let (len, rest) = try_binary!(length::<E>(arg, rest));
+        let (chunk, rest) = try_binary!(take::<E>(len, rest));
+        bytes.extend_from_slice(chunk);
+        s = rest;
+    }
+}
+
+/// Read the items of an array or the keys and values of a map, until the count is reached or until a break if the
+/// length is indefinite
+fn items<'a, E: From<ParserError>>(
+    count: Option<u64>,
+    depth: usize,
+    mut input: &'a [u8],
+) -> BinaryResult<'a, Vec<Value<'a>>, E> {
+    // Every item takes at least a byte, so a count longer than the input can't be trusted for the capacity
+    let capacity = count.unwrap_or(0).min(input.len() as u64) as usize;
+    let mut items = Vec::with_capacity(capacity);
+    loop {
+        match count {
+            Some(count) if items.len() as u64 == count => break,
+            None if input.first() == Some(&0xFF) => {
+                input = &input[1..];
+                break;
+            }
+            _ => {}
+        }
+        let (item, s) = try_binary!(decode_at::<E>(depth, input));
+        items.push(item);
+        input = s;
+    }
+    BinaryResult::from_val(items, input)
+}
+
+/// Decode a data item nested inside `depth` arrays, maps and tags
+fn decode_at<E: From<ParserError>>(depth: usize, input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    if depth == MAX_DEPTH {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("less deeply nested value".into()).into(),
+            input,
+        );
+    }
+    let (Head { major, info, arg }, s) = try_binary!(head::<E>(input));
+    let depth = depth + 1;
+    match (major, arg) {
+        (0, Some(n)) => BinaryResult::from_val(Value::Integer(i128::from(n)), s),
+        (1, Some(n)) => BinaryResult::from_val(Value::Integer(-1 - i128::from(n)), s),
+        (2, _) => string(major, arg, s).map(Value::Bytes),
+        (3, _) => {
+            let (bytes, rest) = try_binary!(string::<E>(major, arg, s));
+            let text = match bytes {
+                Cow::Borrowed(bytes) => std::str::from_utf8(bytes).map(Cow::Borrowed).ok(),
+                Cow::Owned(bytes) => String::from_utf8(bytes).map(Cow::Owned).ok(),
+            };
+            match text {
+                Some(text) => BinaryResult::from_val(Value::Text(text), rest),
+                None => BinaryResult::from_err(
+                    ParserError::ExpectedToken("UTF-8 string".into()).into(),
+                    s,
+                ),
+            }
+        }
+        (4, _) => items(arg, depth, s).map(Value::Array),
+        (5, _) => {
+            let count = match arg.map(|n| n.checked_mul(2)) {
+                Some(None) => {
+                    return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input)
+                }
+                Some(count) => count,
+                None => None,
+            };
+            let (items, rest) = try_binary!(items::<E>(count, depth, s));
+            if items.len() % 2 != 0 {
+                return BinaryResult::from_err(
+                    ParserError::ExpectedToken("map value".into()).into(),
+                    rest,
+                );
+            }
+            let mut items = items.into_iter();
+            let mut entries = vec![];
+            while let (Some(key), Some(value)) = (items.next(), items.next()) {
+                entries.push((key, value));
+            }
+            BinaryResult::from_val(Value::Map(entries), rest)
+        }
+        (6, Some(tag)) => decode_at(depth, s).map(|value| Value::Tag(tag, Box::new(value))),
+        (7, Some(n)) => match info {
+            20 => BinaryResult::from_val(Value::Bool(false), s),
+            21 => BinaryResult::from_val(Value::Bool(true), s),
+            22 => BinaryResult::from_val(Value::Null, s),
+            23 => BinaryResult::from_val(Value::Undefined, s),
+            0..=19 => BinaryResult::from_val(Value::Simple(info), s),
+            24 if n >= 32 => BinaryResult::from_val(Value::Simple(n as u8), s),
+            25 => BinaryResult::from_val(Value::Float(f64::from(f16_to_f32(n as u16))), s),
+            26 => BinaryResult::from_val(Value::Float(f64::from(f32::from_bits(n as u32))), s),
+            27 => BinaryResult::from_val(Value::Float(f64::from_bits(n)), s),
+            _ => BinaryResult::from_err(
+                ParserError::ExpectedToken("simple value of at least 32".into()).into(),
+                input,
+            ),
+        },
+        _ => BinaryResult::from_err(
+            ParserError::ExpectedToken("data item rather than a break".into()).into(),
+            input,
+        ),
+    }
+}
+
+/// Decode a single CBOR data item, which is incomplete until all of it has arrived
+pub fn decode<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    decode_at(0, input)
+}
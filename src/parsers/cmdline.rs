@@ -0,0 +1,134 @@
+use crate::{error::ParserError, ParserResult};
+
+/// The quoting rules to split a command line with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Style {
+    /// POSIX shell quoting, with single quotes, double quotes and backslash escapes, but without expanding variables
+    /// or globs
+    Posix,
+    /// The rules of `CommandLineToArgvW` and the Microsoft C runtime, where backslashes are only special before a
+    /// double quote and `""` inside quotes is a literal quote
+    Windows,
+}
+
+/// Parse a POSIX argument, stopping at unquoted whitespace
+fn posix_arg(input: &str) -> ParserResult<'_, String, ParserError> {
+    let mut arg = String::new();
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            c if c.is_whitespace() => return ParserResult::from_val(arg, &input[i..]),
+            '\'' => loop {
+                match chars.next() {
+                    Some((_, '\'')) => break,
+                    Some((_, c)) => arg.push(c),
+                    None => {
+                        return ParserResult::from_err(
+                            ParserError::ExpectedLiteral("'"),
+                            &input[i..],
+                        )
+                    }
+                }
+            },
+            '"' => loop {
+                match chars.next() {
+                    Some((_, '"')) => break,
+                    Some((_, '\\')) => match chars.next() {
+                        Some((_, '\n')) => {}
+                        Some((_, c @ ('$' | '`' | '"' | '\\'))) => arg.push(c),
+                        Some((_, c)) => {
+                            arg.push('\\');
+                            arg.push(c);
+                        }
+                        None => {
+                            return ParserResult::from_err(
+                                ParserError::ExpectedLiteral("\""),
+                                &input[i..],
+                            )
+                        }
+                    },
+                    Some((_, c)) => arg.push(c),
+                    None => {
+                        return ParserResult::from_err(
+                            ParserError::ExpectedLiteral("\""),
+                            &input[i..],
+                        )
+                    }
+                }
+            },
+            '\\' => match chars.next() {
+                Some((_, '\n')) => {}
+                Some((_, c)) => arg.push(c),
+                None => {
+                    return ParserResult::from_err(ParserError::UnexpectedEndOfFile, &input[i..])
+                }
+            },
+            c => arg.push(c),
+        }
+    }
+    ParserResult::from_val(arg, &input[input.len()..])
+}
+
+/// Parse a Windows argument, stopping at a space or tab outside of quotes
+fn windows_arg(input: &str) -> ParserResult<'_, String, ParserError> {
+    let mut arg = String::new();
+    let mut quoted = false;
+    let mut s = input;
+    while let Some(c) = s.chars().next() {
+        match c {
+            ' ' | '\t' if !quoted => break,
+            '\\' => {
+                let count = s.len() - s.trim_start_matches('\\').len();
+                s = &s[count..];
+                if s.starts_with('"') {
+                    // Pairs of backslashes before a quote are halved, and an odd one escapes the quote
+                    arg.extend(std::iter::repeat_n('\\', count / 2));
+                    if count % 2 == 1 {
+                        arg.push('"');
+                        s = &s[1..];
+                    }
+                } else {
+                    arg.extend(std::iter::repeat_n('\\', count));
+                }
+                continue;
+            }
+            '"' if quoted && s[1..].starts_with('"') => {
+                arg.push('"');
+                s = &s[2..];
+                continue;
+            }
+            '"' => quoted = !quoted,
+            c => arg.push(c),
+        }
+        s = &s[c.len_utf8()..];
+    }
+    ParserResult::from_val(arg, s)
+}
+
+/// Parse a single argument with the given quoting rules
+pub fn arg(style: Style, input: &str) -> ParserResult<'_, String, ParserError> {
+    match style {
+        Style::Posix => posix_arg(input),
+        Style::Windows => windows_arg(input),
+    }
+}
+
+/// Split a whole command line into arguments such as for `argv`, where quoted empty strings are kept as empty
+/// arguments
+pub fn split_args(style: Style, input: &str) -> ParserResult<'_, Vec<String>, ParserError> {
+    let skip = |s: &str| match style {
+        Style::Posix => s.trim_start().len(),
+        Style::Windows => s.trim_start_matches([' ', '\t']).len(),
+    };
+    let mut args = vec![];
+    let mut s = &input[input.len() - skip(input)..];
+    while !s.is_empty() {
+        let (arg, rest) = match arg(style, s).into_result::<Vec<String>>() {
+            Ok(parsed) => parsed,
+            Err(err) => return err,
+        };
+        args.push(arg);
+        s = &rest[rest.len() - skip(rest)..];
+    }
+    ParserResult::from_val(args, s)
+}
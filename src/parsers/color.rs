@@ -0,0 +1,179 @@
+use crate::{error::ParserError, literal, num::float, try_parse, ParserResult};
+
+/// A color with 8-bit red, green, blue and alpha channels, where an alpha of 255 is opaque
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Rgba {
+    pub r: u8,
+    pub g: u8,
+    pub b: u8,
+    pub a: u8,
+}
+
+impl Rgba {
+    /// Create an opaque color
+    pub fn rgb(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+}
+
+/// Scale a fraction from 0 to 1 to a channel, clamping it to that range
+fn channel(fraction: f64) -> u8 {
+    (fraction.clamp(0.0, 1.0) * 255.0).round() as u8
+}
+
+/// Parse a hex color such as `#f80`, `#f80c`, `#ff8800` or `#ff8800cc`
+pub fn hex_color(input: &str) -> ParserResult<'_, Rgba, ParserError> {
+    let (_, s) = try_parse!(literal("#", input));
+    let len = s.find(|c: char| !c.is_ascii_hexdigit()).unwrap_or(s.len());
+    let digits: Vec<u8> = s[..len]
+        .chars()
+        .map(|c| c.to_digit(16).unwrap() as u8)
+        .collect();
+    let channels: Vec<u8> = match len {
+        3 | 4 => digits.iter().map(|d| d * 17).collect(),
+        6 | 8 => digits
+            .chunks(2)
+            .map(|pair| pair[0] * 16 + pair[1])
+            .collect(),
+        _ => {
+            return ParserResult::from_err(
+                ParserError::ExpectedToken("3, 4, 6 or 8 hex digits".into()),
+                s,
+            )
+        }
+    };
+    let a = channels.get(3).copied().unwrap_or(255);
+    ParserResult::from_val(
+        Rgba {
+            r: channels[0],
+            g: channels[1],
+            b: channels[2],
+            a,
+        },
+        &s[len..],
+    )
+}
+
+/// Split the arguments of a functional notation such as `rgb(255, 0, 0)` or `rgb(255 0 0 / 50%)`, returning the
+/// components and the alpha if there is one
+fn arguments(input: &str) -> ParserResult<'_, (Vec<&str>, Option<&str>), ParserError> {
+    let (_, s) = try_parse!(literal("(", input));
+    let Some(end) = s.find(')') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral(")"), &s[s.len()..]);
+    };
+    let body = s[..end].trim();
+    let mut args: Vec<&str> = if body.contains(',') {
+        body.split(',').map(str::trim).collect()
+    } else {
+        body.split_whitespace().collect()
+    };
+    let alpha = match args.iter().position(|arg| arg.starts_with('/')) {
+        Some(i) => {
+            let mut rest = args.split_off(i);
+            let slash = rest[0].strip_prefix('/').unwrap();
+            if !slash.is_empty() {
+                rest[0] = slash;
+            } else {
+                rest.remove(0);
+            }
+            if rest.len() != 1 {
+                return ParserResult::from_err(ParserError::ExpectedToken("alpha".into()), s);
+            }
+            rest.pop()
+        }
+        None if args.len() == 4 => args.pop(),
+        None => None,
+    };
+    if args.len() != 3 {
+        return ParserResult::from_err(ParserError::ExpectedToken("3 components".into()), s);
+    }
+    ParserResult::from_val((args, alpha), &s[end + 1..])
+}
+
+/// Parse a number which must make up the entire argument, returning whether it is a percentage
+fn component(arg: &str) -> Result<(f64, bool), ParserError> {
+    let (n, rest) = float::<f64>(arg)
+        .into_result::<()>()
+        .map_err(|res| res.err().unwrap_or(ParserError::UnexpectedEndOfFile))?;
+    match rest {
+        "" => Ok((n, false)),
+        "%" => Ok((n, true)),
+        _ => Err(ParserError::ExpectedToken("number or percentage".into())),
+    }
+}
+
+/// Parse an alpha from 0 to 1 or a percentage
+fn alpha(arg: Option<&str>) -> Result<u8, ParserError> {
+    match arg {
+        Some(arg) => {
+            let (n, percent) = component(arg)?;
+            Ok(channel(if percent { n / 100.0 } else { n }))
+        }
+        None => Ok(255),
+    }
+}
+
+/// Convert a hue in degrees, and a saturation and lightness from 0 to 1, to a color
+fn hsl_to_rgb(h: f64, s: f64, l: f64, a: u8) -> Rgba {
+    let (s, l) = (s.clamp(0.0, 1.0), l.clamp(0.0, 1.0));
+    let h = h.rem_euclid(360.0) / 30.0;
+    let k = |n: f64| {
+        let k = (n + h) % 12.0;
+        l - s * l.min(1.0 - l) * (k - 3.0).min(9.0 - k).clamp(-1.0, 1.0)
+    };
+    Rgba {
+        r: channel(k(0.0)),
+        g: channel(k(8.0)),
+        b: channel(k(4.0)),
+        a,
+    }
+}
+
+/// Parse the arguments of `rgb()` or `hsl()` notation, after its name
+fn function(hsl: bool, input: &str) -> ParserResult<'_, Rgba, ParserError> {
+    let ((args, alpha_arg), s) = try_parse!(arguments(input));
+    let color = (|| {
+        let a = alpha(alpha_arg)?;
+        if hsl {
+            let hue = args[0].strip_suffix("deg").unwrap_or(args[0]);
+            let (h, _) = component(hue)?;
+            let (sat, l) = (component(args[1])?, component(args[2])?);
+            if !sat.1 || !l.1 {
+                return Err(ParserError::ExpectedLiteral("%"));
+            }
+            return Ok(hsl_to_rgb(h, sat.0 / 100.0, l.0 / 100.0, a));
+        }
+        let mut rgb = [0; 3];
+        for (value, arg) in rgb.iter_mut().zip(&args) {
+            let (n, percent) = component(arg)?;
+            *value = channel(if percent { n / 100.0 } else { n / 255.0 });
+        }
+        Ok(Rgba {
+            r: rgb[0],
+            g: rgb[1],
+            b: rgb[2],
+            a,
+        })
+    })();
+    match color {
+        Ok(color) => ParserResult::from_val(color, s),
+        Err(err) => ParserResult::from_err(err, input),
+    }
+}
+
+/// Parse a color in any of the forms `#rgb`, `#rgba`, `#rrggbb`, `#rrggbbaa`, `rgb()`, `rgba()`, `hsl()` or `hsla()`,
+/// where the functions accept either comma-separated arguments or space-separated ones with a `/` before the alpha
+pub fn color(input: &str) -> ParserResult<'_, Rgba, ParserError> {
+    if input.starts_with('#') {
+        return hex_color(input);
+    }
+    let len = input
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    let hsl = match input[..len].to_ascii_lowercase().as_str() {
+        "rgb" | "rgba" => false,
+        "hsl" | "hsla" => true,
+        _ => return ParserResult::from_err(ParserError::ExpectedToken("color".into()), input),
+    };
+    function(hsl, &input[len..])
+}
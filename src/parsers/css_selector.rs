@@ -0,0 +1,385 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, literal, try_parse, ParserResult};
+
+/// How an attribute selector compares the attribute's value
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeOp {
+    /// `[attr]`
+    Exists,
+    /// `[attr=value]`
+    Equals,
+    /// `[attr~=value]`, a whitespace-separated list containing the value
+    Includes,
+    /// `[attr|=value]`, the value or the value followed by `-`
+    DashMatch,
+    /// `[attr^=value]`
+    Prefix,
+    /// `[attr$=value]`
+    Suffix,
+    /// `[attr*=value]`
+    Substring,
+}
+
+/// An attribute selector such as `[href^="https:" i]`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Attribute<'a> {
+    pub name: &'a str,
+    pub op: AttributeOp,
+    /// The value to compare with, which is empty for [Exists](AttributeOp::Exists)
+    pub value: Cow<'a, str>,
+    /// Whether the value is compared ignoring ASCII case, from an `i` flag
+    pub case_insensitive: bool,
+}
+
+/// The argument of a functional pseudo-class
+#[derive(Debug, Clone, PartialEq)]
+pub enum PseudoArg<'a> {
+    /// The selector list of `:not()`, `:is()` and `:where()`
+    Selectors(Vec<Selector<'a>>),
+    /// The relative selectors of `:has()`, each with the combinator relating it to the subject
+    Relative(Vec<(Combinator, Selector<'a>)>),
+    /// The `An+B` pattern of `:nth-child()` and its relatives, matching every `a`th element starting from the `b`th
+    Nth { a: i32, b: i32 },
+    /// The unparsed argument of any other pseudo-class, such as the `en` of `:lang(en)`
+    Raw(&'a str),
+}
+
+/// A simple selector, several of which make up a [Compound] selector
+#[derive(Debug, Clone, PartialEq)]
+pub enum Simple<'a> {
+    /// `*`
+    Universal,
+    /// An element name such as `div`
+    Type(&'a str),
+    /// `.name`
+    Class(&'a str),
+    /// `#name`
+    Id(&'a str),
+    Attribute(Attribute<'a>),
+    /// `:name` or `:name(argument)`
+    PseudoClass {
+        name: &'a str,
+        arg: Option<PseudoArg<'a>>,
+    },
+    /// `::name`
+    PseudoElement(&'a str),
+}
+
+/// A sequence of simple selectors which all apply to the same element, such as `a.external[href]:hover`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Compound<'a> {
+    pub parts: Vec<Simple<'a>>,
+}
+
+/// How two compound selectors are related
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Combinator {
+    /// Whitespace, for any descendant
+    Descendant,
+    /// `>`, for a direct child
+    Child,
+    /// `+`, for the immediately following sibling
+    NextSibling,
+    /// `~`, for any following sibling
+    SubsequentSibling,
+}
+
+/// A complex selector such as `ul > li a`, made of compound selectors joined by combinators
+#[derive(Debug, Clone, PartialEq)]
+pub struct Selector<'a> {
+    pub first: Compound<'a>,
+    pub rest: Vec<(Combinator, Compound<'a>)>,
+}
+
+fn is_name_char(c: char) -> bool {
+    c.is_ascii_alphanumeric() || c == '-' || c == '_' || !c.is_ascii()
+}
+
+/// Parse a CSS identifier, which may not start with a digit or a `-` followed by a digit
+///
+/// Escape sequences are not supported.
+fn ident(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let len = input.find(|c| !is_name_char(c)).unwrap_or(input.len());
+    let name = &input[..len];
+    let body = name.strip_prefix('-').unwrap_or(name);
+    if body.is_empty() || body.starts_with(|c: char| c.is_ascii_digit()) {
+        return ParserResult::from_err(ParserError::ExpectedToken("identifier".into()), input);
+    }
+    ParserResult::from_val(name, &input[len..])
+}
+
+fn skip_ws(input: &str) -> &str {
+    input.trim_start_matches([' ', '\t', '\n', '\r', '\x0C'])
+}
+
+/// Parse a quoted string, handling backslash escapes of single characters
+fn string(input: &str) -> ParserResult<'_, Cow<'_, str>, ParserError> {
+    let Some(quote) = input.chars().next().filter(|c| *c == '"' || *c == '\'') else {
+        return ParserResult::from_err(ParserError::ExpectedToken("string".into()), input);
+    };
+    let s = &input[1..];
+    let mut value = Cow::Borrowed("");
+    let mut start = 0;
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        if c == quote {
+            match &mut value {
+                Cow::Borrowed(_) => value = Cow::Borrowed(&s[..i]),
+                Cow::Owned(value) => value.push_str(&s[start..i]),
+            }
+            return ParserResult::from_val(value, &s[i + 1..]);
+        }
+        if c == '\\' {
+            value.to_mut().push_str(&s[start..i]);
+            if let Some((_, c)) = chars.next() {
+                value.to_mut().push(c);
+            }
+            start = chars.offset();
+        }
+    }
+    ParserResult::from_err(ParserError::UnexpectedEndOfFile, &s[s.len()..])
+}
+
+fn attribute(input: &str) -> ParserResult<'_, Attribute<'_>, ParserError> {
+    let (_, s) = try_parse!(literal("[", input));
+    let (name, s) = try_parse!(ident(skip_ws(s)));
+    let s = skip_ws(s);
+    if let Some(s) = s.strip_prefix(']') {
+        let attr = Attribute {
+            name,
+            op: AttributeOp::Exists,
+            value: Cow::Borrowed(""),
+            case_insensitive: false,
+        };
+        return ParserResult::from_val(attr, s);
+    }
+    let ops = [
+        ("=", AttributeOp::Equals),
+        ("~=", AttributeOp::Includes),
+        ("|=", AttributeOp::DashMatch),
+        ("^=", AttributeOp::Prefix),
+        ("$=", AttributeOp::Suffix),
+        ("*=", AttributeOp::Substring),
+    ];
+    let Some((op, s)) = ops
+        .iter()
+        .find_map(|(token, op)| s.strip_prefix(token).map(|s| (*op, s)))
+    else {
+        return ParserResult::from_err(ParserError::ExpectedToken("attribute operator".into()), s);
+    };
+    let s = skip_ws(s);
+    let (value, s) = if s.starts_with(['"', '\'']) {
+        try_parse!(string(s))
+    } else {
+        try_parse!(ident(s).map(Cow::Borrowed))
+    };
+    let s = skip_ws(s);
+    let (case_insensitive, s) = match s.strip_prefix(['i', 'I']) {
+        Some(rest) => (true, skip_ws(rest)),
+        None => (false, s.strip_prefix(['s', 'S']).map_or(s, skip_ws)),
+    };
+    let (_, s) = try_parse!(literal("]", s));
+    let attr = Attribute {
+        name,
+        op,
+        value,
+        case_insensitive,
+    };
+    ParserResult::from_val(attr, s)
+}
+
+/// Parse an optionally signed integer, returning `default` if there are no digits
+fn signed(default: i32, input: &str) -> Option<(i32, &str)> {
+    let (negative, s) = match input.strip_prefix(['+', '-']) {
+        Some(s) => (input.starts_with('-'), s),
+        None => (false, input),
+    };
+    let len = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let n = if len == 0 {
+        default
+    } else {
+        s[..len].parse().ok()?
+    };
+    Some((if negative { -n } else { n }, &s[len..]))
+}
+
+/// Parse an `An+B` pattern such as `2n+1`, `-n + 3`, `odd` or `5`
+fn nth(input: &str) -> Option<(i32, i32)> {
+    let s = input.trim();
+    match s.to_ascii_lowercase().as_str() {
+        "odd" => return Some((2, 1)),
+        "even" => return Some((2, 0)),
+        _ => {}
+    }
+    let (n, rest) = signed(1, s)?;
+    let Some(rest) = rest.strip_prefix(['n', 'N']) else {
+        let digits = s.trim_start_matches(['+', '-']);
+        return (rest.is_empty() && !digits.is_empty()).then_some((0, n));
+    };
+    let rest = rest.trim_start();
+    if rest.is_empty() {
+        return Some((n, 0));
+    }
+    let negative = rest.starts_with('-');
+    let digits = rest.strip_prefix(['+', '-'])?.trim_start();
+    let b: i32 = digits.parse().ok()?;
+    Some((n, if negative { -b } else { b }))
+}
+
+/// Find the end of a parenthesized argument, accounting for nested parentheses and strings
+fn argument_end(input: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut quote = None;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        match (quote, c) {
+            _ if escaped => escaped = false,
+            (_, '\\') => escaped = true,
+            (Some(q), c) if c == q => quote = None,
+            (Some(_), _) => {}
+            (None, '"' | '\'') => quote = Some(c),
+            (None, '(') => depth += 1,
+            (None, ')') if depth == 0 => return Some(i),
+            (None, ')') => depth -= 1,
+            _ => {}
+        }
+    }
+    None
+}
+
+fn pseudo(input: &str) -> ParserResult<'_, Simple<'_>, ParserError> {
+    let (_, s) = try_parse!(literal(":", input));
+    if let Some(s) = s.strip_prefix(':') {
+        return ident(s).map(Simple::PseudoElement);
+    }
+    let (name, s) = try_parse!(ident(s));
+    let Some(s) = s.strip_prefix('(') else {
+        return ParserResult::from_val(Simple::PseudoClass { name, arg: None }, s);
+    };
+    let Some(end) = argument_end(s) else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral(")"), &s[s.len()..]);
+    };
+    let (body, rest) = (&s[..end], &s[end + 1..]);
+    let lower = name.to_ascii_lowercase();
+    let arg = match lower.as_str() {
+        "not" | "is" | "where" | "matches" => {
+            let (list, after) = try_parse!(selector_list(body));
+            if !skip_ws(after).is_empty() {
+                return ParserResult::from_err(ParserError::ExpectedLiteral(")"), after);
+            }
+            PseudoArg::Selectors(list)
+        }
+        "has" => {
+            let (list, after) = try_parse!(relative_list(body));
+            if !skip_ws(after).is_empty() {
+                return ParserResult::from_err(ParserError::ExpectedLiteral(")"), after);
+            }
+            PseudoArg::Relative(list)
+        }
+        "nth-child" | "nth-last-child" | "nth-of-type" | "nth-last-of-type" => match nth(body) {
+            Some((a, b)) => PseudoArg::Nth { a, b },
+            None => return ParserResult::from_err(ParserError::ExpectedToken("An+B".into()), body),
+        },
+        _ => PseudoArg::Raw(body.trim()),
+    };
+    ParserResult::from_val(
+        Simple::PseudoClass {
+            name,
+            arg: Some(arg),
+        },
+        rest,
+    )
+}
+
+fn simple(input: &str) -> ParserResult<'_, Simple<'_>, ParserError> {
+    match input.chars().next() {
+        Some('*') => ParserResult::from_val(Simple::Universal, &input[1..]),
+        Some('.') => ident(&input[1..]).map(Simple::Class),
+        Some('#') => {
+            let s = &input[1..];
+            let len = s.find(|c| !is_name_char(c)).unwrap_or(s.len());
+            if len == 0 {
+                return ParserResult::from_err(ParserError::ExpectedToken("id".into()), s);
+            }
+            ParserResult::from_val(Simple::Id(&s[..len]), &s[len..])
+        }
+        Some('[') => attribute(input).map(Simple::Attribute),
+        Some(':') => pseudo(input),
+        _ => ident(input).map(Simple::Type),
+    }
+}
+
+/// Parse a compound selector such as `a.external[href]:hover`, where a type or `*` may only come first
+pub fn compound(input: &str) -> ParserResult<'_, Compound<'_>, ParserError> {
+    let (first, mut s) = try_parse!(simple(input));
+    let mut parts = vec![first];
+    while s.starts_with(['.', '#', '[', ':']) {
+        let (part, rest) = try_parse!(simple(s));
+        parts.push(part);
+        s = rest;
+    }
+    ParserResult::from_val(Compound { parts }, s)
+}
+
+/// Parse an explicit combinator along with the whitespace around it, or whitespace alone as a descendant combinator
+/// if it is followed by another compound selector
+fn combinator(input: &str) -> Option<(Combinator, &str)> {
+    let s = skip_ws(input);
+    let explicit = match s.chars().next() {
+        Some('>') => Some(Combinator::Child),
+        Some('+') => Some(Combinator::NextSibling),
+        Some('~') => Some(Combinator::SubsequentSibling),
+        _ => None,
+    };
+    match explicit {
+        Some(combinator) => Some((combinator, skip_ws(&s[1..]))),
+        None if s.len() < input.len() && !s.is_empty() && !s.starts_with([',', ')']) => {
+            Some((Combinator::Descendant, s))
+        }
+        None => None,
+    }
+}
+
+/// Parse a complex selector such as `ul > li a`
+pub fn selector(input: &str) -> ParserResult<'_, Selector<'_>, ParserError> {
+    let (first, mut s) = try_parse!(compound(input));
+    let mut rest = vec![];
+    while let Some((combinator, after)) = combinator(s) {
+        let (next, after) = try_parse!(compound(after));
+        rest.push((combinator, next));
+        s = after;
+    }
+    ParserResult::from_val(Selector { first, rest }, s)
+}
+
+/// Parse a comma-separated list of selectors, such as `h1, h2.title, nav > a`
+pub fn selector_list(input: &str) -> ParserResult<'_, Vec<Selector<'_>>, ParserError> {
+    let (first, mut s) = try_parse!(selector(skip_ws(input)));
+    let mut list = vec![first];
+    while let Some(rest) = skip_ws(s).strip_prefix(',') {
+        let (next, rest) = try_parse!(selector(skip_ws(rest)));
+        list.push(next);
+        s = rest;
+    }
+    ParserResult::from_val(list, s)
+}
+
+/// Parse the comma-separated relative selectors of `:has()`, such as `> img, + p`
+fn relative_list(input: &str) -> ParserResult<'_, Vec<(Combinator, Selector<'_>)>, ParserError> {
+    let mut list = vec![];
+    let mut s = input;
+    loop {
+        let start = skip_ws(s);
+        let (combinator, start) = match combinator(start) {
+            Some((combinator, rest)) if combinator != Combinator::Descendant => (combinator, rest),
+            _ => (Combinator::Descendant, start),
+        };
+        let (sel, rest) = try_parse!(selector(start));
+        list.push((combinator, sel));
+        match skip_ws(rest).strip_prefix(',') {
+            Some(rest) => s = rest,
+            None => return ParserResult::from_val(list, rest),
+        }
+    }
+}
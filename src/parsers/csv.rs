@@ -0,0 +1,186 @@
+use std::{error::Error, fmt::Display};
+
+use crate::{
+    delimited_list, error::ParserError, iter, line_ending, literal, matching_char, peek,
+    take_while, try_parse, Parse, Parser, ParserResult, ParsingIterator, SourceIterator,
+};
+
+/// Parse a single field, which may be quoted, stopping at the delimiter or end of the record
+pub fn field(delimiter: char, input: &str) -> ParserResult<'_, String, ParserError> {
+    if peek(input).ok() == Some('"') {
+        quoted_field(input)
+    } else {
+        take_while(
+            "field",
+            |c| c != delimiter && !matches!(c, '"' | '\r' | '\n'),
+            input,
+        )
+        .optional(input)
+        .map(|s| s.unwrap_or_default().to_string())
+    }
+}
+
+fn quoted_field(input: &str) -> ParserResult<'_, String, ParserError> {
+    let (_, mut s) = try_parse!(literal("\"", input));
+    let field = iter(
+        |s| {
+            literal("\"\"", s)
+                .is('"')
+                .or(|s| matching_char("character", |c| c != '"', s), s)
+        },
+        &mut s,
+    )
+    .ok()
+    .collect();
+    let (_, s) = try_parse!(literal("\"", s));
+    ParserResult::from_val(field, s)
+}
+
+fn record_end(input: &str) -> ParserResult<'_, (), ParserError> {
+    if input.is_empty() {
+        return ParserResult::from_val((), input);
+    }
+    line_ending(input).is(())
+}
+
+/// Parse a single record of delimited fields, along with its line ending
+pub fn record(delimiter: char, input: &str) -> ParserResult<'_, Vec<String>, ParserError> {
+    if input.is_empty() {
+        return ParserResult::from_err(ParserError::UnexpectedEndOfFile, input);
+    }
+    let ((fields, ()), s) = try_parse!(delimited_list::<_, _, _, Vec<String>, ()>(
+        |s| field(delimiter, s),
+        |s| matching_char("delimiter", |c| c == delimiter, s),
+        input,
+    ));
+    let (_, s) = try_parse!(record_end(s));
+    ParserResult::from_val(fields, s)
+}
+
+/// Create a [ParsingIterator] which lazily parses records until the input is exhausted or a record fails to parse
+pub fn records<'a, 'b>(
+    delimiter: char,
+    source: &'b mut &'a str,
+) -> impl SourceIterator<'a, Vec<String>, ParserError> + 'b {
+    iter(move |s| record(delimiter, s), source)
+}
+
+/// Parse an entire CSV document into its records, failing if any record is malformed
+pub fn parse_csv(
+    delimiter: char,
+    mut input: &str,
+) -> ParserResult<'_, Vec<Vec<String>>, ParserError> {
+    let mut records = vec![];
+    while !input.is_empty() {
+        let (record, s) = try_parse!(record(delimiter, input));
+        records.push(record);
+        input = s;
+    }
+    ParserResult::from_val(records, input)
+}
+
+/// An error from mapping a record into a type with [FromRecord]
+#[derive(Debug, Clone, PartialEq)]
+pub enum RecordError {
+    ParserError(ParserError),
+    /// A column which the header or the record doesn't have
+    MissingColumn(String),
+    /// A field which failed to parse, along with the name of its column
+    InvalidField(String, ParserError),
+}
+
+impl From<ParserError> for RecordError {
+    fn from(value: ParserError) -> Self {
+        RecordError::ParserError(value)
+    }
+}
+
+impl Display for RecordError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RecordError::ParserError(err) => err.fmt(f),
+            RecordError::MissingColumn(column) => write!(f, "Missing column '{column}'"),
+            RecordError::InvalidField(column, err) => write!(f, "Invalid field '{column}': {err}"),
+        }
+    }
+}
+
+impl Error for RecordError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RecordError::ParserError(err) | RecordError::InvalidField(_, err) => Some(err),
+            RecordError::MissingColumn(_) => None,
+        }
+    }
+}
+
+/// A record whose fields can be looked up by the column names in the header
+#[derive(Debug, Clone, Copy)]
+pub struct Row<'r> {
+    pub headers: &'r [String],
+    pub fields: &'r [String],
+}
+
+impl<'r> Row<'r> {
+    /// Get the field in the named column
+    pub fn get(&self, column: &str) -> Result<&'r str, RecordError> {
+        self.headers
+            .iter()
+            .position(|header| header == column)
+            .and_then(|i| self.fields.get(i))
+            .map(String::as_str)
+            .ok_or_else(|| RecordError::MissingColumn(column.to_string()))
+    }
+
+    /// Parse the entire field in the named column with the type's [Parse] implementation
+    pub fn parse<T: Parse<'r, Error = ParserError>>(&self, column: &str) -> Result<T, RecordError> {
+        self.parse_with(column, T::parse)
+    }
+
+    /// Parse the entire field in the named column with a parser, for fields which need a different format than the
+    /// type's [Parse] implementation or have no such implementation
+    pub fn parse_with<T>(
+        &self,
+        column: &str,
+        parser: impl Parser<'r, T, ParserError>,
+    ) -> Result<T, RecordError> {
+        let invalid = |err| RecordError::InvalidField(column.to_string(), err);
+        let (val, rest) = parser
+            .parse(self.get(column)?)
+            .into_result::<()>()
+            .map_err(|res| invalid(res.err().unwrap_or(ParserError::UnexpectedEndOfFile)))?;
+        if !rest.is_empty() {
+            return Err(invalid(ParserError::ExpectedToken("end of field".into())));
+        }
+        Ok(val)
+    }
+}
+
+/// A type which can be built from a record by looking up its fields by column name, so that columns can be in any
+/// order
+pub trait FromRecord: Sized {
+    fn from_record(row: &Row<'_>) -> Result<Self, RecordError>;
+}
+
+/// Parse an entire CSV document whose first record is a header, mapping each following record into a type with
+/// [FromRecord], and reporting a record which can't be mapped where it starts
+pub fn parse_records<T: FromRecord>(
+    delimiter: char,
+    input: &str,
+) -> ParserResult<'_, Vec<T>, RecordError> {
+    let (headers, mut input) = try_parse!(record(delimiter, input));
+    let mut values = vec![];
+    while !input.is_empty() {
+        let (fields, s) = try_parse!(record(delimiter, input));
+        let row = Row {
+            headers: &headers,
+            fields: &fields,
+        };
+        match T::from_record(&row) {
+            Ok(value) => values.push(value),
+            Err(err) => return ParserResult::from_err(err, input),
+        }
+        input = s;
+    }
+    ParserResult::from_val(values, input)
+}
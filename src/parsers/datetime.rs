@@ -0,0 +1,342 @@
+use crate::*;
+
+type Result<'a, T> = ParserResult<'a, T, DateTimeError>;
+
+#[derive(Debug)]
+pub enum DateTimeError {
+    ParserError(ParserError),
+    /// A component was syntactically valid but out of range, such as the 13th month
+    OutOfRange(&'static str),
+    /// A conversion required an offset, but the date-time was local
+    MissingOffset,
+}
+
+impl From<ParserError> for DateTimeError {
+    fn from(value: ParserError) -> Self {
+        DateTimeError::ParserError(value)
+    }
+}
+
+/// A calendar date
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Date {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+}
+
+/// A time of day, where `second` may be 60 to represent a leap second
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Time {
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+    pub nanosecond: u32,
+}
+
+/// An offset from UTC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Offset {
+    /// UTC, written as `Z`
+    Utc,
+    /// An offset in minutes east of UTC, written as `+HH:MM` or `-HH:MM`
+    Minutes(i16),
+}
+
+impl Offset {
+    /// Get the offset in seconds east of UTC
+    pub fn seconds(&self) -> i32 {
+        match self {
+            Offset::Utc => 0,
+            Offset::Minutes(m) => *m as i32 * 60,
+        }
+    }
+}
+
+/// A date and time, with an offset unless it is a local date-time
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DateTime {
+    pub date: Date,
+    pub time: Time,
+    pub offset: Option<Offset>,
+}
+
+fn is_leap_year(year: u16) -> bool {
+    year.is_multiple_of(4) && (!year.is_multiple_of(100) || year.is_multiple_of(400))
+}
+
+pub(super) fn days_in_month(year: u16, month: u8) -> u8 {
+    match month {
+        2 if is_leap_year(year) => 29,
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// Parse exactly `n` decimal digits as a number
+fn fixed_digits(n: usize, s: &str) -> Result<'_, u32> {
+    repeating(
+        cur!(matching_char <= "digit", |c| c.is_ascii_digit()),
+        n..=n,
+        s,
+    )
+    .map(|digits| {
+        digits
+            .into_iter()
+            .fold(0, |n, d| n * 10 + (d as u32 - '0' as u32))
+    })
+    .err_into()
+}
+
+/// Parse a component of exactly `n` digits, checking that it lies within a range
+pub(super) fn ranged<'a>(
+    name: &'static str,
+    n: usize,
+    range: std::ops::RangeInclusive<u32>,
+    s: &'a str,
+) -> Result<'a, u32> {
+    let (value, rest) = try_parse!(fixed_digits(n, s));
+    if range.contains(&value) {
+        ParserResult::from_val(value, rest)
+    } else {
+        ParserResult::from_err(DateTimeError::OutOfRange(name), s)
+    }
+}
+
+/// Parse a full date such as `2024-02-29`
+pub fn date(s: &str) -> Result<'_, Date> {
+    let (year, s) = try_parse!(fixed_digits(4, s));
+    let (_, s) = try_parse!(literal("-", s));
+    let (month, s) = try_parse!(ranged("month", 2, 1..=12, s));
+    let (_, s) = try_parse!(literal("-", s));
+    let max_day = days_in_month(year as u16, month as u8) as u32;
+    let (day, s) = try_parse!(ranged("day", 2, 1..=max_day, s));
+    ParserResult::from_val(
+        Date {
+            year: year as u16,
+            month: month as u8,
+            day: day as u8,
+        },
+        s,
+    )
+}
+
+fn fraction(s: &str) -> Result<'_, u32> {
+    let (_, s) = try_parse!(literal(".", s));
+    let (digits, s) = try_parse!(take_while("digit", |c| c.is_ascii_digit(), s));
+    let nanos = digits
+        .bytes()
+        .chain(std::iter::repeat(b'0'))
+        .take(9)
+        .fold(0, |n, d| n * 10 + (d - b'0') as u32);
+    ParserResult::from_val(nanos, s)
+}
+
+/// Parse a time of day such as `23:59:60.5`, with optional fractional seconds
+pub fn time(s: &str) -> Result<'_, Time> {
+    let (hour, s) = try_parse!(ranged("hour", 2, 0..=23, s));
+    let (_, s) = try_parse!(literal(":", s));
+    let (minute, s) = try_parse!(ranged("minute", 2, 0..=59, s));
+    let (_, s) = try_parse!(literal(":", s));
+    let (second, s) = try_parse!(ranged("second", 2, 0..=60, s));
+    let (nanosecond, s) = try_parse!(fraction(s).optional(s));
+    ParserResult::from_val(
+        Time {
+            hour: hour as u8,
+            minute: minute as u8,
+            second: second as u8,
+            nanosecond: nanosecond.unwrap_or(0),
+        },
+        s,
+    )
+}
+
+/// Parse a UTC offset, either `Z` or a signed `HH:MM`, also accepting the ISO 8601 forms `HHMM` and `HH`
+pub fn offset(s: &str) -> Result<'_, Offset> {
+    if let Some(rest) = s.strip_prefix(['Z', 'z']) {
+        return ParserResult::from_val(Offset::Utc, rest);
+    }
+    let (sign, rest) = try_parse!(matching_char("offset", |c| c == '+' || c == '-', s));
+    let (hours, rest) = try_parse!(ranged("offset hour", 2, 0..=23, rest));
+    let minutes = |s| ranged("offset minute", 2, 0..=59, s);
+    // The colon belongs to the minutes, so that a colon without them is left unparsed
+    let colon_minutes = |s| {
+        let (_, s) = try_parse!(literal(":", s));
+        minutes(s)
+    };
+    let (minutes, rest) = try_parse!(colon_minutes(rest).or(minutes, rest).optional(rest));
+    ParserResult::from_val(signed_offset(sign, hours, minutes.unwrap_or(0)), rest)
+}
+
+/// Parse a UTC offset as RFC 3339 requires it, either `Z` or a signed `HH:MM`
+fn rfc3339_offset(s: &str) -> Result<'_, Offset> {
+    if let Some(rest) = s.strip_prefix(['Z', 'z']) {
+        return ParserResult::from_val(Offset::Utc, rest);
+    }
+    let (sign, rest) = try_parse!(matching_char("offset", |c| c == '+' || c == '-', s));
+    let (hours, rest) = try_parse!(ranged("offset hour", 2, 0..=23, rest));
+    let (_, rest) = try_parse!(literal(":", rest));
+    let (minutes, rest) = try_parse!(ranged("offset minute", 2, 0..=59, rest));
+    ParserResult::from_val(signed_offset(sign, hours, minutes), rest)
+}
+
+fn signed_offset(sign: char, hours: u32, minutes: u32) -> Offset {
+    let minutes = (hours * 60 + minutes) as i16;
+    Offset::Minutes(if sign == '-' { -minutes } else { minutes })
+}
+
+/// Parse a date and time joined by `T`, `t` or a space
+fn date_and_time(s: &str) -> Result<'_, (Date, Time)> {
+    let (date, s) = try_parse!(date(s));
+    let (_, s) = try_parse!(matching_char(
+        "date-time separator",
+        |c| matches!(c, 'T' | 't' | ' '),
+        s
+    ));
+    let (time, s) = try_parse!(time(s));
+    ParserResult::from_val((date, time), s)
+}
+
+/// Parse a date-time such as `1985-04-12T23:20:50.52Z`, where the offset is optional
+pub fn datetime(s: &str) -> Result<'_, DateTime> {
+    let ((date, time), s) = try_parse!(date_and_time(s));
+    let (offset, s) = try_parse!(offset(s).optional(s));
+    ParserResult::from_val(DateTime { date, time, offset }, s)
+}
+
+/// Parse an RFC 3339 timestamp, which requires an offset written as `Z` or `+HH:MM`
+pub fn rfc3339(s: &str) -> Result<'_, DateTime> {
+    let ((date, time), s) = try_parse!(date_and_time(s));
+    if !s.starts_with(['Z', 'z', '+', '-']) {
+        return ParserResult::from_err(DateTimeError::MissingOffset, s);
+    }
+    let (offset, s) = try_parse!(rfc3339_offset(s));
+    ParserResult::from_val(
+        DateTime {
+            date,
+            time,
+            offset: Some(offset),
+        },
+        s,
+    )
+}
+
+#[cfg(feature = "chrono")]
+mod chrono_impls {
+    use super::*;
+
+    impl TryFrom<Date> for chrono::NaiveDate {
+        type Error = DateTimeError;
+
+        fn try_from(d: Date) -> std::result::Result<Self, Self::Error> {
+            chrono::NaiveDate::from_ymd_opt(d.year as i32, d.month as u32, d.day as u32)
+                .ok_or(DateTimeError::OutOfRange("date"))
+        }
+    }
+
+    impl TryFrom<Time> for chrono::NaiveTime {
+        type Error = DateTimeError;
+
+        fn try_from(t: Time) -> std::result::Result<Self, Self::Error> {
+            // chrono represents leap seconds as an overflowing nanosecond count
+            let (second, nano) = if t.second == 60 {
+                (59, t.nanosecond + 1_000_000_000)
+            } else {
+                (t.second as u32, t.nanosecond)
+            };
+            chrono::NaiveTime::from_hms_nano_opt(t.hour as u32, t.minute as u32, second, nano)
+                .ok_or(DateTimeError::OutOfRange("time"))
+        }
+    }
+
+    impl TryFrom<Offset> for chrono::FixedOffset {
+        type Error = DateTimeError;
+
+        fn try_from(o: Offset) -> std::result::Result<Self, Self::Error> {
+            chrono::FixedOffset::east_opt(o.seconds()).ok_or(DateTimeError::OutOfRange("offset"))
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::NaiveDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(dt: DateTime) -> std::result::Result<Self, Self::Error> {
+            Ok(chrono::NaiveDateTime::new(
+                dt.date.try_into()?,
+                dt.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<DateTime> for chrono::DateTime<chrono::FixedOffset> {
+        type Error = DateTimeError;
+
+        fn try_from(dt: DateTime) -> std::result::Result<Self, Self::Error> {
+            let offset: chrono::FixedOffset =
+                dt.offset.ok_or(DateTimeError::MissingOffset)?.try_into()?;
+            let naive: chrono::NaiveDateTime = dt.try_into()?;
+            naive
+                .and_local_timezone(offset)
+                .single()
+                .ok_or(DateTimeError::OutOfRange("date-time"))
+        }
+    }
+}
+
+#[cfg(feature = "time")]
+mod time_impls {
+    use super::*;
+
+    impl TryFrom<Date> for time::Date {
+        type Error = DateTimeError;
+
+        fn try_from(d: Date) -> std::result::Result<Self, Self::Error> {
+            let month =
+                time::Month::try_from(d.month).map_err(|_| DateTimeError::OutOfRange("month"))?;
+            time::Date::from_calendar_date(d.year as i32, month, d.day)
+                .map_err(|_| DateTimeError::OutOfRange("date"))
+        }
+    }
+
+    impl TryFrom<Time> for time::Time {
+        type Error = DateTimeError;
+
+        /// Fails for leap seconds, which the `time` crate cannot represent
+        fn try_from(t: Time) -> std::result::Result<Self, Self::Error> {
+            time::Time::from_hms_nano(t.hour, t.minute, t.second, t.nanosecond)
+                .map_err(|_| DateTimeError::OutOfRange("second"))
+        }
+    }
+
+    impl TryFrom<Offset> for time::UtcOffset {
+        type Error = DateTimeError;
+
+        fn try_from(o: Offset) -> std::result::Result<Self, Self::Error> {
+            time::UtcOffset::from_whole_seconds(o.seconds())
+                .map_err(|_| DateTimeError::OutOfRange("offset"))
+        }
+    }
+
+    impl TryFrom<DateTime> for time::PrimitiveDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(dt: DateTime) -> std::result::Result<Self, Self::Error> {
+            Ok(time::PrimitiveDateTime::new(
+                dt.date.try_into()?,
+                dt.time.try_into()?,
+            ))
+        }
+    }
+
+    impl TryFrom<DateTime> for time::OffsetDateTime {
+        type Error = DateTimeError;
+
+        fn try_from(dt: DateTime) -> std::result::Result<Self, Self::Error> {
+            let offset: time::UtcOffset =
+                dt.offset.ok_or(DateTimeError::MissingOffset)?.try_into()?;
+            let primitive: time::PrimitiveDateTime = dt.try_into()?;
+            Ok(primitive.assume_offset(offset))
+        }
+    }
+}
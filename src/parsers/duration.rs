@@ -0,0 +1,161 @@
+use std::time::Duration;
+
+use super::quantity::unit;
+use crate::{error::ParserError, try_parse, Parser, ParserResult};
+
+/// A unit of time which a [DurationParser] can accept
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Unit {
+    Nanoseconds,
+    Microseconds,
+    Milliseconds,
+    Seconds,
+    Minutes,
+    Hours,
+    Days,
+    Weeks,
+}
+
+impl Unit {
+    const ALL: [Unit; 8] = [
+        Unit::Nanoseconds,
+        Unit::Microseconds,
+        Unit::Milliseconds,
+        Unit::Seconds,
+        Unit::Minutes,
+        Unit::Hours,
+        Unit::Days,
+        Unit::Weeks,
+    ];
+
+    /// The length of the unit in nanoseconds
+    fn nanos(self) -> u128 {
+        match self {
+            Unit::Nanoseconds => 1,
+            Unit::Microseconds => 1_000,
+            Unit::Milliseconds => 1_000_000,
+            Unit::Seconds => 1_000_000_000,
+            Unit::Minutes => 60 * 1_000_000_000,
+            Unit::Hours => 60 * 60 * 1_000_000_000,
+            Unit::Days => 24 * 60 * 60 * 1_000_000_000,
+            Unit::Weeks => 7 * 24 * 60 * 60 * 1_000_000_000,
+        }
+    }
+
+    /// The names the unit can be written as, with the shortest first
+    pub fn names(self) -> &'static [&'static str] {
+        match self {
+            Unit::Nanoseconds => &["ns", "nsec", "nanos", "nanosecond", "nanoseconds"],
+            Unit::Microseconds => &["us", "µs", "usec", "micros", "microsecond", "microseconds"],
+            Unit::Milliseconds => &["ms", "msec", "millis", "millisecond", "milliseconds"],
+            Unit::Seconds => &["s", "sec", "secs", "second", "seconds"],
+            Unit::Minutes => &["m", "min", "mins", "minute", "minutes"],
+            Unit::Hours => &["h", "hr", "hrs", "hour", "hours"],
+            Unit::Days => &["d", "day", "days"],
+            Unit::Weeks => &["w", "week", "weeks"],
+        }
+    }
+}
+
+/// A parser for human-readable durations such as `1h30m15s`, `2 days` or `1.5s`, made of one or more numbers each
+/// followed by a unit, optionally separated by spaces
+#[derive(Debug, Clone)]
+pub struct DurationParser {
+    units: Vec<Unit>,
+    names: Vec<(&'static str, Unit)>,
+}
+
+impl Default for DurationParser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl DurationParser {
+    /// Accept every [Unit]
+    pub fn new() -> Self {
+        Self::with_units(&Unit::ALL)
+    }
+
+    /// Accept only the given units, such as to disallow sub-second timeouts
+    pub fn with_units(units: &[Unit]) -> Self {
+        let names = units
+            .iter()
+            .flat_map(|&unit| unit.names().iter().map(move |&name| (name, unit)))
+            .collect();
+        Self {
+            units: units.to_vec(),
+            names,
+        }
+    }
+
+    /// Parse the name of an allowed unit, which must not be followed by more letters
+    fn unit<'a>(&self, input: &'a str) -> ParserResult<'a, Unit, ParserError> {
+        unit(&self.names, false, input).map_err(|_| {
+            let names: Vec<_> = self.units.iter().map(|unit| unit.names()[0]).collect();
+            ParserError::ExpectedToken(format!("unit ({})", names.join(", ")).into())
+        })
+    }
+}
+
+/// Parse a number with an optional fraction, returning it along with the power of ten its fraction is scaled by
+pub(super) fn number(input: &str) -> ParserResult<'_, (u128, u128), ParserError> {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let whole = digits(input);
+    let (frac, len) = match input[whole..].strip_prefix('.') {
+        Some(rest) => {
+            let frac = digits(rest);
+            (&rest[..frac], whole + 1 + frac)
+        }
+        None => ("", whole),
+    };
+    if whole == 0 && frac.is_empty() {
+        return ParserResult::from_err(ParserError::ExpectedToken("number".into()), input);
+    }
+    // Digits beyond 18 places can't affect a result in nanoseconds or bytes
+    let frac = &frac[..frac.len().min(18)];
+    let digits = input[..whole].bytes().chain(frac.bytes());
+    let mut value = 0u128;
+    for digit in digits {
+        value = match value.checked_mul(10) {
+            Some(n) => n + u128::from(digit - b'0'),
+            None => return ParserResult::from_err(ParserError::IntegerOverflow, input),
+        };
+    }
+    ParserResult::from_val((value, 10u128.pow(frac.len() as u32)), &input[len..])
+}
+
+impl<'a> Parser<'a, Duration, ParserError> for DurationParser {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, Duration, ParserError> {
+        let mut nanos = 0u128;
+        let (mut parsed, mut s) = try_parse!(number(input));
+        loop {
+            let (value, scale) = parsed;
+            let (unit, rest) = try_parse!(self.unit(s.trim_start_matches(' ')));
+            nanos = match value
+                .checked_mul(unit.nanos())
+                .and_then(|n| nanos.checked_add(n / scale))
+            {
+                Some(n) => n,
+                None => return ParserResult::from_err(ParserError::IntegerOverflow, s),
+            };
+            s = rest;
+            match number(s.trim_start_matches(' ')).into_result::<()>() {
+                Ok((next, rest)) => (parsed, s) = (next, rest),
+                Err(_) => break,
+            }
+        }
+        let secs = nanos / 1_000_000_000;
+        match u64::try_from(secs) {
+            Ok(secs) => {
+                ParserResult::from_val(Duration::new(secs, (nanos % 1_000_000_000) as u32), s)
+            }
+            Err(_) => ParserResult::from_err(ParserError::IntegerOverflow, input),
+        }
+    }
+}
+
+/// Parse a human-readable duration with any [Unit], see [DurationParser]
+pub fn duration(input: &str) -> ParserResult<'_, Duration, ParserError> {
+    DurationParser::new().parse(input)
+}
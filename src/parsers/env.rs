@@ -0,0 +1,141 @@
+use std::ops::Range;
+
+use crate::{
+    advance, comment::line_comment, error::ParserError, line_ending, literal, space0, take_while,
+    try_parse, ParserResult,
+};
+
+/// A `KEY=value` entry of a dotenv file, with the byte ranges of its key and value in the original input
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EnvVar {
+    pub key: String,
+    pub value: String,
+    pub key_span: Range<usize>,
+    /// The range of the value as written, including any quotes
+    pub value_span: Range<usize>,
+}
+
+fn line_end(input: &str) -> ParserResult<'_, (), ParserError> {
+    let (_, s) = try_parse!(space0(input));
+    let (_, s) = try_parse!(line_comment("#", s).optional(s));
+    if s.is_empty() {
+        return ParserResult::from_val((), s);
+    }
+    line_ending(s).is(())
+}
+
+/// Parse a key, which may be preceded by `export`
+pub fn key(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let s = match input.strip_prefix("export") {
+        Some(rest) if rest.starts_with([' ', '\t']) => rest.trim_start_matches([' ', '\t']),
+        _ => input,
+    };
+    take_while(
+        "key",
+        |c| c.is_ascii_alphanumeric() || matches!(c, '_' | '.' | '-'),
+        s,
+    )
+}
+
+/// Parse a single-quoted value, which is taken literally and may span multiple lines
+fn single_quoted(input: &str) -> ParserResult<'_, String, ParserError> {
+    let (_, s) = try_parse!(literal("'", input));
+    match s.find('\'') {
+        Some(end) => ParserResult::from_val(s[..end].to_string(), &s[end + 1..]),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral("'"), input),
+    }
+}
+
+/// Parse a double-quoted value, which may contain `\n`, `\r`, `\t`, `\"`, `\\` and `\$` escapes and span multiple
+/// lines
+fn double_quoted(input: &str) -> ParserResult<'_, String, ParserError> {
+    let (_, mut s) = try_parse!(literal("\"", input));
+    let mut value = String::new();
+    loop {
+        let (c, rest) = match advance(s).into_result::<()>() {
+            Ok(parsed) => parsed,
+            Err(_) => return ParserResult::from_err(ParserError::ExpectedLiteral("\""), input),
+        };
+        s = rest;
+        match c {
+            '"' => return ParserResult::from_val(value, s),
+            '\\' => {
+                let (escaped, rest) = try_parse!(advance(s));
+                s = rest;
+                match escaped {
+                    'n' => value.push('\n'),
+                    'r' => value.push('\r'),
+                    't' => value.push('\t'),
+                    '"' | '\\' | '$' => value.push(escaped),
+                    c => {
+                        value.push('\\');
+                        value.push(c);
+                    }
+                }
+            }
+            c => value.push(c),
+        }
+    }
+}
+
+/// Parse an unquoted value up to the end of the line or a ` #` comment, trimming surrounding whitespace
+fn unquoted(input: &str) -> ParserResult<'_, String, ParserError> {
+    let mut end = 0;
+    for (i, c) in input.char_indices() {
+        if matches!(c, '\r' | '\n') || (c == '#' && input[..i].ends_with([' ', '\t'])) {
+            break;
+        }
+        end = i + c.len_utf8();
+    }
+    let value = input[..end].trim_end();
+    ParserResult::from_val(value.to_string(), &input[value.len()..])
+}
+
+/// Parse a value, which may be single-quoted, double-quoted or unquoted
+pub fn value(input: &str) -> ParserResult<'_, String, ParserError> {
+    match input.chars().next() {
+        Some('\'') => single_quoted(input),
+        Some('"') => double_quoted(input),
+        _ => unquoted(input),
+    }
+}
+
+/// Parse a `KEY=value` line, recording spans relative to `original`
+pub fn entry<'a>(original: &'a str, input: &'a str) -> ParserResult<'a, EnvVar, ParserError> {
+    let offset = |s: &str| original.len() - s.len();
+    let (_, s) = try_parse!(space0(input));
+    let (key, s) = try_parse!(key(s));
+    let key_end = offset(s);
+    let key_span = key_end - key.len()..key_end;
+    let (_, s) = try_parse!(space0(s));
+    let (_, s) = try_parse!(literal("=", s));
+    let (_, s) = try_parse!(space0(s));
+    let value_start = offset(s);
+    let (value, s) = try_parse!(value(s));
+    let value_span = value_start..offset(s);
+    let (_, s) = try_parse!(line_end(s));
+    let var = EnvVar {
+        key: key.to_string(),
+        value,
+        key_span,
+        value_span,
+    };
+    ParserResult::from_val(var, s)
+}
+
+/// Parse an entire dotenv file into its entries in order, skipping blank lines and `#` comments
+pub fn parse_env(input: &str) -> ParserResult<'_, Vec<EnvVar>, ParserError> {
+    let mut vars = vec![];
+    let mut s = input;
+    while !s.is_empty() {
+        let blank = line_end(s);
+        if blank.is_ok() && blank.source.len() < s.len() {
+            s = blank.source;
+            continue;
+        }
+        let (var, rest) = try_parse!(entry(input, s));
+        vars.push(var);
+        s = rest;
+    }
+    ParserResult::from_val(vars, s)
+}
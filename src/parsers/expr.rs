@@ -0,0 +1,128 @@
+use std::num::ParseIntError;
+
+use crate::*;
+
+type Result<'a, T> = ParserResult<'a, T, ExprError>;
+
+#[derive(Debug)]
+pub enum ExprError {
+    ParseInt(ParseIntError),
+    ParserError(ParserError),
+}
+
+impl From<ParserError> for ExprError {
+    fn from(value: ParserError) -> Self {
+        ExprError::ParserError(value)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UnaryOp {
+    Neg,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Rem,
+}
+
+impl BinaryOp {
+    /// The binding power of this operator, where higher values bind more tightly
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOp::Add | BinaryOp::Sub => 1,
+            BinaryOp::Mul | BinaryOp::Div | BinaryOp::Rem => 2,
+        }
+    }
+}
+
+/// An arithmetic expression tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Expr {
+    Number(i64),
+    Unary(UnaryOp, Box<Expr>),
+    Binary(Box<Expr>, BinaryOp, Box<Expr>),
+}
+
+impl Expr {
+    /// Evaluate this expression, returning [None] on overflow or division by zero
+    pub fn eval(&self) -> Option<i64> {
+        match self {
+            Expr::Number(n) => Some(*n),
+            Expr::Unary(UnaryOp::Neg, e) => e.eval()?.checked_neg(),
+            Expr::Binary(a, op, b) => {
+                let (a, b) = (a.eval()?, b.eval()?);
+                match op {
+                    BinaryOp::Add => a.checked_add(b),
+                    BinaryOp::Sub => a.checked_sub(b),
+                    BinaryOp::Mul => a.checked_mul(b),
+                    BinaryOp::Div => a.checked_div(b),
+                    BinaryOp::Rem => a.checked_rem(b),
+                }
+            }
+        }
+    }
+}
+
+fn number(s: &str) -> Result<'_, Expr> {
+    let (digits, rest) = try_parse!(take_while("digit", |c| c.is_ascii_digit(), s));
+    match digits.parse() {
+        Ok(n) => ParserResult::from_val(Expr::Number(n), rest),
+        Err(e) => ParserResult::from_err(ExprError::ParseInt(e), s),
+    }
+}
+
+fn binary_op(s: &str) -> ParserResult<'_, BinaryOp, ParserError> {
+    matching_char("operator", |c| "+-*/%".contains(c), s).map(|c| match c {
+        '+' => BinaryOp::Add,
+        '-' => BinaryOp::Sub,
+        '*' => BinaryOp::Mul,
+        '/' => BinaryOp::Div,
+        _ => BinaryOp::Rem,
+    })
+}
+
+/// Parse a number, a negated atom, or a parenthesized expression
+fn atom(s: &str) -> Result<'_, Expr> {
+    let (_, s) = try_parse!(opt_whitespace(s));
+    match try_parse!(peek(s)).0 {
+        '-' => {
+            let (_, s) = try_parse!(advance(s));
+            atom(s).map(|e| Expr::Unary(UnaryOp::Neg, e.into()))
+        }
+        '(' => {
+            let (_, s) = try_parse!(advance(s));
+            let (e, mut s) = try_parse!(expr(s));
+            try_parse!(ignore((opt_whitespace, ")"), &mut s));
+            ParserResult::from_val(e, s)
+        }
+        _ => number(s),
+    }
+}
+
+/// Precedence climbing: parse operators binding at least as tightly as `min_precedence`
+fn expr_bp(min_precedence: u8, s: &str) -> Result<'_, Expr> {
+    let (mut lhs, mut s) = try_parse!(atom(s));
+    loop {
+        let (_, rest) = try_parse!(opt_whitespace(s));
+        let op = binary_op(rest);
+        let after = op.source;
+        let Some(op) = op.ok().filter(|op| op.precedence() >= min_precedence) else {
+            break;
+        };
+        // Binding the right side one level tighter makes every operator left-associative
+        let (rhs, rest) = try_parse!(expr_bp(op.precedence() + 1, after));
+        lhs = Expr::Binary(lhs.into(), op, rhs.into());
+        s = rest;
+    }
+    ParserResult::from_val(lhs, s)
+}
+
+/// Parse an arithmetic expression with `+ - * / %`, unary minus and parentheses
+pub fn expr(s: &str) -> Result<'_, Expr> {
+    expr_bp(0, s)
+}
@@ -0,0 +1,280 @@
+use std::ops::Bound;
+
+use crate::*;
+
+type Result<'a, T> = ParserResult<'a, T, ParserError>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassItem {
+    Char(char),
+    Range(char, char),
+}
+
+/// A set of characters, such as `[a-z_]` or `[!0-9]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match *item {
+            ClassItem::Char(ch) => c == ch,
+            ClassItem::Range(lo, hi) => (lo..=hi).contains(&c),
+        });
+        c != '/' && found != self.negated
+    }
+}
+
+/// A node of a parsed glob pattern
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Char(char),
+    /// `?`, matching any character but a `/`
+    Any,
+    /// `*`, matching any sequence of characters within a single path segment
+    Star,
+    /// `**`, matching any sequence of characters including `/`
+    AnyPath,
+    /// `**/`, matching zero or more whole directories
+    AnyDirs,
+    Class(Class),
+    /// `{a,b}`, matching any of the alternatives
+    Alternation(Vec<Vec<Node>>),
+}
+
+/// Parse a character which may be escaped with a `\`
+fn glob_char(s: &str) -> Result<'_, char> {
+    match s.strip_prefix('\\') {
+        Some(rest) => advance(rest),
+        None => advance(s),
+    }
+}
+
+fn class_item(s: &str) -> Result<'_, ClassItem> {
+    let (lo, rest) = try_parse!(glob_char(s));
+    if !rest.starts_with('-') || rest[1..].starts_with(']') {
+        return ParserResult::from_val(ClassItem::Char(lo), rest);
+    }
+    let (hi, end) = try_parse!(glob_char(&rest[1..]));
+    if hi < lo {
+        return ParserResult::from_err(
+            ParserError::ExpectedCharInRange(Bound::Included(lo), Bound::Unbounded),
+            &rest[1..],
+        );
+    }
+    ParserResult::from_val(ClassItem::Range(lo, hi), end)
+}
+
+/// Parse a bracketed class such as `[!a-z]`, where a leading `]` is taken literally
+fn class(s: &str) -> Result<'_, Class> {
+    let (_, s) = try_parse!(literal("[", s));
+    let negated = s.starts_with(['!', '^']);
+    let mut s = if negated { &s[1..] } else { s };
+    let mut items = vec![];
+    loop {
+        if s.starts_with(']') && !items.is_empty() {
+            break;
+        }
+        let (item, rest) = try_parse!(class_item(s));
+        items.push(item);
+        s = rest;
+    }
+    let (_, s) = try_parse!(literal("]", s));
+    ParserResult::from_val(Class { negated, items }, s)
+}
+
+/// Parse a `{a,b}` alternation, whose alternatives may themselves contain any glob syntax
+fn alternation(s: &str) -> Result<'_, Node> {
+    let (_, mut s) = try_parse!(literal("{", s));
+    let mut alts = vec![];
+    loop {
+        let (alt, rest) = try_parse!(sequence(true, s));
+        alts.push(alt);
+        if let Some(rest) = rest.strip_prefix(',') {
+            s = rest;
+            continue;
+        }
+        let (_, rest) = try_parse!(literal("}", rest));
+        return ParserResult::from_val(Node::Alternation(alts), rest);
+    }
+}
+
+fn node(s: &str) -> Result<'_, Node> {
+    if let Some(rest) = s.strip_prefix("**/") {
+        return ParserResult::from_val(Node::AnyDirs, rest);
+    }
+    if let Some(rest) = s.strip_prefix("**") {
+        return ParserResult::from_val(Node::AnyPath, rest);
+    }
+    match try_parse!(peek(s)).0 {
+        '*' => ParserResult::from_val(Node::Star, &s[1..]),
+        '?' => ParserResult::from_val(Node::Any, &s[1..]),
+        '[' => class(s).map(Node::Class),
+        '{' => alternation(s),
+        _ => glob_char(s).map(Node::Char),
+    }
+}
+
+/// Parse nodes up to the end of the input, or up to a `,` or `}` inside an alternation
+fn sequence(nested: bool, mut s: &str) -> Result<'_, Vec<Node>> {
+    let mut nodes = vec![];
+    while !s.is_empty() && (!nested || !s.starts_with([',', '}'])) {
+        let (node, rest) = try_parse!(node(s));
+        nodes.push(node);
+        s = rest;
+    }
+    ParserResult::from_val(nodes, s)
+}
+
+/// Parse a glob pattern into its nodes
+pub fn pattern(s: &str) -> Result<'_, Vec<Node>> {
+    sequence(false, s)
+}
+
+/// An instruction of a compiled pattern
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    /// Any character but a `/`
+    Any,
+    /// Any character including a `/`
+    AnyPath,
+    Class(Class),
+    /// Continue at both targets
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+fn compile(nodes: &[Node], prog: &mut Vec<Inst>) {
+    for node in nodes {
+        match node {
+            Node::Char(c) => prog.push(Inst::Char(*c)),
+            Node::Any => prog.push(Inst::Any),
+            Node::Class(class) => prog.push(Inst::Class(class.clone())),
+            Node::Star => {
+                let start = prog.len();
+                prog.extend([
+                    Inst::Split(start + 1, start + 3),
+                    Inst::Any,
+                    Inst::Jump(start),
+                ]);
+            }
+            Node::AnyPath => {
+                let start = prog.len();
+                prog.extend([
+                    Inst::Split(start + 1, start + 3),
+                    Inst::AnyPath,
+                    Inst::Jump(start),
+                ]);
+            }
+            Node::AnyDirs => {
+                // Either nothing, or any characters ending with a `/`
+                let start = prog.len();
+                prog.extend([
+                    Inst::Split(start + 1, start + 5),
+                    Inst::Split(start + 2, start + 4),
+                    Inst::AnyPath,
+                    Inst::Jump(start + 1),
+                    Inst::Char('/'),
+                ]);
+            }
+            Node::Alternation(alts) => {
+                let mut jumps = vec![];
+                for (i, alt) in alts.iter().enumerate() {
+                    if i + 1 == alts.len() {
+                        compile(alt, prog);
+                        break;
+                    }
+                    let split = prog.len();
+                    prog.push(Inst::Jump(0));
+                    compile(alt, prog);
+                    jumps.push(prog.len());
+                    prog.push(Inst::Jump(0));
+                    prog[split] = Inst::Split(split + 1, prog.len());
+                }
+                let end = prog.len();
+                for jump in jumps {
+                    prog[jump] = Inst::Jump(end);
+                }
+            }
+        }
+    }
+}
+/// A compiled glob pattern, which matches whole paths using `/` as the separator
+#[derive(Debug, Clone)]
+pub struct Glob {
+    nodes: Vec<Node>,
+    prog: Vec<Inst>,
+}
+
+impl Glob {
+    /// Compile a pattern, failing with the position of any syntax error
+    pub fn new(pattern_str: &str) -> std::result::Result<Glob, (ParserError, usize)> {
+        let res = pattern(pattern_str);
+        let offset = pattern_str.len() - res.source.len();
+        match res.typ {
+            ParserResultType::Ok(nodes) => {
+                let mut prog = vec![];
+                compile(&nodes, &mut prog);
+                prog.push(Inst::Match);
+                Ok(Glob { nodes, prog })
+            }
+            ParserResultType::Err(e) => Err((e, offset)),
+            ParserResultType::Incomplete(_) => Err((ParserError::UnexpectedEndOfFile, offset)),
+        }
+    }
+
+    /// Get the nodes of this pattern
+    pub fn nodes(&self) -> &[Node] {
+        &self.nodes
+    }
+
+    /// Add the state at `pc` and every state reachable from it without consuming input
+    fn add(&self, states: &mut Vec<usize>, seen: &mut [bool], pc: usize) {
+        if std::mem::replace(&mut seen[pc], true) {
+            return;
+        }
+        match self.prog[pc] {
+            Inst::Jump(to) => self.add(states, seen, to),
+            Inst::Split(first, second) => {
+                self.add(states, seen, first);
+                self.add(states, seen, second);
+            }
+            _ => states.push(pc),
+        }
+    }
+
+    /// Check whether this pattern matches the whole path, in time proportional to the length of the path times the
+    /// size of the pattern
+    pub fn is_match(&self, path: &str) -> bool {
+        let mut seen = vec![false; self.prog.len()];
+        let mut states = vec![];
+        self.add(&mut states, &mut seen, 0);
+        for c in path.chars() {
+            let mut next = vec![];
+            seen.fill(false);
+            for &pc in &states {
+                let matched = match &self.prog[pc] {
+                    Inst::Char(ch) => c == *ch,
+                    Inst::Any => c != '/',
+                    Inst::AnyPath => true,
+                    Inst::Class(class) => class.matches(c),
+                    _ => false,
+                };
+                if matched {
+                    self.add(&mut next, &mut seen, pc + 1);
+                }
+            }
+            if next.is_empty() {
+                return false;
+            }
+            states = next;
+        }
+        states
+            .iter()
+            .any(|&pc| matches!(self.prog[pc], Inst::Match))
+    }
+}
@@ -0,0 +1,139 @@
+use crate::{error::ParserError, try_parse, ParserResult};
+
+/// Parse two hex digits into a byte
+fn hex_byte(input: &str) -> ParserResult<'_, u8, ParserError> {
+    match input.get(..2).map(|digits| u8::from_str_radix(digits, 16)) {
+        Some(Ok(byte)) if !input.starts_with('+') => ParserResult::from_val(byte, &input[2..]),
+        _ => ParserResult::from_err(ParserError::ExpectedToken("hex digit".into()), input),
+    }
+}
+
+/// Parse a contiguous string of hex digit pairs such as `deadBEEF` into bytes
+pub fn hex_bytes(input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (first, mut s) = try_parse!(hex_byte(input));
+    let mut bytes = vec![first];
+    while s.starts_with(|c: char| c.is_ascii_hexdigit()) {
+        let (byte, rest) = try_parse!(hex_byte(s));
+        bytes.push(byte);
+        s = rest;
+    }
+    ParserResult::from_val(bytes, s)
+}
+
+/// Parse hex digit pairs separated by a character, such as the `:` of a MAC address like `00:1A:2b:3C:4d:5E` or the
+/// spaces of a fingerprint
+pub fn separated_hex_bytes(sep: char, input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (first, mut s) = try_parse!(hex_byte(input));
+    let mut bytes = vec![first];
+    while let Some(rest) = s.strip_prefix(sep) {
+        let Ok((byte, rest)) = hex_byte(rest).into_result::<()>() else {
+            break;
+        };
+        bytes.push(byte);
+        s = rest;
+    }
+    ParserResult::from_val(bytes, s)
+}
+
+/// Parse the hex digits of a hexdump line into bytes, stopping at the text column
+///
+/// Lines from `xxd` use groups of digits separated by single spaces, with the text column after two spaces, while
+/// lines from `hexdump -C` use separate bytes with an extra space in the middle, and the text column between `|`s.
+fn dump_line<'a>(
+    xxd: bool,
+    bytes: &mut Vec<u8>,
+    mut s: &'a str,
+) -> ParserResult<'a, (), ParserError> {
+    loop {
+        let gap = s.len() - s.trim_start_matches(' ').len();
+        let group = &s[gap..];
+        let ends = group.is_empty() || group.starts_with(['|', '\n', '\r']);
+        if ends || gap == 0 || (xxd && gap > 1) || gap > 2 {
+            return ParserResult::from_val((), s);
+        }
+        let end = group.find([' ', '\n', '\r']).unwrap_or(group.len());
+        let digits = &group[..end];
+        if !digits.len().is_multiple_of(2) || !digits.bytes().all(|b| b.is_ascii_hexdigit()) {
+            // `hexdump -C` only uses single bytes, so anything else must be text
+            if !xxd {
+                return ParserResult::from_err(
+                    ParserError::ExpectedToken("hex digit".into()),
+                    group,
+                );
+            }
+            return ParserResult::from_val((), s);
+        }
+        let mut digits = digits;
+        while !digits.is_empty() {
+            let (byte, rest) = try_parse!(hex_byte(digits));
+            bytes.push(byte);
+            digits = rest;
+        }
+        s = &group[end..];
+    }
+}
+
+/// Parse the output of `xxd` or `hexdump -C` back into at most `max_len` bytes, checking that each line's offset
+/// follows on from the previous line, and expanding the `*` lines which `hexdump` writes in place of repeated lines
+pub fn hexdump(max_len: usize, input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let mut bytes = vec![];
+    let mut prev: Vec<u8> = vec![];
+    let mut repeat = false;
+    let mut s = input;
+    while !s.is_empty() {
+        let end = s.find('\n').map_or(s.len(), |i| i + 1);
+        let (line, rest) = s.split_at(end);
+        let trimmed = line.trim_end();
+        if trimmed.is_empty() {
+            s = rest;
+            continue;
+        }
+        if trimmed == "*" {
+            repeat = true;
+            s = rest;
+            continue;
+        }
+        let digits = trimmed.len()
+            - trimmed
+                .trim_start_matches(|c: char| c.is_ascii_hexdigit())
+                .len();
+        let Ok(offset) = usize::from_str_radix(&trimmed[..digits], 16) else {
+            return ParserResult::from_err(ParserError::ExpectedToken("offset".into()), line);
+        };
+        if offset > max_len {
+            let expected = format!("offset at most {max_len:08x}");
+            return ParserResult::from_err(ParserError::ExpectedToken(expected.into()), line);
+        }
+        if repeat && !prev.is_empty() {
+            // The skipped lines must each repeat the previous line in full
+            let gap = offset.saturating_sub(bytes.len());
+            if gap.is_multiple_of(prev.len()) {
+                if let Err(err) = bytes.try_reserve(gap) {
+                    return ParserResult::from_err(ParserError::external(err), line);
+                }
+                for _ in 0..gap / prev.len() {
+                    bytes.extend_from_slice(&prev);
+                }
+            }
+            repeat = false;
+        }
+        if offset != bytes.len() {
+            let expected = format!("offset {:08x}", bytes.len());
+            return ParserResult::from_err(ParserError::ExpectedToken(expected.into()), line);
+        }
+        let after = &line[digits..];
+        let (xxd, data) = match after.strip_prefix(':') {
+            Some(data) => (true, data),
+            None => (false, after),
+        };
+        let start = bytes.len();
+        try_parse!(dump_line(xxd, &mut bytes, data));
+        if bytes.len() > max_len {
+            let expected = format!("at most {max_len} bytes");
+            return ParserResult::from_err(ParserError::ExpectedToken(expected.into()), line);
+        }
+        prev = bytes[start..].to_vec();
+        s = rest;
+    }
+    ParserResult::from_val(bytes, s)
+}
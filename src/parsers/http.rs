@@ -0,0 +1,318 @@
+use std::cmp::Reverse;
+
+use super::mime::skip_ows;
+pub use super::mime::{
+    media_type, parameters, quoted_string, token, token_or_quoted, MediaType, Parameter,
+};
+use crate::{
+    error::ParserError, literal, matching_char, take_while, try_parse, Parser, ParserResult,
+};
+
+/// An HTTP protocol version, such as `HTTP/1.1`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Version {
+    pub major: u8,
+    pub minor: u8,
+}
+
+/// The first line of an HTTP request
+#[derive(Debug, Clone, PartialEq)]
+pub struct RequestLine<'a> {
+    pub method: &'a str,
+    pub target: &'a str,
+    pub version: Version,
+}
+
+/// The first line of an HTTP response
+#[derive(Debug, Clone, PartialEq)]
+pub struct StatusLine<'a> {
+    pub version: Version,
+    pub status: u16,
+    pub reason: &'a str,
+}
+
+/// A header field, with its value trimmed and any obsolete line folding replaced by single spaces
+#[derive(Debug, Clone, PartialEq)]
+pub struct Header<'a> {
+    pub name: &'a str,
+    pub value: String,
+}
+
+/// The head of an HTTP request, excluding the body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request<'a> {
+    pub line: RequestLine<'a>,
+    pub headers: Vec<Header<'a>>,
+}
+
+/// The head of an HTTP response, excluding the body
+#[derive(Debug, Clone, PartialEq)]
+pub struct Response<'a> {
+    pub line: StatusLine<'a>,
+    pub headers: Vec<Header<'a>>,
+}
+
+fn find_header<'b>(headers: &'b [Header], name: &str) -> Option<&'b str> {
+    headers
+        .iter()
+        .find(|h| h.name.eq_ignore_ascii_case(name))
+        .map(|h| h.value.as_str())
+}
+
+impl<'a> Request<'a> {
+    /// Get the value of the first header with the given name, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+impl<'a> Response<'a> {
+    /// Get the value of the first header with the given name, ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        find_header(&self.headers, name)
+    }
+}
+
+fn is_ows(c: char) -> bool {
+    c == ' ' || c == '\t'
+}
+
+fn digit(input: &str) -> ParserResult<'_, u8, ParserError> {
+    matching_char("digit", |c| c.is_ascii_digit(), input).map(|c| c as u8 - b'0')
+}
+
+/// Split off a single CRLF-terminated line, which is incomplete if the terminator has not arrived yet
+fn line(input: &str) -> ParserResult<'_, &str, ParserError> {
+    match input.find("\r\n") {
+        Some(end) => ParserResult::from_val(&input[..end], &input[end + 2..]),
+        None => ParserResult::incomplete(input),
+    }
+}
+
+fn end_of_line(input: &str) -> ParserResult<'_, (), ParserError> {
+    if input.is_empty() {
+        ParserResult::from_val((), input)
+    } else {
+        ParserResult::from_err(ParserError::ExpectedToken("end of line".into()), input)
+    }
+}
+
+/// Parse a protocol version such as `HTTP/1.1`
+pub fn version(input: &str) -> ParserResult<'_, Version, ParserError> {
+    let (_, s) = try_parse!(literal("HTTP/", input));
+    let (major, s) = try_parse!(digit(s));
+    let (_, s) = try_parse!(literal(".", s));
+    let (minor, s) = try_parse!(digit(s));
+    ParserResult::from_val(Version { major, minor }, s)
+}
+
+/// Parse a request line such as `GET /index.html HTTP/1.1`, including its CRLF
+pub fn request_line(input: &str) -> ParserResult<'_, RequestLine<'_>, ParserError> {
+    let (line, rest) = try_parse!(line(input));
+    let (method, s) = try_parse!(token(line));
+    let (_, s) = try_parse!(literal(" ", s));
+    let (target, s) = try_parse!(take_while("request target", |c| c != ' ', s));
+    let (_, s) = try_parse!(literal(" ", s));
+    let (version, s) = try_parse!(version(s));
+    try_parse!(end_of_line(s));
+    ParserResult::from_val(
+        RequestLine {
+            method,
+            target,
+            version,
+        },
+        rest,
+    )
+}
+
+/// Parse a status line such as `HTTP/1.1 404 Not Found`, including its CRLF
+pub fn status_line(input: &str) -> ParserResult<'_, StatusLine<'_>, ParserError> {
+    let (line, rest) = try_parse!(line(input));
+    let (version, s) = try_parse!(version(line));
+    let (_, s) = try_parse!(literal(" ", s));
+    let (mut status, mut s) = (0, s);
+    for _ in 0..3 {
+        let (d, rest) = try_parse!(digit(s));
+        status = status * 10 + d as u16;
+        s = rest;
+    }
+    let reason = match s.strip_prefix(' ') {
+        Some(reason) => reason,
+        None => {
+            try_parse!(end_of_line(s));
+            s
+        }
+    };
+    ParserResult::from_val(
+        StatusLine {
+            version,
+            status,
+            reason,
+        },
+        rest,
+    )
+}
+
+/// Parse a block of header fields up to and including the empty line which terminates it
+pub fn headers(mut input: &str) -> ParserResult<'_, Vec<Header<'_>>, ParserError> {
+    let mut headers: Vec<Header> = vec![];
+    loop {
+        let (line, rest) = try_parse!(line(input));
+        if line.is_empty() {
+            return ParserResult::from_val(headers, rest);
+        }
+        if line.starts_with(is_ows) {
+            let Some(header) = headers.last_mut() else {
+                return ParserResult::from_err(
+                    ParserError::ExpectedToken("header field".into()),
+                    input,
+                );
+            };
+            if !header.value.is_empty() {
+                header.value.push(' ');
+            }
+            header.value.push_str(line.trim_matches(is_ows));
+        } else {
+            let (name, s) = try_parse!(token(line));
+            let (_, s) = try_parse!(literal(":", s));
+            headers.push(Header {
+                name,
+                value: s.trim_matches(is_ows).to_string(),
+            });
+        }
+        input = rest;
+    }
+}
+
+/// Parse the head of a request, which is incomplete until the terminating CRLFCRLF has arrived
+pub fn request(input: &str) -> ParserResult<'_, Request<'_>, ParserError> {
+    let (line, s) = try_parse!(request_line(input));
+    let (headers, s) = try_parse!(headers(s));
+    ParserResult::from_val(Request { line, headers }, s)
+}
+
+/// Parse the head of a response, which is incomplete until the terminating CRLFCRLF has arrived
+pub fn response(input: &str) -> ParserResult<'_, Response<'_>, ParserError> {
+    let (line, s) = try_parse!(status_line(input));
+    let (headers, s) = try_parse!(headers(s));
+    ParserResult::from_val(Response { line, headers }, s)
+}
+
+/// A list element with its `q` weight from 0 to 1000, which is 1000 when it has no weight
+#[derive(Debug, Clone, PartialEq)]
+pub struct Weighted<T> {
+    pub value: T,
+    pub quality: u16,
+}
+
+/// Parse a weight such as `0.8` into thousandths
+fn qvalue(input: &str) -> ParserResult<'_, u16, ParserError> {
+    let (whole, s) = try_parse!(matching_char("qvalue", |c| c == '0' || c == '1', input));
+    let Some(s) = s.strip_prefix('.') else {
+        return ParserResult::from_val(if whole == '1' { 1000 } else { 0 }, s);
+    };
+    let len = s.bytes().take(3).take_while(u8::is_ascii_digit).count();
+    let mut quality = if whole == '1' { 1000 } else { 0 };
+    for (i, digit) in s[..len].bytes().enumerate() {
+        quality += u16::from(digit - b'0') * [100, 10, 1][i];
+    }
+    if quality > 1000 {
+        return ParserResult::from_err(ParserError::ExpectedToken("qvalue".into()), input);
+    }
+    ParserResult::from_val(quality, &s[len..])
+}
+
+/// Parse a `q` parameter such as `;q=0.8`
+fn weight(input: &str) -> ParserResult<'_, u16, ParserError> {
+    let Some(s) = skip_ows(input).strip_prefix(';') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral(";"), input);
+    };
+    let s = skip_ows(s);
+    match s.get(..2) {
+        Some(q) if q.eq_ignore_ascii_case("q=") => qvalue(&s[2..]),
+        _ => ParserResult::from_err(ParserError::ExpectedLiteral("q="), s),
+    }
+}
+
+/// Parse a comma-separated list of elements, such as the value of `Connection` or `Cache-Control`, ignoring empty
+/// elements as recipients are required to
+pub fn list<'a, T>(
+    element: impl Parser<'a, T, ParserError>,
+    mut input: &'a str,
+) -> ParserResult<'a, Vec<T>, ParserError> {
+    let mut elems = vec![];
+    loop {
+        let s = skip_ows(input);
+        if let Some(s) = s.strip_prefix(',') {
+            input = s;
+            continue;
+        }
+        if s.is_empty() {
+            return ParserResult::from_val(elems, s);
+        }
+        let (elem, s) = try_parse!(element.parse(s));
+        elems.push(elem);
+        let after = skip_ows(s);
+        if !after.is_empty() && !after.starts_with(',') {
+            return ParserResult::from_err(ParserError::ExpectedLiteral(","), after);
+        }
+        input = after;
+    }
+}
+
+/// Parse a comma-separated list of weighted elements, such as the value of `Accept-Encoding` or `Accept-Language`,
+/// sorted from the highest weight to the lowest, keeping elements with equal weights in order
+pub fn quality_list<'a, T>(
+    element: impl Parser<'a, T, ParserError>,
+    input: &'a str,
+) -> ParserResult<'a, Vec<Weighted<T>>, ParserError> {
+    let weighted = |s| {
+        let (value, s) = try_parse!(element.parse(s));
+        let (quality, s) = try_parse!(weight(s).optional(s));
+        ParserResult::from_val(
+            Weighted {
+                value,
+                quality: quality.unwrap_or(1000),
+            },
+            s,
+        )
+    };
+    list(weighted, input).map(|mut elems| {
+        elems.sort_by_key(|elem| Reverse(elem.quality));
+        elems
+    })
+}
+
+/// Parse the value of an `Accept` header into media ranges such as `text/*`, sorted from the highest weight to the
+/// lowest, with the `q` parameter and any extension parameters after it removed from each media type's parameters
+pub fn accept(input: &str) -> ParserResult<'_, Vec<Weighted<MediaType<'_>>>, ParserError> {
+    let range = |s| {
+        let (mut media, s) = try_parse!(media_type(s));
+        let quality = match media
+            .params
+            .iter()
+            .position(|p| p.name.eq_ignore_ascii_case("q"))
+        {
+            Some(i) => {
+                let q = media.params[i].value.clone();
+                let Ok((quality, "")) = qvalue(&q).into_result::<()>() else {
+                    return ParserResult::from_err(ParserError::ExpectedToken("qvalue".into()), s);
+                };
+                media.params.truncate(i);
+                quality
+            }
+            None => 1000,
+        };
+        ParserResult::from_val(
+            Weighted {
+                value: media,
+                quality,
+            },
+            s,
+        )
+    };
+    list(range, input).map(|mut elems| {
+        elems.sort_by_key(|elem| Reverse(elem.quality));
+        elems
+    })
+}
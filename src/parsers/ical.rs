@@ -0,0 +1,222 @@
+use crate::{error::ParserError, try_parse, ParserResult};
+
+/// A property parameter such as `TZID=Europe/London` or `MEMBER="mailto:a@example.com","mailto:b@example.com"`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Param {
+    /// The name of the parameter, in uppercase
+    pub name: String,
+    pub values: Vec<String>,
+}
+
+/// A content line of an iCalendar (RFC 5545) or vCard (RFC 6350) document, such as
+/// `DTSTART;TZID=Europe/London:20240101T090000`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContentLine {
+    /// The group before the name in uppercase, such as `ITEM1` for the vCard property `item1.EMAIL`
+    pub group: Option<String>,
+    /// The name of the property, in uppercase
+    pub name: String,
+    pub params: Vec<Param>,
+    /// The unfolded value, with any escapes left in place since only some value types use them
+    pub value: String,
+}
+
+impl ContentLine {
+    /// Get the values of a parameter, matching its name ignoring case
+    pub fn param(&self, name: &str) -> Option<&[String]> {
+        self.params
+            .iter()
+            .find(|param| param.name.eq_ignore_ascii_case(name))
+            .map(|param| param.values.as_slice())
+    }
+
+    /// Get the value as TEXT, with its escapes replaced
+    pub fn text(&self) -> String {
+        unescape_text(&self.value)
+    }
+
+    /// Get the value as a comma-separated list of TEXT, such as for `CATEGORIES`
+    pub fn texts(&self) -> Vec<String> {
+        let mut texts = vec![];
+        let mut start = 0;
+        let mut escaped = false;
+        for (i, c) in self.value.char_indices() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' => escaped = true,
+                ',' => {
+                    texts.push(unescape_text(&self.value[start..i]));
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        texts.push(unescape_text(&self.value[start..]));
+        texts
+    }
+}
+
+/// Replace the `\\`, `\;`, `\,` and `\n` escapes of a TEXT value
+pub fn unescape_text(value: &str) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n' | 'N')) => text.push('\n'),
+            ('\\', Some(c @ ('\\' | ';' | ','))) => text.push(c),
+            (c, _) => {
+                text.push(c);
+                continue;
+            }
+        }
+        chars.next();
+    }
+    text
+}
+
+/// Skip any folds at the start of the input, each being a line break followed by a space or tab
+fn unfold(mut input: &str) -> &str {
+    loop {
+        let rest = input.strip_prefix("\r\n").or(input.strip_prefix('\n'));
+        match rest {
+            Some(rest) if rest.starts_with([' ', '\t']) => input = &rest[1..],
+            _ => return input,
+        }
+    }
+}
+
+/// Get the next character of a content line, skipping folds
+fn next_char(input: &str) -> Option<(char, &str)> {
+    let s = unfold(input);
+    let c = s.chars().next()?;
+    Some((c, &s[c.len_utf8()..]))
+}
+
+/// Parse a property or parameter name made of letters, digits and dashes
+fn ident<'a>(expected: &'static str, input: &'a str) -> ParserResult<'a, String, ParserError> {
+    let mut name = String::new();
+    let mut s = input;
+    while let Some((c, rest)) = next_char(s).filter(|(c, _)| c.is_ascii_alphanumeric() || *c == '-')
+    {
+        name.push(c.to_ascii_uppercase());
+        s = rest;
+    }
+    if name.is_empty() {
+        return ParserResult::from_err(ParserError::ExpectedToken(expected.into()), input);
+    }
+    ParserResult::from_val(name, s)
+}
+
+/// Parse a parameter value, which may be quoted, replacing the `^n`, `^^` and `^'` escapes of RFC 6868
+fn param_value(input: &str) -> ParserResult<'_, String, ParserError> {
+    let mut value = String::new();
+    let mut s = input;
+    let quoted = matches!(next_char(s), Some(('"', _)));
+    if quoted {
+        s = next_char(s).unwrap().1;
+    }
+    loop {
+        match next_char(s) {
+            Some(('"', rest)) if quoted => {
+                s = rest;
+                break;
+            }
+            Some(('"' | ';' | ':' | ',', _)) if !quoted => break,
+            Some(('\r' | '\n', _)) | None if quoted => {
+                return ParserResult::from_err(ParserError::ExpectedLiteral("\""), s)
+            }
+            Some(('\r' | '\n', _)) | None => break,
+            Some(('^', rest)) => {
+                let escaped = next_char(rest).and_then(|(c, after)| match c {
+                    'n' => Some(('\n', after)),
+                    '^' => Some(('^', after)),
+                    '\'' => Some(('"', after)),
+                    _ => None,
+                });
+                let (c, rest) = escaped.unwrap_or(('^', rest));
+                value.push(c);
+                s = rest;
+            }
+            Some((c, rest)) => {
+                value.push(c);
+                s = rest;
+            }
+        }
+    }
+    ParserResult::from_val(value, s)
+}
+
+/// Parse a `;NAME=value,value` parameter
+fn param(input: &str) -> ParserResult<'_, Param, ParserError> {
+    let (name, mut s) = match next_char(input) {
+        Some((';', rest)) => try_parse!(ident("parameter name", rest)),
+        _ => return ParserResult::from_err(ParserError::ExpectedLiteral(";"), input),
+    };
+    match next_char(s) {
+        Some(('=', rest)) => s = rest,
+        _ => return ParserResult::from_err(ParserError::ExpectedLiteral("="), s),
+    }
+    let mut values = vec![];
+    loop {
+        let (value, rest) = try_parse!(param_value(s));
+        values.push(value);
+        match next_char(rest) {
+            Some((',', rest)) => s = rest,
+            _ => {
+                s = rest;
+                break;
+            }
+        }
+    }
+    ParserResult::from_val(Param { name, values }, s)
+}
+
+/// Parse a single content line, unfolding it and consuming its line ending
+pub fn content_line(input: &str) -> ParserResult<'_, ContentLine, ParserError> {
+    let (mut name, mut s) = try_parse!(ident("property name", input));
+    let mut group = None;
+    if let Some(('.', rest)) = next_char(s) {
+        let (property, rest) = try_parse!(ident("property name", rest));
+        group = Some(std::mem::replace(&mut name, property));
+        s = rest;
+    }
+    let mut params = vec![];
+    while let Some((';', _)) = next_char(s) {
+        let (param, rest) = try_parse!(param(s));
+        params.push(param);
+        s = rest;
+    }
+    match next_char(s) {
+        Some((':', rest)) => s = rest,
+        _ => return ParserResult::from_err(ParserError::ExpectedLiteral(":"), s),
+    }
+    let mut value = String::new();
+    while let Some((c, rest)) = next_char(s).filter(|(c, _)| !matches!(c, '\r' | '\n')) {
+        value.push(c);
+        s = rest;
+    }
+    let s = unfold(s);
+    let s = s.strip_prefix("\r\n").or(s.strip_prefix('\n')).unwrap_or(s);
+    let line = ContentLine {
+        group,
+        name,
+        params,
+        value,
+    };
+    ParserResult::from_val(line, s)
+}
+
+/// Parse every content line of a document, skipping blank lines
+pub fn content_lines(mut input: &str) -> ParserResult<'_, Vec<ContentLine>, ParserError> {
+    let mut lines = vec![];
+    loop {
+        input = input.trim_start_matches(['\r', '\n']);
+        if input.is_empty() {
+            break;
+        }
+        let (line, s) = try_parse!(content_line(input));
+        lines.push(line);
+        input = s;
+    }
+    ParserResult::from_val(lines, input)
+}
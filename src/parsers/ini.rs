@@ -0,0 +1,98 @@
+use std::collections::HashMap;
+
+use crate::{
+    comment::line_comment, error::ParserError, ignore, line_ending, not_line_ending,
+    opt_whitespace, peek, space0, take_while, try_parse, Parser, ParserResult,
+};
+
+/// The sections of an INI document, each mapping keys to values; properties before the first section header use the empty section name
+pub type Ini = HashMap<String, HashMap<String, String>>;
+
+fn line_end(input: &str) -> ParserResult<'_, (), ParserError> {
+    if input.is_empty() {
+        return ParserResult::from_val((), input);
+    }
+    line_ending(input).is(())
+}
+
+/// Parse a comment starting with `;` or `#`, returning its body
+pub fn comment(input: &str) -> ParserResult<'_, &str, ParserError> {
+    line_comment(";", input).or(|s| line_comment("#", s), input)
+}
+
+/// Parse a `[section]` header line, returning the trimmed section name
+pub fn section_header(mut input: &str) -> ParserResult<'_, String, ParserError> {
+    try_parse!(ignore("[", &mut input));
+    let (name, mut s) = try_parse!(take_while(
+        "section name",
+        |c| !matches!(c, ']' | '\r' | '\n'),
+        input
+    ));
+    try_parse!(ignore(("]", space0), &mut s));
+    try_parse!(ignore(comment.optional(), &mut s));
+    let (_, s) = try_parse!(line_end(s));
+    ParserResult::from_val(name.trim().to_string(), s)
+}
+
+/// Parse a property value, joining lines ending with a `\` continuation
+pub fn value(mut input: &str) -> ParserResult<'_, String, ParserError> {
+    let mut value = String::new();
+    loop {
+        let (line, s) = try_parse!(not_line_ending(input));
+        let (_, s) = try_parse!(line_end(s));
+        input = s;
+        match line.trim_end().strip_suffix('\\') {
+            Some(line) if !input.is_empty() => {
+                value.push_str(line);
+                try_parse!(ignore(space0, &mut input));
+            }
+            _ => {
+                value.push_str(line.trim_end());
+                break;
+            }
+        }
+    }
+    ParserResult::from_val(value, input)
+}
+
+/// Parse a `key = value` property line
+pub fn property(input: &str) -> ParserResult<'_, (String, String), ParserError> {
+    let (key, mut s) = try_parse!(take_while(
+        "key",
+        |c| !matches!(c, '=' | '\r' | '\n'),
+        input
+    ));
+    try_parse!(ignore("=", &mut s));
+    try_parse!(ignore(space0, &mut s));
+    let (value, s) = try_parse!(value(s));
+    ParserResult::from_val((key.trim().to_string(), value), s)
+}
+
+/// Parse an entire INI document into its sections
+pub fn parse_ini(mut input: &str) -> ParserResult<'_, Ini, ParserError> {
+    let mut ini = Ini::new();
+    let mut section = String::new();
+    loop {
+        try_parse!(ignore(opt_whitespace, &mut input));
+        if input.is_empty() {
+            break;
+        }
+        match try_parse!(peek(input)).0 {
+            ';' | '#' => {
+                try_parse!(ignore(comment, &mut input));
+            }
+            '[' => {
+                let (name, s) = try_parse!(section_header(input));
+                ini.entry(name.clone()).or_default();
+                section = name;
+                input = s;
+            }
+            _ => {
+                let ((key, value), s) = try_parse!(property(input));
+                ini.entry(section.clone()).or_default().insert(key, value);
+                input = s;
+            }
+        }
+    }
+    ParserResult::from_val(ini, input)
+}
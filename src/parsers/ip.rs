@@ -0,0 +1,147 @@
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use crate::*;
+
+/// An address with a routing prefix length, as written in CIDR notation such as `10.0.0.0/8`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Cidr<A> {
+    pub addr: A,
+    pub prefix_len: u8,
+}
+
+/// Parse a decimal number of at most `max_len` digits without leading zeroes, up to `max`
+fn decimal<'a>(
+    name: &'static str,
+    max_len: usize,
+    max: u32,
+    input: &'a str,
+) -> ParserResult<'a, u32, ParserError> {
+    let (digits, s) = try_parse!(take_while(name, |c| c.is_ascii_digit(), input));
+    if digits.len() > max_len || (digits.len() > 1 && digits.starts_with('0')) {
+        return ParserResult::from_err(ParserError::ExpectedToken(name.into()), input);
+    }
+    match digits.parse() {
+        Ok(n) if n <= max => ParserResult::from_val(n, s),
+        _ => ParserResult::from_err(ParserError::ExpectedToken(name.into()), input),
+    }
+}
+
+/// Parse a dotted-quad IPv4 address such as `192.168.0.1`
+pub fn ipv4(input: &str) -> ParserResult<'_, Ipv4Addr, ParserError> {
+    let mut s = input;
+    let mut octets = [0; 4];
+    for (i, octet) in octets.iter_mut().enumerate() {
+        if i > 0 {
+            try_parse!(ignore(".", &mut s));
+        }
+        let (n, rest) = try_parse!(decimal("octet", 3, 255, s));
+        *octet = n as u8;
+        s = rest;
+    }
+    ParserResult::from_val(Ipv4Addr::from(octets), s)
+}
+
+fn h16(input: &str) -> ParserResult<'_, u16, ParserError> {
+    repeating(
+        cur!(matching_char <= "hex digit", |c| c.is_ascii_hexdigit()),
+        1..=4,
+        input,
+    )
+    .map(|digits| {
+        digits
+            .into_iter()
+            .fold(0, |n, d| n * 16 + d.to_digit(16).unwrap() as u16)
+    })
+}
+
+/// Parse an IPv6 address, including `::` compression and a trailing embedded IPv4 address
+pub fn ipv6(input: &str) -> ParserResult<'_, Ipv6Addr, ParserError> {
+    let mut head = vec![];
+    // The groups after a `::`, if one was found
+    let mut tail: Option<Vec<u16>> = None;
+    let mut s = input;
+    if let Some(rest) = s.strip_prefix("::") {
+        tail = Some(vec![]);
+        s = rest;
+    }
+    loop {
+        let total = head.len() + tail.as_ref().map_or(0, Vec::len);
+        let groups = tail.as_mut().unwrap_or(&mut head);
+        let embedded = ipv4(s);
+        if total <= 6 && embedded.is_ok() {
+            s = embedded.source;
+            let [a, b, c, d] = embedded.unwrap().octets();
+            groups.extend([u16::from_be_bytes([a, b]), u16::from_be_bytes([c, d])]);
+            break;
+        }
+        let group = h16(s);
+        if !group.is_ok() {
+            // A `::` may end the address, but a single `:` must be followed by a group
+            if input[..input.len() - s.len()].ends_with("::") {
+                break;
+            }
+            return group.map(|_| unreachable!());
+        }
+        s = group.source;
+        groups.push(group.unwrap());
+        if total + 1 == 8 {
+            break;
+        }
+        match s.strip_prefix("::") {
+            Some(rest) if tail.is_none() => {
+                tail = Some(vec![]);
+                s = rest;
+            }
+            _ => match s.strip_prefix(':') {
+                Some(rest) if !rest.starts_with(':') => s = rest,
+                _ => break,
+            },
+        }
+    }
+    let mut addr = [0; 8];
+    match tail {
+        Some(tail) if head.len() + tail.len() < 8 => {
+            addr[..head.len()].copy_from_slice(&head);
+            addr[8 - tail.len()..].copy_from_slice(&tail);
+        }
+        None if head.len() == 8 => addr.copy_from_slice(&head),
+        _ => {
+            return ParserResult::from_err(ParserError::ExpectedToken("IPv6 address".into()), input)
+        }
+    }
+    ParserResult::from_val(Ipv6Addr::from(addr), s)
+}
+
+/// Parse either an IPv4 or an IPv6 address
+pub fn ip(input: &str) -> ParserResult<'_, IpAddr, ParserError> {
+    ipv4(input)
+        .map(IpAddr::V4)
+        .or(|s| ipv6(s).map(IpAddr::V6), input)
+}
+
+fn prefix_len(max: u32, input: &str) -> ParserResult<'_, u8, ParserError> {
+    let (_, s) = try_parse!(literal("/", input));
+    decimal("prefix length", 3, max, s).map(|n| n as u8)
+}
+
+/// Parse an IPv4 network in CIDR notation such as `10.0.0.0/8`
+pub fn ipv4_cidr(input: &str) -> ParserResult<'_, Cidr<Ipv4Addr>, ParserError> {
+    let (addr, s) = try_parse!(ipv4(input));
+    let (prefix_len, s) = try_parse!(prefix_len(32, s));
+    ParserResult::from_val(Cidr { addr, prefix_len }, s)
+}
+
+/// Parse an IPv6 network in CIDR notation such as `2001:db8::/32`
+pub fn ipv6_cidr(input: &str) -> ParserResult<'_, Cidr<Ipv6Addr>, ParserError> {
+    let (addr, s) = try_parse!(ipv6(input));
+    let (prefix_len, s) = try_parse!(prefix_len(128, s));
+    ParserResult::from_val(Cidr { addr, prefix_len }, s)
+}
+
+/// Parse either an IPv4 or an IPv6 network in CIDR notation
+pub fn ip_cidr(input: &str) -> ParserResult<'_, Cidr<IpAddr>, ParserError> {
+    let (addr, s) = try_parse!(ip(input));
+    let max = if addr.is_ipv4() { 32 } else { 128 };
+    let (prefix_len, s) = try_parse!(prefix_len(max, s));
+    ParserResult::from_val(Cidr { addr, prefix_len }, s)
+}
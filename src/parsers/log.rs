@@ -0,0 +1,330 @@
+use super::datetime::{
+    days_in_month, offset, ranged, rfc3339, time, Date, DateTime, DateTimeError,
+};
+use crate::{error::ParserError, try_parse, ParserResult};
+
+type Result<'a, T> = ParserResult<'a, T, DateTimeError>;
+
+/// A structured data element of a syslog message, such as `[exampleSDID@32473 iut="3"]`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SdElement {
+    pub id: String,
+    pub params: Vec<(String, String)>,
+}
+
+/// An RFC 5424 syslog message, where fields written as `-` are `None`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyslogMessage {
+    pub facility: u8,
+    pub severity: u8,
+    pub version: u16,
+    pub timestamp: Option<DateTime>,
+    pub hostname: Option<String>,
+    pub app_name: Option<String>,
+    pub proc_id: Option<String>,
+    pub msg_id: Option<String>,
+    pub structured_data: Vec<SdElement>,
+    /// The free-form message, without the byte order mark which marks it as UTF-8
+    pub message: Option<String>,
+}
+
+/// A line of the Common Log Format, or of the Combined Log Format if it has a referer and user agent, as written by
+/// Apache and nginx
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessLogEntry {
+    pub host: String,
+    pub ident: Option<String>,
+    pub user: Option<String>,
+    pub timestamp: DateTime,
+    /// The request line, such as `GET /index.html HTTP/1.1`, with its escapes left in place
+    pub request: String,
+    pub status: u16,
+    /// The size of the response body, which is `None` when written as `-`
+    pub bytes: Option<u64>,
+    pub referer: Option<String>,
+    pub user_agent: Option<String>,
+}
+
+/// Parse a field up to the next space, returning `None` for `-`
+fn field(input: &str) -> Result<'_, Option<String>> {
+    let len = input.find([' ', '\n', '\r']).unwrap_or(input.len());
+    if len == 0 {
+        return ParserResult::from_err(ParserError::ExpectedToken("field".into()).into(), input);
+    }
+    let value = Some(&input[..len]).filter(|v| *v != "-").map(String::from);
+    ParserResult::from_val(value, &input[len..])
+}
+
+/// Parse a single space
+fn space(input: &str) -> Result<'_, ()> {
+    match input.strip_prefix(' ') {
+        Some(rest) => ParserResult::from_val((), rest),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral(" ").into(), input),
+    }
+}
+
+/// Parse the rest of the line
+fn rest_of_line(input: &str) -> (&str, &str) {
+    let len = input.find(['\n', '\r']).unwrap_or(input.len());
+    input.split_at(len)
+}
+
+/// Parse a decimal number of at most `max` digits
+fn number(max: usize, input: &str) -> Result<'_, u64> {
+    let len = input
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(input.len());
+    if len == 0 || len > max {
+        return ParserResult::from_err(ParserError::ExpectedToken("number".into()).into(), input);
+    }
+    match input[..len].parse() {
+        Ok(n) => ParserResult::from_val(n, &input[len..]),
+        Err(_) => ParserResult::from_err(ParserError::IntegerOverflow.into(), input),
+    }
+}
+
+/// Parse the `<PRI>` at the start of a syslog message into its facility and severity
+fn priority(input: &str) -> Result<'_, (u8, u8)> {
+    let Some(s) = input.strip_prefix('<') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("<").into(), input);
+    };
+    let (pri, s) = try_parse!(number(3, s));
+    if pri > 191 {
+        return ParserResult::from_err(DateTimeError::OutOfRange("priority"), input);
+    }
+    match s.strip_prefix('>') {
+        Some(s) => ParserResult::from_val(((pri / 8) as u8, (pri % 8) as u8), s),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral(">").into(), s),
+    }
+}
+
+/// Parse a structured data element in square brackets
+fn sd_element(input: &str) -> Result<'_, SdElement> {
+    let name_len = |s: &str| {
+        s.find(|c: char| matches!(c, ' ' | '=' | ']' | '"') || !c.is_ascii_graphic())
+            .unwrap_or(s.len())
+    };
+    let Some(s) = input.strip_prefix('[') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("[").into(), input);
+    };
+    let len = name_len(s);
+    if len == 0 {
+        return ParserResult::from_err(ParserError::ExpectedToken("SD-ID".into()).into(), s);
+    }
+    let (id, mut s) = s.split_at(len);
+    let mut params = vec![];
+    while let Some(rest) = s.strip_prefix(' ') {
+        let len = name_len(rest);
+        let (name, rest) = rest.split_at(len);
+        let Some(rest) = rest.strip_prefix("=\"").filter(|_| len > 0) else {
+            return ParserResult::from_err(
+                ParserError::ExpectedToken("SD-PARAM".into()).into(),
+                rest,
+            );
+        };
+        // A backslash escapes `"`, `\` and `]`, and is kept before anything else
+        let mut value = String::new();
+        let mut chars = rest.char_indices();
+        let end = loop {
+            match chars.next() {
+                Some((i, '"')) => break i,
+                Some((_, '\\')) => match chars.clone().next() {
+                    Some((_, c @ ('"' | '\\' | ']'))) => {
+                        value.push(c);
+                        chars.next();
+                    }
+                    _ => value.push('\\'),
+                },
+                Some((_, c)) => value.push(c),
+                None => {
+                    return ParserResult::from_err(ParserError::ExpectedLiteral("\"").into(), rest)
+                }
+            }
+        };
+        params.push((name.to_string(), value));
+        s = &rest[end + 1..];
+    }
+    match s.strip_prefix(']') {
+        Some(s) => ParserResult::from_val(
+            SdElement {
+                id: id.to_string(),
+                params,
+            },
+            s,
+        ),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral("]").into(), s),
+    }
+}
+
+/// Parse an RFC 5424 syslog message, up to the end of its line
+pub fn syslog(input: &str) -> Result<'_, SyslogMessage> {
+    let ((facility, severity), s) = try_parse!(priority(input));
+    let (version, s) = try_parse!(number(3, s));
+    if version == 0 {
+        return ParserResult::from_err(DateTimeError::OutOfRange("version"), s);
+    }
+    let (_, s) = try_parse!(space(s));
+    let (timestamp, s) = match s.strip_prefix('-') {
+        Some(rest) => (None, rest),
+        None => {
+            let (timestamp, rest) = try_parse!(rfc3339(s));
+            (Some(timestamp), rest)
+        }
+    };
+    let mut fields = [None, None, None, None];
+    let mut s = s;
+    for field_value in &mut fields {
+        let (_, rest) = try_parse!(space(s));
+        let (value, rest) = try_parse!(field(rest));
+        *field_value = value;
+        s = rest;
+    }
+    let [hostname, app_name, proc_id, msg_id] = fields;
+    let (_, mut s) = try_parse!(space(s));
+    let mut structured_data = vec![];
+    match s.strip_prefix('-') {
+        Some(rest) => s = rest,
+        None => loop {
+            let (element, rest) = try_parse!(sd_element(s));
+            structured_data.push(element);
+            s = rest;
+            if !s.starts_with('[') {
+                break;
+            }
+        },
+    }
+    let (message, s) = match s.strip_prefix(' ') {
+        Some(rest) => {
+            let (message, rest) = rest_of_line(rest);
+            let message = message.strip_prefix('\u{FEFF}').unwrap_or(message);
+            (Some(message.to_string()), rest)
+        }
+        None => (None, s),
+    };
+    let message = SyslogMessage {
+        facility,
+        severity,
+        version: version as u16,
+        timestamp,
+        hostname,
+        app_name,
+        proc_id,
+        msg_id,
+        structured_data,
+        message,
+    };
+    ParserResult::from_val(message, s)
+}
+
+/// Parse a timestamp such as `[10/Oct/2000:13:55:36 -0700]`
+fn access_log_time(input: &str) -> Result<'_, DateTime> {
+    const MONTHS: [&str; 12] = [
+        "Jan", "Feb", "Mar", "Apr", "May", "Jun", "Jul", "Aug", "Sep", "Oct", "Nov", "Dec",
+    ];
+    let Some(s) = input.strip_prefix('[') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("[").into(), input);
+    };
+    let (day, s) = try_parse!(ranged("day", 2, 1..=31, s));
+    let month = s
+        .strip_prefix('/')
+        .and_then(|s| MONTHS.iter().position(|m| s.starts_with(m)));
+    let Some(month) = month else {
+        return ParserResult::from_err(ParserError::ExpectedToken("month".into()).into(), s);
+    };
+    let s = &s[4..];
+    let Some(s) = s.strip_prefix('/') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("/").into(), s);
+    };
+    let (year, s) = try_parse!(ranged("year", 4, 0..=9999, s));
+    let date = Date {
+        year: year as u16,
+        month: month as u8 + 1,
+        day: day as u8,
+    };
+    if date.day > days_in_month(date.year, date.month) {
+        return ParserResult::from_err(DateTimeError::OutOfRange("day"), input);
+    }
+    let Some(s) = s.strip_prefix(':') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral(":").into(), s);
+    };
+    let (time, s) = try_parse!(time(s));
+    let (_, s) = try_parse!(space(s));
+    let (offset, s) = try_parse!(offset(s));
+    let Some(s) = s.strip_prefix(']') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("]").into(), s);
+    };
+    let datetime = DateTime {
+        date,
+        time,
+        offset: Some(offset),
+    };
+    ParserResult::from_val(datetime, s)
+}
+
+/// Parse a field in double quotes, where a backslash escapes the next character
+fn quoted(input: &str) -> Result<'_, &str> {
+    let Some(s) = input.strip_prefix('"') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("\"").into(), input);
+    };
+    let mut escaped = false;
+    for (i, c) in s.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' => escaped = true,
+            '"' => {
+                return ParserResult::from_val(&s[..i], &s[i + 1..]);
+            }
+            '\n' | '\r' => break,
+            _ => {}
+        }
+    }
+    ParserResult::from_err(ParserError::ExpectedLiteral("\"").into(), input)
+}
+
+/// Parse a line of the Common or Combined Log Format, up to the end of the line
+pub fn access_log(input: &str) -> Result<'_, AccessLogEntry> {
+    let (host, s) = try_parse!(field(input));
+    let Some(host) = host else {
+        return ParserResult::from_err(ParserError::ExpectedToken("host".into()).into(), input);
+    };
+    let (_, s) = try_parse!(space(s));
+    let (ident, s) = try_parse!(field(s));
+    let (_, s) = try_parse!(space(s));
+    let (user, s) = try_parse!(field(s));
+    let (_, s) = try_parse!(space(s));
+    let (timestamp, s) = try_parse!(access_log_time(s));
+    let (_, s) = try_parse!(space(s));
+    let (request, s) = try_parse!(quoted(s));
+    let (_, s) = try_parse!(space(s));
+    let (status, s) = try_parse!(number(3, s));
+    let (_, s) = try_parse!(space(s));
+    let (bytes, mut s) = match s.strip_prefix('-') {
+        Some(rest) => (None, rest),
+        None => {
+            let (bytes, rest) = try_parse!(number(20, s));
+            (Some(bytes), rest)
+        }
+    };
+    let (mut referer, mut user_agent) = (None, None);
+    if s.starts_with(" \"") {
+        let optional = |value: &str| Some(value).filter(|v| *v != "-").map(String::from);
+        let (parsed_referer, rest) = try_parse!(quoted(&s[1..]));
+        let (_, rest) = try_parse!(space(rest));
+        let (parsed_user_agent, rest) = try_parse!(quoted(rest));
+        referer = optional(parsed_referer);
+        user_agent = optional(parsed_user_agent);
+        s = rest;
+    }
+    let entry = AccessLogEntry {
+        host,
+        ident,
+        user,
+        timestamp,
+        request: request.to_string(),
+        status: status as u16,
+        bytes,
+        referer,
+        user_agent,
+    };
+    ParserResult::from_val(entry, s)
+}
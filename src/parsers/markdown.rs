@@ -0,0 +1,296 @@
+use std::{
+    borrow::Cow,
+    cell::{Cell, RefCell},
+    collections::HashSet,
+};
+
+use crate::{error::ParserError, literal, try_parse, ParserResult};
+
+/// An inline Markdown element
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Inline<'a> {
+    /// Plain text, with backslash escapes removed
+    Text(Cow<'a, str>),
+    /// A code span such as `` `x` ``, with the single space padding either side removed
+    Code(&'a str),
+    /// `*text*` or `_text_`
+    Emphasis(Vec<Inline<'a>>),
+    /// `**text**` or `__text__`
+    Strong(Vec<Inline<'a>>),
+    /// `[text](url "title")`
+    Link {
+        text: Vec<Inline<'a>>,
+        url: &'a str,
+        title: Option<&'a str>,
+    },
+    /// `<https://example.com>` or `<user@example.com>`
+    Autolink(&'a str),
+}
+
+fn push_text<'a>(out: &mut Vec<Inline<'a>>, text: &'a str) {
+    match out.last_mut() {
+        Some(Inline::Text(prev)) => prev.to_mut().push_str(text),
+        _ => out.push(Inline::Text(text.into())),
+    }
+}
+
+/// The input being parsed, along with the offsets where a link or emphasis was already found not to start
+///
+/// Those elements depend only on where they start, so remembering failures stops unclosed delimiters from being
+/// retried from every enclosing element, which would take exponential time.
+struct Context<'a> {
+    full: &'a str,
+    failed: RefCell<HashSet<usize>>,
+    depth: Cell<usize>,
+}
+
+/// The deepest that links and emphasis can be nested, beyond which their delimiters are kept as text
+pub const MAX_NESTING: usize = 64;
+
+/// The character before `s` in the full input
+fn prev_char(full: &str, s: &str) -> Option<char> {
+    full[..full.len() - s.len()].chars().next_back()
+}
+
+/// Check whether a run of `len` delimiter characters at the start of `s` can open emphasis, meaning it is followed by
+/// a non-whitespace character and, for `_`, isn't inside a word
+fn can_open(full: &str, s: &str, len: usize) -> bool {
+    let next_ok = s[len..].starts_with(|c: char| !c.is_whitespace());
+    let intraword = s.starts_with('_') && prev_char(full, s).is_some_and(char::is_alphanumeric);
+    next_ok && !intraword
+}
+
+/// Check whether a closing delimiter at the start of `s` can close emphasis, meaning it follows a non-whitespace
+/// character and, for `_`, isn't inside a word
+fn can_close(full: &str, s: &str, close: &str) -> bool {
+    if !close.starts_with(['*', '_']) {
+        return true;
+    }
+    let prev_ok = prev_char(full, s).is_some_and(|c| !c.is_whitespace());
+    let intraword = close.starts_with('_') && s[close.len()..].starts_with(char::is_alphanumeric);
+    prev_ok && !intraword
+}
+
+/// Parse a code span delimited by matching runs of backticks, such as ``` `` a ` b `` ```
+pub fn code_span(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let len = input.len() - input.trim_start_matches('`').len();
+    if len == 0 {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("`"), input);
+    }
+    let body = &input[len..];
+    let mut i = 0;
+    while let Some(start) = body[i..].find('`').map(|start| i + start) {
+        let run = body[start..].len() - body[start..].trim_start_matches('`').len();
+        if run == len {
+            let code = &body[..start];
+            let code = match code.strip_prefix(' ').and_then(|c| c.strip_suffix(' ')) {
+                Some(stripped) if !code.trim_start_matches(' ').is_empty() => stripped,
+                _ => code,
+            };
+            return ParserResult::from_val(code, &body[start + run..]);
+        }
+        i = start + run;
+    }
+    ParserResult::from_err(
+        ParserError::ExpectedToken("closing backticks".into()),
+        input,
+    )
+}
+
+/// Parse an autolink such as `<https://example.com>` or `<user@example.com>`, returning its destination
+pub fn autolink(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let (_, s) = try_parse!(literal("<", input));
+    let Some(end) = s
+        .find(['>', '<', ' ', '\n'])
+        .filter(|&end| s[end..].starts_with('>'))
+    else {
+        return ParserResult::from_err(ParserError::ExpectedToken("autolink".into()), input);
+    };
+    let dest = &s[..end];
+    let is_uri = dest.split_once(':').is_some_and(|(scheme, _)| {
+        (2..=32).contains(&scheme.len())
+            && scheme.starts_with(|c: char| c.is_ascii_alphabetic())
+            && scheme
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '+' | '.' | '-'))
+    });
+    let is_email = dest.split_once('@').is_some_and(|(local, domain)| {
+        !local.is_empty()
+            && !domain.is_empty()
+            && local
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || ".!#$%&'*+/=?^_`{|}~-".contains(c))
+            && domain
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '-'))
+    });
+    if !is_uri && !is_email {
+        return ParserResult::from_err(ParserError::ExpectedToken("autolink".into()), input);
+    }
+    ParserResult::from_val(dest, &s[end + 1..])
+}
+
+/// Parse a link destination, either in angle brackets or up to whitespace or an unbalanced `)`
+fn destination(input: &str) -> ParserResult<'_, &str, ParserError> {
+    if let Some(s) = input.strip_prefix('<') {
+        return match s.find(['>', '\n']).filter(|&end| s[end..].starts_with('>')) {
+            Some(end) => ParserResult::from_val(&s[..end], &s[end + 1..]),
+            None => ParserResult::from_err(ParserError::ExpectedLiteral(">"), input),
+        };
+    }
+    let mut depth = 0usize;
+    let mut end = input.len();
+    for (i, c) in input.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' if depth == 0 => {
+                end = i;
+                break;
+            }
+            ')' => depth -= 1,
+            c if c.is_whitespace() => {
+                end = i;
+                break;
+            }
+            _ => {}
+        }
+    }
+    ParserResult::from_val(&input[..end], &input[end..])
+}
+
+/// Parse a link title in double quotes, single quotes or parentheses
+fn title(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let close = match input.chars().next() {
+        Some('"') => '"',
+        Some('\'') => '\'',
+        Some('(') => ')',
+        _ => return ParserResult::from_err(ParserError::ExpectedToken("link title".into()), input),
+    };
+    match input[1..].find(close) {
+        Some(end) => ParserResult::from_val(&input[1..end + 1], &input[end + 2..]),
+        None => ParserResult::from_err(ParserError::ExpectedToken("link title".into()), input),
+    }
+}
+
+/// Parse a `[text](url "title")` link
+fn link<'a>(cx: &Context<'a>, input: &'a str) -> ParserResult<'a, Inline<'a>, ParserError> {
+    let (_, s) = try_parse!(literal("[", input));
+    let (text, s) = try_parse!(sequence(cx, Some("]"), s));
+    let (_, s) = try_parse!(literal("](", s));
+    let s = s.trim_start();
+    let (url, s) = try_parse!(destination(s));
+    let trimmed = s.trim_start();
+    let (title, s) = match title(trimmed).into_result::<Inline>() {
+        Ok((title, rest)) if trimmed.len() < s.len() => (Some(title), rest),
+        _ => (None, s),
+    };
+    let (_, s) = try_parse!(literal(")", s.trim_start()));
+    ParserResult::from_val(Inline::Link { text, url, title }, s)
+}
+
+/// Parse emphasis or strong emphasis, preferring strong emphasis when the delimiter is doubled
+fn emphasis<'a>(cx: &Context<'a>, input: &'a str) -> ParserResult<'a, Inline<'a>, ParserError> {
+    let (delim, strong): (&'static str, &'static str) = if input.starts_with('*') {
+        ("*", "**")
+    } else {
+        ("_", "__")
+    };
+    for close in [strong, delim] {
+        if !input.starts_with(close) || !can_open(cx.full, input, close.len()) {
+            continue;
+        }
+        let Ok((nodes, s)) = sequence(cx, Some(close), &input[close.len()..]).into_result::<()>()
+        else {
+            continue;
+        };
+        if nodes.is_empty() {
+            continue;
+        }
+        let node = if close.len() == 2 {
+            Inline::Strong(nodes)
+        } else {
+            Inline::Emphasis(nodes)
+        };
+        return ParserResult::from_val(node, &s[close.len()..]);
+    }
+    ParserResult::from_err(ParserError::ExpectedToken("emphasis".into()), input)
+}
+
+/// Parse a link or emphasis, failing immediately where one has already failed to parse
+fn element<'a>(cx: &Context<'a>, s: &'a str) -> ParserResult<'a, Inline<'a>, ParserError> {
+    let offset = cx.full.len() - s.len();
+    if cx.depth.get() >= MAX_NESTING || cx.failed.borrow().contains(&offset) {
+        return ParserResult::from_err(ParserError::ExpectedToken("inline element".into()), s);
+    }
+    cx.depth.set(cx.depth.get() + 1);
+    let parsed = if s.starts_with('[') {
+        link(cx, s)
+    } else {
+        emphasis(cx, s)
+    };
+    cx.depth.set(cx.depth.get() - 1);
+    if !parsed.is_ok() {
+        cx.failed.borrow_mut().insert(offset);
+    }
+    parsed
+}
+
+/// Parse inline elements up to the end of the input or, if given, a closing delimiter which is left unconsumed
+fn sequence<'a>(
+    cx: &Context<'a>,
+    close: Option<&'static str>,
+    mut s: &'a str,
+) -> ParserResult<'a, Vec<Inline<'a>>, ParserError> {
+    let mut out = vec![];
+    loop {
+        if let Some(close) = close {
+            if s.starts_with(close) && can_close(cx.full, s, close) {
+                let strong = close.len() == 1 && s[1..].starts_with(close);
+                if !strong || !element(cx, s).is_ok() {
+                    return ParserResult::from_val(out, s);
+                }
+            }
+        }
+        let Some(c) = s.chars().next() else {
+            return match close {
+                Some(close) => ParserResult::from_err(ParserError::ExpectedLiteral(close), s),
+                None => ParserResult::from_val(out, s),
+            };
+        };
+        let parsed = match c {
+            '`' => code_span(s).map(Inline::Code),
+            '*' | '_' | '[' => element(cx, s),
+            '<' => autolink(s).map(Inline::Autolink),
+            _ => ParserResult::from_err(ParserError::ExpectedToken("inline element".into()), s),
+        };
+        if let Ok((node, rest)) = parsed.into_result::<()>() {
+            out.push(node);
+            s = rest;
+            continue;
+        }
+        let len = match c {
+            '\\' if s[1..].starts_with(|c: char| c.is_ascii_punctuation()) => {
+                push_text(&mut out, &s[1..2]);
+                s = &s[2..];
+                continue;
+            }
+            '`' => s.len() - s.trim_start_matches('`').len(),
+            '\\' | '*' | '_' | '[' | ']' | '<' => 1,
+            _ => s
+                .find(['\\', '`', '*', '_', '[', ']', '<'])
+                .unwrap_or(s.len()),
+        };
+        push_text(&mut out, &s[..len]);
+        s = &s[len..];
+    }
+}
+
+/// Parse a run of inline Markdown, where any delimiter which doesn't form an element is kept as text
+pub fn inlines(input: &str) -> ParserResult<'_, Vec<Inline<'_>>, ParserError> {
+    let cx = Context {
+        full: input,
+        failed: RefCell::default(),
+        depth: Cell::new(0),
+    };
+    sequence(&cx, None, input)
+}
@@ -0,0 +1,162 @@
+use std::{borrow::Cow, fmt::Display};
+
+use crate::{error::ParserError, literal, take_while, try_parse, ParserResult};
+
+/// A `name=value` parameter of a media type or header value, such as `charset=utf-8`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Parameter<'a> {
+    pub name: &'a str,
+    /// The value, which is only owned if it was a quoted string containing escapes
+    pub value: Cow<'a, str>,
+}
+
+/// A MIME type such as `text/html; charset=utf-8`, as used by `Content-Type` and file type detection
+#[derive(Debug, Clone, PartialEq)]
+pub struct MediaType<'a> {
+    pub typ: &'a str,
+    pub subtype: &'a str,
+    pub params: Vec<Parameter<'a>>,
+}
+
+impl<'a> MediaType<'a> {
+    /// Get the value of the first parameter with the given name, ignoring case
+    pub fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|p| p.name.eq_ignore_ascii_case(name))
+            .map(|p| p.value.as_ref())
+    }
+
+    /// Returns whether this media type has the given type and subtype, ignoring case and parameters
+    pub fn is(&self, typ: &str, subtype: &str) -> bool {
+        self.typ.eq_ignore_ascii_case(typ) && self.subtype.eq_ignore_ascii_case(subtype)
+    }
+
+    /// Get the type and subtype without parameters in lowercase, such as `text/html`
+    pub fn essence(&self) -> String {
+        format!("{}/{}", self.typ, self.subtype).to_ascii_lowercase()
+    }
+}
+
+/// Writes parameter values as tokens where possible, quoting and escaping them otherwise
+impl Display for MediaType<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}/{}", self.typ, self.subtype)?;
+        for Parameter { name, value } in &self.params {
+            write!(f, ";{name}=")?;
+            if !value.is_empty() && value.chars().all(is_tchar) {
+                write!(f, "{value}")?;
+                continue;
+            }
+            write!(f, "\"")?;
+            for c in value.chars() {
+                if c == '"' || c == '\\' {
+                    write!(f, "\\")?;
+                }
+                write!(f, "{c}")?;
+            }
+            write!(f, "\"")?;
+        }
+        Ok(())
+    }
+}
+
+fn is_tchar(c: char) -> bool {
+    c.is_ascii_alphanumeric() || "!#$%&'*+-.^_`|~".contains(c)
+}
+
+/// Skip optional spaces and tabs
+pub(crate) fn skip_ows(input: &str) -> &str {
+    input.trim_start_matches([' ', '\t'])
+}
+
+/// Parse a token, such as a type, subtype or parameter name
+pub fn token(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("token", is_tchar, input)
+}
+
+fn is_qdtext(c: char) -> bool {
+    matches!(c, '\t' | ' ' | '!' | '#'..='[' | ']'..='~') || !c.is_ascii()
+}
+
+/// Parse a quoted string such as `"a \"quoted\" value"`, borrowing its contents unless it contains escapes
+pub fn quoted_string(input: &str) -> ParserResult<'_, Cow<'_, str>, ParserError> {
+    let (_, s) = try_parse!(literal("\"", input));
+    let mut value = Cow::Borrowed("");
+    let mut start = 0;
+    let mut chars = s.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => {
+                match &mut value {
+                    Cow::Borrowed(_) => value = Cow::Borrowed(&s[..i]),
+                    Cow::Owned(value) => value.push_str(&s[start..i]),
+                }
+                return ParserResult::from_val(value, &s[i + 1..]);
+            }
+            '\\' => {
+                value.to_mut().push_str(&s[start..i]);
+                match chars.next() {
+                    Some((_, c))
+                        if c == '\t' || c == ' ' || c.is_ascii_graphic() || !c.is_ascii() =>
+                    {
+                        value.to_mut().push(c);
+                        start = chars.offset();
+                    }
+                    _ => {
+                        return ParserResult::from_err(
+                            ParserError::ExpectedToken("quoted character".into()),
+                            &s[i + 1..],
+                        )
+                    }
+                }
+            }
+            c if is_qdtext(c) => {}
+            _ => return ParserResult::from_err(ParserError::ExpectedLiteral("\""), &s[i..]),
+        }
+    }
+    ParserResult::from_err(ParserError::ExpectedLiteral("\""), &s[s.len()..])
+}
+
+/// Parse a token or a quoted string, such as the value of a parameter
+pub fn token_or_quoted(input: &str) -> ParserResult<'_, Cow<'_, str>, ParserError> {
+    if input.starts_with('"') {
+        quoted_string(input)
+    } else {
+        token(input).map(Cow::Borrowed)
+    }
+}
+
+/// Parse any number of `;`-separated parameters such as `; charset=utf-8; q=0.5`, ignoring empty ones
+pub fn parameters(mut input: &str) -> ParserResult<'_, Vec<Parameter<'_>>, ParserError> {
+    let mut params = vec![];
+    while let Some(s) = skip_ows(input).strip_prefix(';') {
+        let s = skip_ows(s);
+        input = s;
+        if s.is_empty() || s.starts_with([';', ',']) {
+            continue;
+        }
+        let (name, s) = try_parse!(token(s));
+        let (_, s) = try_parse!(literal("=", s));
+        let (value, s) = try_parse!(token_or_quoted(s));
+        params.push(Parameter { name, value });
+        input = s;
+    }
+    ParserResult::from_val(params, input)
+}
+
+/// Parse a media type such as `text/html; charset=utf-8`
+pub fn media_type(input: &str) -> ParserResult<'_, MediaType<'_>, ParserError> {
+    let (typ, s) = try_parse!(token(input));
+    let (_, s) = try_parse!(literal("/", s));
+    let (subtype, s) = try_parse!(token(s));
+    let (params, s) = try_parse!(parameters(s));
+    ParserResult::from_val(
+        MediaType {
+            typ,
+            subtype,
+            params,
+        },
+        s,
+    )
+}
@@ -0,0 +1,186 @@
+use crate::{
+    binary::{
+        be_f32, be_f64, be_i16, be_i32, be_i64, be_u16, be_u32, be_u64, take, try_binary, u8,
+        BinaryResult,
+    },
+    error::ParserError,
+};
+
+/// How many arrays and maps can be nested inside each other before the input is rejected
+const MAX_DEPTH: usize = 128;
+
+/// A decoded MessagePack value, borrowing strings and binary data from the input
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value<'a> {
+    Nil,
+    Bool(bool),
+    /// An integer which fits in an `i64`, whichever encoding it used
+    Int(i64),
+    /// An unsigned integer too large for an `i64`
+    UInt(u64),
+    F32(f32),
+    F64(f64),
+    Str(&'a str),
+    Bin(&'a [u8]),
+    Array(Vec<Value<'a>>),
+    /// The entries of a map in the order they were encoded, since keys can be of any type
+    Map(Vec<(Value<'a>, Value<'a>)>),
+    /// An extension type and its data, such as type -1 for timestamps
+    Ext(i8, &'a [u8]),
+}
+
+impl Value<'_> {
+    /// Get the value as an `i64` if it is an integer which fits
+    pub fn as_i64(&self) -> Option<i64> {
+        match *self {
+            Value::Int(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Get the value as a `u64` if it is a non-negative integer
+    pub fn as_u64(&self) -> Option<u64> {
+        match *self {
+            Value::Int(n) => u64::try_from(n).ok(),
+            Value::UInt(n) => Some(n),
+            _ => None,
+        }
+    }
+
+    /// Get the value of a map entry whose key is the given string
+    pub fn get(&self, key: &str) -> Option<&Self> {
+        match self {
+            Value::Map(entries) => entries
+                .iter()
+                .find(|(k, _)| *k == Value::Str(key))
+                .map(|(_, v)| v),
+            _ => None,
+        }
+    }
+}
+
+/// Parse a length of the given number of bytes
+fn length<E: From<ParserError>>(bytes: usize, input: &[u8]) -> BinaryResult<'_, usize, E> {
+    match bytes {
+        1 => u8(input).map(usize::from),
+        2 => be_u16(input).map(usize::from),
+        _ => be_u32(input).map(|n| n as usize),
+    }
+}
+
+/// Decode a string of `len` bytes
+fn str<E: From<ParserError>>(len: usize, input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    let (bytes, s) = try_binary!(take::<E>(len, input));
+    match std::str::from_utf8(bytes) {
+        Ok(str) => BinaryResult::from_val(Value::Str(str), s),
+        Err(_) => BinaryResult::from_err(
+            ParserError::ExpectedToken("UTF-8 string".into()).into(),
+            input,
+        ),
+    }
+}
+
+/// Decode `len` values into an array
+fn array<E: From<ParserError>>(
+    len: usize,
+    depth: usize,
+    mut input: &[u8],
+) -> BinaryResult<'_, Value<'_>, E> {
+    // Every value takes at least a byte, so a length longer than the input can't be trusted for the capacity
+    let mut values = Vec::with_capacity(len.min(input.len()));
+    for _ in 0..len {
+        let (value, s) = try_binary!(decode_at::<E>(depth, input));
+        values.push(value);
+        input = s;
+    }
+    BinaryResult::from_val(Value::Array(values), input)
+}
+
+/// Decode `len` key-value pairs into a map
+fn map<E: From<ParserError>>(
+    len: usize,
+    depth: usize,
+    mut input: &[u8],
+) -> BinaryResult<'_, Value<'_>, E> {
+    let mut entries = Vec::with_capacity(len.min(input.len() / 2));
+    for _ in 0..len {
+        let (key, s) = try_binary!(decode_at::<E>(depth, input));
+        let (value, s) = try_binary!(decode_at::<E>(depth, s));
+        entries.push((key, value));
+        input = s;
+    }
+    BinaryResult::from_val(Value::Map(entries), input)
+}
+
+/// Decode an extension with `len` bytes of data after its type
+fn ext<E: From<ParserError>>(len: usize, input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    let (typ, s) = try_binary!(u8::<E>(input));
+    take(len, s).map(|data| Value::Ext(typ as i8, data))
+}
+
+/// Decode a value nested inside `depth` arrays and maps
+fn decode_at<E: From<ParserError>>(depth: usize, input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    if depth == MAX_DEPTH {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("less deeply nested value".into()).into(),
+            input,
+        );
+    }
+    let (marker, s) = try_binary!(u8::<E>(input));
+    // The lengths of sized types follow their marker in 1, 2 or 4 bytes
+    let sized = |bytes: usize| length::<E>(bytes, s);
+    let depth = depth + 1;
+    match marker {
+        0x00..=0x7F => BinaryResult::from_val(Value::Int(i64::from(marker)), s),
+        0x80..=0x8F => map(usize::from(marker & 0x0F), depth, s),
+        0x90..=0x9F => array(usize::from(marker & 0x0F), depth, s),
+        0xA0..=0xBF => str(usize::from(marker & 0x1F), s),
+        0xC0 => BinaryResult::from_val(Value::Nil, s),
+        0xC2 => BinaryResult::from_val(Value::Bool(false), s),
+        0xC3 => BinaryResult::from_val(Value::Bool(true), s),
+        0xC4..=0xC6 => {
+            let (len, s) = try_binary!(sized(1 << (marker - 0xC4)));
+            take(len, s).map(Value::Bin)
+        }
+        0xC7..=0xC9 => {
+            let (len, s) = try_binary!(sized(1 << (marker - 0xC7)));
+            ext(len, s)
+        }
+        0xCA => be_f32(s).map(Value::F32),
+        0xCB => be_f64(s).map(Value::F64),
+        0xCC => u8(s).map(|n| Value::Int(i64::from(n))),
+        0xCD => be_u16(s).map(|n| Value::Int(i64::from(n))),
+        0xCE => be_u32(s).map(|n| Value::Int(i64::from(n))),
+        0xCF => be_u64(s).map(|n| match i64::try_from(n) {
+            Ok(n) => Value::Int(n),
+            Err(_) => Value::UInt(n),
+        }),
+        0xD0 => u8(s).map(|n| Value::Int(i64::from(n as i8))),
+        0xD1 => be_i16(s).map(|n| Value::Int(i64::from(n))),
+        0xD2 => be_i32(s).map(|n| Value::Int(i64::from(n))),
+        0xD3 => be_i64(s).map(Value::Int),
+        0xD4..=0xD8 => ext(1 << (marker - 0xD4), s),
+        0xD9..=0xDB => {
+            let (len, s) = try_binary!(sized(1 << (marker - 0xD9)));
+            str(len, s)
+        }
+        0xDC | 0xDD => {
+            let (len, s) = try_binary!(sized(2 << (marker - 0xDC)));
+            array(len, depth, s)
+        }
+        0xDE | 0xDF => {
+            let (len, s) = try_binary!(sized(2 << (marker - 0xDE)));
+            map(len, depth, s)
+        }
+        0xE0..=0xFF => BinaryResult::from_val(Value::Int(i64::from(marker as i8)), s),
+        0xC1 => BinaryResult::from_err(
+            ParserError::ExpectedToken("MessagePack marker".into()).into(),
+            input,
+        ),
+    }
+}
+
+/// Decode a single MessagePack value, which is incomplete until all of it has arrived
+pub fn decode<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Value<'_>, E> {
+    decode_at(0, input)
+}
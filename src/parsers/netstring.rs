@@ -0,0 +1,88 @@
+use crate::{
+    binary::{take, try_binary, BinaryResult},
+    error::ParserError,
+    Needed,
+};
+
+/// Parse the decimal length of a netstring and its `:`, which can't have leading zeros
+fn length<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, usize, E> {
+    let digits = input.iter().take_while(|b| b.is_ascii_digit()).count();
+    // A length this long can't fit in a usize, so don't wait for the rest of it
+    if digits > 20 {
+        return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input);
+    }
+    let Some(&next) = input.get(digits) else {
+        return BinaryResult::incomplete_needing(Needed::new(1), input);
+    };
+    if digits == 0 {
+        return BinaryResult::from_err(ParserError::ExpectedToken("length".into()).into(), input);
+    }
+    if next != b':' {
+        return BinaryResult::from_err(ParserError::ExpectedLiteral(":").into(), &input[digits..]);
+    }
+    if digits > 1 && input[0] == b'0' {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("length without leading zeros".into()).into(),
+            input,
+        );
+    }
+    let mut len = 0usize;
+    for &digit in &input[..digits] {
+        len = match len
+            .checked_mul(10)
+            .and_then(|n| n.checked_add(usize::from(digit - b'0')))
+        {
+            Some(n) => n,
+            None => return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input),
+        };
+    }
+    BinaryResult::from_val(len, &input[digits + 1..])
+}
+
+/// Parse a netstring such as `5:hello,`, returning its payload, which is incomplete until the whole payload and its
+/// trailing comma have arrived
+pub fn netstring<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, &[u8], E> {
+    let (len, s) = try_binary!(length::<E>(input));
+    let Some(total) = len.checked_add(1) else {
+        return BinaryResult::from_err(ParserError::IntegerOverflow.into(), input);
+    };
+    if s.len() < total {
+        return BinaryResult::incomplete_needing(Needed::new(total - s.len()), input);
+    }
+    let (payload, s) = try_binary!(take::<E>(len, s));
+    match s.split_first() {
+        Some((b',', rest)) => BinaryResult::from_val(payload, rest),
+        _ => BinaryResult::from_err(ParserError::ExpectedLiteral(",").into(), s),
+    }
+}
+
+/// Parse as many complete netstrings as there are, returning their payloads along with any trailing partial netstring
+/// as the remaining input
+pub fn netstrings<E: From<ParserError>>(mut input: &[u8]) -> BinaryResult<'_, Vec<&[u8]>, E> {
+    let mut payloads = vec![];
+    while !input.is_empty() {
+        let res = netstring::<E>(input);
+        if res.is_incomplete() {
+            break;
+        }
+        let (payload, rest) = try_binary!(res);
+        payloads.push(payload);
+        input = rest;
+    }
+    BinaryResult::from_val(payloads, input)
+}
+
+/// Append a payload to a buffer as a netstring
+pub fn encode_into(payload: &[u8], out: &mut Vec<u8>) {
+    out.extend_from_slice(payload.len().to_string().as_bytes());
+    out.push(b':');
+    out.extend_from_slice(payload);
+    out.push(b',');
+}
+
+/// Encode a payload as a netstring
+pub fn encode(payload: &[u8]) -> Vec<u8> {
+    let mut out = Vec::with_capacity(payload.len() + 8);
+    encode_into(payload, &mut out);
+    out
+}
@@ -0,0 +1,99 @@
+use super::base64::Base64;
+use crate::{error::ParserError, try_parse, Parser, ParserResult};
+
+/// A PEM block, such as a certificate between `-----BEGIN CERTIFICATE-----` and `-----END CERTIFICATE-----`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pem<'a> {
+    /// The label after `BEGIN`, such as `CERTIFICATE` or `PRIVATE KEY`
+    pub label: &'a str,
+    /// The RFC 1421 headers before the body, such as `Proc-Type: 4,ENCRYPTED`, with continuation lines joined
+    pub headers: Vec<(&'a str, String)>,
+    /// The decoded body, which is usually DER
+    pub contents: Vec<u8>,
+}
+
+impl Pem<'_> {
+    /// Get the value of a header, matching its name ignoring case
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(name))
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Split off the line at the start of the input, returning it without its line ending
+fn line(input: &str) -> (&str, &str) {
+    let (line, rest) = input.split_once('\n').unwrap_or((input, ""));
+    (line.strip_suffix('\r').unwrap_or(line), rest)
+}
+
+/// Parse an encapsulation boundary such as `-----BEGIN CERTIFICATE-----`, returning its label
+fn boundary<'a>(kind: &'static str, input: &'a str) -> ParserResult<'a, &'a str, ParserError> {
+    let (line, rest) = line(input);
+    let label = line
+        .trim_end()
+        .strip_prefix("-----")
+        .and_then(|s| s.strip_prefix(kind))
+        .and_then(|s| s.strip_prefix(' '))
+        .and_then(|s| s.strip_suffix("-----"));
+    match label {
+        Some(label) => ParserResult::from_val(label, rest),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral("-----"), input),
+    }
+}
+
+/// Parse the headers before a body, which end at a blank line, returning none if the body starts straight away
+fn headers(input: &str) -> ParserResult<'_, Vec<(&str, String)>, ParserError> {
+    let mut headers: Vec<(&str, String)> = vec![];
+    let mut s = input;
+    loop {
+        let (line, rest) = line(s);
+        if line.trim().is_empty() {
+            return ParserResult::from_val(headers, rest);
+        }
+        match headers.last_mut() {
+            Some((_, value)) if line.starts_with([' ', '\t']) => {
+                value.push_str(line.trim());
+            }
+            _ => match line.split_once(':') {
+                Some((name, value)) => headers.push((name.trim(), value.trim().to_string())),
+                None if headers.is_empty() => return ParserResult::from_val(headers, input),
+                None => return ParserResult::from_err(ParserError::ExpectedLiteral(":"), s),
+            },
+        }
+        s = rest;
+    }
+}
+
+/// Parse a single PEM block, whose end boundary must have the same label as its start
+pub fn pem(input: &str) -> ParserResult<'_, Pem<'_>, ParserError> {
+    let (label, s) = try_parse!(boundary("BEGIN", input));
+    let (headers, s) = try_parse!(headers(s));
+    let (contents, s) = try_parse!(Base64::standard().ignore_whitespace().parse(s));
+    let s = s.trim_start();
+    let (end, rest) = try_parse!(boundary("END", s));
+    if end != label {
+        return ParserResult::from_err(
+            ParserError::ExpectedToken(format!("-----END {label}-----").into()),
+            s,
+        );
+    }
+    let pem = Pem {
+        label,
+        headers,
+        contents,
+    };
+    ParserResult::from_val(pem, rest)
+}
+
+/// Parse every PEM block in the input, skipping any explanatory text between them
+pub fn pem_blocks(mut input: &str) -> ParserResult<'_, Vec<Pem<'_>>, ParserError> {
+    let mut blocks = vec![];
+    while let Some(start) = input.find("-----BEGIN ") {
+        let (block, s) = try_parse!(pem(&input[start..]));
+        blocks.push(block);
+        input = s;
+    }
+    ParserResult::from_val(blocks, &input[input.len()..])
+}
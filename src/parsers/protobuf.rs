@@ -0,0 +1,133 @@
+use crate::{
+    binary::{le_u32, le_u64, length_take, try_binary, uleb128_u32, uleb128_u64, BinaryResult},
+    error::ParserError,
+};
+
+/// How many groups can be nested inside each other before the input is rejected
+const MAX_GROUP_DEPTH: usize = 64;
+
+/// The wire type in the low three bits of a field's tag, which says how its value is encoded
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WireType {
+    Varint,
+    I64,
+    Len,
+    StartGroup,
+    EndGroup,
+    I32,
+}
+
+/// A field value as it appears on the wire, without a schema to say what type it is
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Value<'a> {
+    /// A varint, which may be an `int32`, `int64`, `uint32`, `uint64`, `bool`, enum, or zigzag-encoded `sint32` or
+    /// `sint64`
+    Varint(u64),
+    /// A little-endian `fixed64`, `sfixed64` or `double`
+    I64(u64),
+    /// Length-delimited bytes, which may be a string, bytes, an embedded message or a packed repeated field
+    Len(&'a [u8]),
+    /// The fields of a deprecated group
+    Group(Vec<Field<'a>>),
+    /// A little-endian `fixed32`, `sfixed32` or `float`
+    I32(u32),
+}
+
+impl<'a> Value<'a> {
+    /// Try to parse a length-delimited value as an embedded message, which may succeed by coincidence for a string
+    pub fn as_message(&self) -> Option<Vec<Field<'a>>> {
+        match self {
+            Value::Len(bytes) => message::<ParserError>(bytes).ok(),
+            _ => None,
+        }
+    }
+}
+
+/// A field of a message
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Field<'a> {
+    pub number: u32,
+    pub value: Value<'a>,
+}
+
+/// Parse a field tag into its field number and wire type
+pub fn tag<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, (u32, WireType), E> {
+    let (tag, s) = try_binary!(uleb128_u32::<E>(input));
+    let wire_type = match tag & 7 {
+        0 => WireType::Varint,
+        1 => WireType::I64,
+        2 => WireType::Len,
+        3 => WireType::StartGroup,
+        4 => WireType::EndGroup,
+        5 => WireType::I32,
+        _ => {
+            return BinaryResult::from_err(
+                ParserError::ExpectedToken("wire type".into()).into(),
+                input,
+            )
+        }
+    };
+    let number = tag >> 3;
+    if number == 0 {
+        return BinaryResult::from_err(
+            ParserError::ExpectedToken("nonzero field number".into()).into(),
+            input,
+        );
+    }
+    BinaryResult::from_val((number, wire_type), s)
+}
+
+/// Parse the value of a field after its tag, where `depth` is how many groups it is inside
+fn value<'a, E: From<ParserError>>(
+    number: u32,
+    wire_type: WireType,
+    depth: usize,
+    input: &'a [u8],
+) -> BinaryResult<'a, Value<'a>, E> {
+    match wire_type {
+        WireType::Varint => uleb128_u64(input).map(Value::Varint),
+        WireType::I64 => le_u64(input).map(Value::I64),
+        WireType::Len => length_take(uleb128_u64::<E>, input).map(Value::Len),
+        WireType::I32 => le_u32(input).map(Value::I32),
+        WireType::StartGroup if depth == MAX_GROUP_DEPTH => BinaryResult::from_err(
+            ParserError::ExpectedToken("less deeply nested group".into()).into(),
+            input,
+        ),
+        WireType::StartGroup => fields(Some(number), depth + 1, input).map(Value::Group),
+        WireType::EndGroup => {
+            BinaryResult::from_err(ParserError::ExpectedToken("field".into()).into(), input)
+        }
+    }
+}
+
+/// Parse fields until the end of the input, or until the end of the group with the given number
+fn fields<'a, E: From<ParserError>>(
+    group: Option<u32>,
+    depth: usize,
+    mut input: &'a [u8],
+) -> BinaryResult<'a, Vec<Field<'a>>, E> {
+    let mut fields = vec![];
+    loop {
+        if input.is_empty() && group.is_none() {
+            return BinaryResult::from_val(fields, input);
+        }
+        let ((number, wire_type), s) = try_binary!(tag::<E>(input));
+        if wire_type == WireType::EndGroup && group == Some(number) {
+            return BinaryResult::from_val(fields, s);
+        }
+        let (value, s) = try_binary!(value::<E>(number, wire_type, depth, s));
+        fields.push(Field { number, value });
+        input = s;
+    }
+}
+
+/// Parse a single field, including the whole of a group
+pub fn field<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Field<'_>, E> {
+    let ((number, wire_type), s) = try_binary!(tag::<E>(input));
+    value(number, wire_type, 0, s).map(|value| Field { number, value })
+}
+
+/// Parse every field of a message, which is incomplete if the message was cut off
+pub fn message<E: From<ParserError>>(input: &[u8]) -> BinaryResult<'_, Vec<Field<'_>>, E> {
+    fields(None, 0, input)
+}
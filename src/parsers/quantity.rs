@@ -0,0 +1,81 @@
+use crate::{error::ParserError, num::float, try_parse, Parser, ParserResult};
+
+/// Whether a character can be part of a unit name such as `kg`, `µs`, `%` or `°C`
+fn is_unit_char(c: char) -> bool {
+    c.is_alphabetic() || matches!(c, '%' | '°')
+}
+
+/// Parse a unit name, which must not be followed by more letters, looking it up in a table of names and the canonical
+/// units they stand for
+pub fn unit<'a, U: Clone>(
+    units: &[(&str, U)],
+    ignore_case: bool,
+    input: &'a str,
+) -> ParserResult<'a, U, ParserError> {
+    let len = input
+        .find(|c: char| !is_unit_char(c))
+        .unwrap_or(input.len());
+    let word = &input[..len];
+    let found = units.iter().find(|(name, _)| {
+        if ignore_case {
+            name.eq_ignore_ascii_case(word)
+        } else {
+            *name == word
+        }
+    });
+    match found {
+        Some((_, unit)) if len > 0 => ParserResult::from_val(unit.clone(), &input[len..]),
+        _ => ParserResult::from_err(ParserError::ExpectedToken("unit".into()), input),
+    }
+}
+
+/// A number along with the canonical unit it was written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity<U> {
+    pub value: f64,
+    pub unit: U,
+}
+
+/// A parser for a number followed by a unit, such as `12.5 kg`, `3ms`, `100 %` or `1.2e-3 m`, where the caller
+/// supplies a table of unit names and the canonical units they stand for
+#[derive(Debug, Clone, Copy)]
+pub struct QuantityParser<'t, U> {
+    units: &'t [(&'t str, U)],
+    ignore_case: bool,
+}
+
+impl<'t, U> QuantityParser<'t, U> {
+    /// Accept the units in a table, such as `&[("kg", Mass::Kilogram), ("kilogram", Mass::Kilogram)]`
+    pub fn new(units: &'t [(&'t str, U)]) -> Self {
+        Self {
+            units,
+            ignore_case: false,
+        }
+    }
+
+    /// Match unit names ignoring ASCII case
+    pub fn ignore_case(mut self) -> Self {
+        self.ignore_case = true;
+        self
+    }
+}
+
+impl<'a, U: Clone> Parser<'a, Quantity<U>, ParserError> for QuantityParser<'_, U> {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, Quantity<U>, ParserError> {
+        let (value, s) = try_parse!(float::<f64>(input));
+        let (unit, s) = try_parse!(unit(
+            self.units,
+            self.ignore_case,
+            s.trim_start_matches(' ')
+        ));
+        ParserResult::from_val(Quantity { value, unit }, s)
+    }
+}
+
+/// Parse a number followed by one of the units in a table, see [QuantityParser]
+pub fn quantity<'a, U: Clone>(
+    units: &[(&str, U)],
+    input: &'a str,
+) -> ParserResult<'a, Quantity<U>, ParserError> {
+    QuantityParser::new(units).parse(input)
+}
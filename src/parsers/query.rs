@@ -0,0 +1,68 @@
+use crate::{container::Container, *};
+
+fn hex_byte(input: &str) -> ParserResult<'_, u8, ParserError> {
+    repeating(
+        cur!(matching_char <= "hex digit", |c| c.is_ascii_hexdigit()),
+        2..=2,
+        input,
+    )
+    .map(|digits| {
+        digits
+            .into_iter()
+            .fold(0, |b, d| b * 16 + d.to_digit(16).unwrap() as u8)
+    })
+}
+
+/// Parse a percent-encoded component up to a character matching `is_end`, decoding `%XX` sequences and `+` as a space
+pub fn percent_decoded(
+    is_end: impl Fn(char) -> bool,
+    mut input: &str,
+) -> ParserResult<'_, String, ParserError> {
+    let mut bytes = vec![];
+    while let Some(c) = input.chars().next().filter(|c| !is_end(*c)) {
+        input = &input[c.len_utf8()..];
+        match c {
+            '+' => bytes.push(b' '),
+            '%' => {
+                let (byte, rest) = try_parse!(hex_byte(input));
+                bytes.push(byte);
+                input = rest;
+            }
+            c => bytes.extend_from_slice(c.encode_utf8(&mut [0; 4]).as_bytes()),
+        }
+    }
+    ParserResult::from_val(String::from_utf8_lossy(&bytes).into_owned(), input)
+}
+
+fn is_query_end(c: char) -> bool {
+    c == '&' || c == '#'
+}
+
+/// Parse a single `key=value` pair, where a key without `=` has an empty value
+pub fn pair(input: &str) -> ParserResult<'_, (String, String), ParserError> {
+    let (key, s) = try_parse!(percent_decoded(|c| c == '=' || is_query_end(c), input));
+    let (eq, s) = try_parse!(literal("=", s).optional(s));
+    let (value, s) = match eq {
+        Some(_) => try_parse!(percent_decoded(is_query_end, s)),
+        None => (String::new(), s),
+    };
+    ParserResult::from_val((key, value), s)
+}
+
+/// Parse a query string such as `a=1&b=two%20words&flag` into a container of pairs, keeping repeated keys
+///
+/// A leading `?` is skipped, empty segments are ignored, and parsing stops before a `#` fragment.
+pub fn query<C: Container<(String, String)>>(input: &str) -> ParserResult<'_, C, ParserError> {
+    let mut pairs = C::default();
+    let mut s = input.strip_prefix('?').unwrap_or(input);
+    while !s.is_empty() && !s.starts_with('#') {
+        if let Some(rest) = s.strip_prefix('&') {
+            s = rest;
+            continue;
+        }
+        let (pair, rest) = try_parse!(pair(s));
+        pairs.add(pair);
+        s = rest;
+    }
+    ParserResult::from_val(pairs, s)
+}
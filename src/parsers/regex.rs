@@ -0,0 +1,512 @@
+use crate::*;
+
+type Result<'a, T> = ParserResult<'a, T, RegexError>;
+
+#[derive(Debug)]
+pub enum RegexError {
+    ParserError(ParserError),
+    /// A character class range whose start is after its end, such as `[z-a]`
+    InvalidRange(char, char),
+    /// A counted repetition whose minimum exceeds its maximum, such as `a{3,1}`, or whose bounds exceed [MAX_REPEAT]
+    InvalidRepetition,
+    InvalidEscape(char),
+    /// A pattern which would compile to more than [MAX_PROGRAM] instructions, such as one nesting counted repetitions
+    TooLarge,
+}
+
+impl From<ParserError> for RegexError {
+    fn from(value: ParserError) -> Self {
+        RegexError::ParserError(value)
+    }
+}
+
+/// A shorthand class such as `\d`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Perl {
+    Digit,
+    Word,
+    Space,
+}
+
+impl Perl {
+    fn matches(self, c: char) -> bool {
+        match self {
+            Perl::Digit => c.is_ascii_digit(),
+            Perl::Word => c.is_alphanumeric() || c == '_',
+            Perl::Space => c.is_whitespace(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ClassItem {
+    Char(char),
+    Range(char, char),
+    Perl { class: Perl, negated: bool },
+}
+
+/// A set of characters, such as `[a-z_]` or `\w`
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Class {
+    pub negated: bool,
+    pub items: Vec<ClassItem>,
+}
+
+impl Class {
+    fn matches(&self, c: char) -> bool {
+        let found = self.items.iter().any(|item| match *item {
+            ClassItem::Char(ch) => c == ch,
+            ClassItem::Range(lo, hi) => (lo..=hi).contains(&c),
+            ClassItem::Perl { class, negated } => class.matches(c) != negated,
+        });
+        found != self.negated
+    }
+}
+
+/// A node of a parsed regular expression
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Node {
+    Char(char),
+    /// `.`, matching anything but a newline
+    Any,
+    Class(Class),
+    /// `^`, matching only at the start of the input
+    Start,
+    /// `$`, matching only at the end of the input
+    End,
+    Concat(Vec<Node>),
+    Alternation(Vec<Node>),
+    Repeat {
+        node: Box<Node>,
+        min: usize,
+        max: Option<usize>,
+        greedy: bool,
+    },
+}
+
+fn perl_class(c: char) -> Option<ClassItem> {
+    let (class, negated) = match c {
+        'd' => (Perl::Digit, false),
+        'D' => (Perl::Digit, true),
+        'w' => (Perl::Word, false),
+        'W' => (Perl::Word, true),
+        's' => (Perl::Space, false),
+        'S' => (Perl::Space, true),
+        _ => return None,
+    };
+    Some(ClassItem::Perl { class, negated })
+}
+
+/// Parse the character after a `\`, which is either a shorthand class or an escaped character
+fn escape(s: &str) -> Result<'_, ClassItem> {
+    let (_, rest) = try_parse!(literal("\\", s));
+    let (c, rest) = try_parse!(advance(rest));
+    if let Some(class) = perl_class(c) {
+        return ParserResult::from_val(class, rest);
+    }
+    let c = match c {
+        'n' => '\n',
+        't' => '\t',
+        'r' => '\r',
+        '0' => '\0',
+        c if c.is_alphanumeric() => return ParserResult::from_err(RegexError::InvalidEscape(c), s),
+        c => c,
+    };
+    ParserResult::from_val(ClassItem::Char(c), rest)
+}
+
+fn class_char(s: &str) -> Result<'_, ClassItem> {
+    if try_parse!(peek(s)).0 == '\\' {
+        escape(s)
+    } else {
+        advance(s).map(ClassItem::Char).err_into()
+    }
+}
+
+fn class_item(s: &str) -> Result<'_, ClassItem> {
+    let (start, rest) = try_parse!(class_char(s));
+    let ClassItem::Char(lo) = start else {
+        return ParserResult::from_val(start, rest);
+    };
+    if !rest.starts_with('-') || rest[1..].starts_with(']') {
+        return ParserResult::from_val(start, rest);
+    }
+    let end_start = &rest[1..];
+    let (end, rest) = try_parse!(class_char(end_start));
+    match end {
+        ClassItem::Char(hi) if lo <= hi => ParserResult::from_val(ClassItem::Range(lo, hi), rest),
+        ClassItem::Char(hi) => ParserResult::from_err(RegexError::InvalidRange(lo, hi), s),
+        _ => ParserResult::from_err(
+            ParserError::ExpectedToken("range end".into()).into(),
+            end_start,
+        ),
+    }
+}
+
+/// Parse a bracketed class such as `[^a-z]`, where a leading `]` is taken literally
+fn class(s: &str) -> Result<'_, Class> {
+    let (_, s) = try_parse!(literal("[", s));
+    let (negated, mut s) = try_parse!(literal("^", s).optional(s));
+    let mut items = vec![];
+    loop {
+        if s.starts_with(']') && !items.is_empty() {
+            break;
+        }
+        let (item, rest) = try_parse!(class_item(s));
+        items.push(item);
+        s = rest;
+    }
+    let (_, s) = try_parse!(literal("]", s));
+    ParserResult::from_val(
+        Class {
+            negated: negated.is_some(),
+            items,
+        },
+        s,
+    )
+}
+
+fn atom(s: &str) -> Result<'_, Node> {
+    let (c, rest) = try_parse!(peek(s));
+    match c {
+        '(' => {
+            let (_, rest) = try_parse!(advance(rest));
+            let (_, rest) = try_parse!(literal("?:", rest).optional(rest));
+            let (node, rest) = try_parse!(alternation(rest));
+            let (_, rest) = try_parse!(literal(")", rest));
+            ParserResult::from_val(node, rest)
+        }
+        '[' => class(s).map(Node::Class),
+        '.' => ParserResult::from_val(Node::Any, &s[1..]),
+        '^' => ParserResult::from_val(Node::Start, &s[1..]),
+        '$' => ParserResult::from_val(Node::End, &s[1..]),
+        '\\' => escape(s).map(|item| match item {
+            ClassItem::Char(c) => Node::Char(c),
+            item => Node::Class(Class {
+                negated: false,
+                items: vec![item],
+            }),
+        }),
+        '*' | '+' | '?' | '{' | ')' | '|' => {
+            ParserResult::from_err(ParserError::ExpectedToken("atom".into()).into(), s)
+        }
+        c => ParserResult::from_val(Node::Char(c), &s[c.len_utf8()..]),
+    }
+}
+
+/// The largest bound allowed in a counted repetition, since each repetition is compiled separately
+pub const MAX_REPEAT: usize = 1000;
+
+fn number(s: &str) -> Result<'_, usize> {
+    take_while("digit", |c| c.is_ascii_digit(), s)
+        .map(|n| n.parse().unwrap_or(usize::MAX))
+        .err_into()
+}
+
+/// Parse a `{n}`, `{n,}` or `{n,m}` repetition count
+fn counted(s: &str) -> Result<'_, (usize, Option<usize>)> {
+    let (_, rest) = try_parse!(literal("{", s));
+    let (min, rest) = try_parse!(number(rest));
+    let (comma, rest) = try_parse!(literal(",", rest).optional(rest));
+    let (max, rest) = match comma {
+        Some(_) => try_parse!(number(rest).optional(rest)),
+        None => (Some(min), rest),
+    };
+    let (_, rest) = try_parse!(literal("}", rest));
+    if max.is_some_and(|max| max < min) || max.unwrap_or(min) > MAX_REPEAT {
+        return ParserResult::from_err(RegexError::InvalidRepetition, s);
+    }
+    ParserResult::from_val((min, max), rest)
+}
+
+fn repeat(s: &str) -> Result<'_, Node> {
+    let (node, rest) = try_parse!(atom(s));
+    let (min, max, rest) = match peek(rest).ok() {
+        Some('*') => (0, None, &rest[1..]),
+        Some('+') => (1, None, &rest[1..]),
+        Some('?') => (0, Some(1), &rest[1..]),
+        Some('{') => {
+            let ((min, max), rest) = try_parse!(counted(rest));
+            (min, max, rest)
+        }
+        _ => return ParserResult::from_val(node, rest),
+    };
+    let (lazy, rest) = try_parse!(literal("?", rest).optional(rest));
+    ParserResult::from_val(
+        Node::Repeat {
+            node: node.into(),
+            min,
+            max,
+            greedy: lazy.is_none(),
+        },
+        rest,
+    )
+}
+
+fn concat(mut s: &str) -> Result<'_, Node> {
+    let mut nodes = vec![];
+    while !s.is_empty() && !s.starts_with([')', '|']) {
+        let (node, rest) = try_parse!(repeat(s));
+        nodes.push(node);
+        s = rest;
+    }
+    let node = if nodes.len() == 1 {
+        nodes.pop().unwrap()
+    } else {
+        Node::Concat(nodes)
+    };
+    ParserResult::from_val(node, s)
+}
+
+fn alternation(s: &str) -> Result<'_, Node> {
+    delimited_list::<_, _, _, Vec<Node>, ()>(concat, |s| literal("|", s).err_into(), s).map(
+        |(mut alts, _)| {
+            if alts.len() == 1 {
+                alts.pop().unwrap()
+            } else {
+                Node::Alternation(alts)
+            }
+        },
+    )
+}
+
+/// Parse a regular expression pattern into its syntax tree
+pub fn pattern(s: &str) -> Result<'_, Node> {
+    let (node, rest) = try_parse!(alternation(s));
+    if !rest.is_empty() {
+        return ParserResult::from_err(
+            ParserError::ExpectedToken("end of pattern".into()).into(),
+            rest,
+        );
+    }
+    ParserResult::from_val(node, rest)
+}
+
+/// The most instructions a pattern may compile to, since nested repetitions multiply their counts
+pub const MAX_PROGRAM: usize = 100_000;
+
+/// The number of instructions a node compiles to, saturating rather than overflowing
+fn size(node: &Node) -> usize {
+    match node {
+        Node::Char(_) | Node::Any | Node::Class(_) | Node::Start | Node::End => 1,
+        Node::Concat(nodes) => nodes
+            .iter()
+            .fold(0, |sum, node| sum.saturating_add(size(node))),
+        Node::Alternation(alts) => alts
+            .iter()
+            .fold(2 * alts.len().saturating_sub(1), |sum, alt| {
+                sum.saturating_add(size(alt))
+            }),
+        Node::Repeat { node, min, max, .. } => {
+            let size = size(node);
+            let optional = match max {
+                None => size.saturating_add(2),
+                Some(max) => (max - min).saturating_mul(size.saturating_add(1)),
+            };
+            min.saturating_mul(size).saturating_add(optional)
+        }
+    }
+}
+
+/// An instruction of a compiled expression
+#[derive(Debug, Clone)]
+enum Inst {
+    Char(char),
+    Any,
+    Class(Class),
+    Start,
+    End,
+    /// Continue at both targets, preferring the first
+    Split(usize, usize),
+    Jump(usize),
+    Match,
+}
+
+fn compile(node: &Node, prog: &mut Vec<Inst>) {
+    match node {
+        Node::Char(c) => prog.push(Inst::Char(*c)),
+        Node::Any => prog.push(Inst::Any),
+        Node::Class(class) => prog.push(Inst::Class(class.clone())),
+        Node::Start => prog.push(Inst::Start),
+        Node::End => prog.push(Inst::End),
+        Node::Concat(nodes) => nodes.iter().for_each(|node| compile(node, prog)),
+        Node::Alternation(alts) => {
+            let mut jumps = vec![];
+            for (i, alt) in alts.iter().enumerate() {
+                if i + 1 == alts.len() {
+                    compile(alt, prog);
+                    break;
+                }
+                let split = prog.len();
+                prog.push(Inst::Split(split + 1, 0));
+                compile(alt, prog);
+                jumps.push(prog.len());
+                prog.push(Inst::Jump(0));
+                prog[split] = Inst::Split(split + 1, prog.len());
+            }
+            let end = prog.len();
+            for jump in jumps {
+                prog[jump] = Inst::Jump(end);
+            }
+        }
+        Node::Repeat {
+            node,
+            min,
+            max,
+            greedy,
+        } => {
+            let split = |prog: &mut Vec<Inst>, at: usize, end: usize| {
+                prog[at] = match greedy {
+                    true => Inst::Split(at + 1, end),
+                    false => Inst::Split(end, at + 1),
+                };
+            };
+            for _ in 0..*min {
+                let start = prog.len();
+                compile(node, prog);
+                if prog.len() == start {
+                    // Repeating a node which compiles to nothing would only waste time
+                    break;
+                }
+            }
+            match max {
+                None => {
+                    let start = prog.len();
+                    prog.push(Inst::Jump(0));
+                    compile(node, prog);
+                    prog.push(Inst::Jump(start));
+                    split(prog, start, prog.len());
+                }
+                Some(max) => {
+                    let mut splits = vec![];
+                    for _ in *min..*max {
+                        splits.push(prog.len());
+                        prog.push(Inst::Jump(0));
+                        compile(node, prog);
+                    }
+                    let end = prog.len();
+                    for at in splits {
+                        split(prog, at, end);
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// A compiled regular expression, which parses a match at the start of its input with Perl-style leftmost-first semantics in time linear in the input
+#[derive(Debug, Clone)]
+pub struct Regex {
+    root: Node,
+    prog: Vec<Inst>,
+}
+
+impl Regex {
+    /// Compile a pattern, failing with the position of any syntax error
+    pub fn new(pattern_str: &str) -> std::result::Result<Regex, (RegexError, usize)> {
+        let res = pattern(pattern_str);
+        let offset = pattern_str.len() - res.source.len();
+        match res.typ {
+            ParserResultType::Ok(root) => {
+                if size(&root) >= MAX_PROGRAM {
+                    return Err((RegexError::TooLarge, 0));
+                }
+                let mut prog = vec![];
+                compile(&root, &mut prog);
+                prog.push(Inst::Match);
+                Ok(Regex { root, prog })
+            }
+            ParserResultType::Err(e) => Err((e, offset)),
+            ParserResultType::Incomplete(_) => {
+                Err((ParserError::UnexpectedEndOfFile.into(), offset))
+            }
+        }
+    }
+
+    /// Get the syntax tree of this expression
+    pub fn node(&self) -> &Node {
+        &self.root
+    }
+
+    /// Add the thread at `pc` and every thread reachable from it without consuming input, in priority order
+    ///
+    /// This uses an explicit stack rather than recursion, so that long chains of jumps can't overflow the call stack.
+    fn add(&self, threads: &mut Vec<usize>, seen: &mut [bool], pc: usize, text: &str, pos: usize) {
+        let mut stack = vec![pc];
+        while let Some(pc) = stack.pop() {
+            if std::mem::replace(&mut seen[pc], true) {
+                continue;
+            }
+            match self.prog[pc] {
+                Inst::Jump(to) => stack.push(to),
+                Inst::Split(first, second) => {
+                    // The first target is popped, and so added, before the second
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::Start if pos == 0 => stack.push(pc + 1),
+                Inst::End if pos == text.len() => stack.push(pc + 1),
+                Inst::Start | Inst::End => {}
+                _ => threads.push(pc),
+            }
+        }
+    }
+
+    /// Find the byte length of a match starting at `pos`
+    fn match_at(&self, text: &str, mut pos: usize) -> Option<usize> {
+        let mut seen = vec![false; self.prog.len()];
+        let mut threads = vec![];
+        self.add(&mut threads, &mut seen, 0, text, pos);
+        let mut end = None;
+        while !threads.is_empty() {
+            let c = text[pos..].chars().next();
+            let next = pos + c.map_or(0, char::len_utf8);
+            let mut next_threads = vec![];
+            seen.fill(false);
+            for &pc in &threads {
+                let matched = match (&self.prog[pc], c) {
+                    (Inst::Match, _) => {
+                        // Threads after this one have lower priority than the match it found
+                        end = Some(pos);
+                        break;
+                    }
+                    (Inst::Char(ch), Some(c)) => *ch == c,
+                    (Inst::Any, Some(c)) => c != '\n',
+                    (Inst::Class(class), Some(c)) => class.matches(c),
+                    _ => false,
+                };
+                if matched {
+                    self.add(&mut next_threads, &mut seen, pc + 1, text, next);
+                }
+            }
+            if c.is_none() {
+                break;
+            }
+            threads = next_threads;
+            pos = next;
+        }
+        end
+    }
+
+    /// Search for the first match anywhere in the text, returning its byte range
+    pub fn find(&self, text: &str) -> Option<std::ops::Range<usize>> {
+        text.char_indices()
+            .map(|(i, _)| i)
+            .chain(std::iter::once(text.len()))
+            .find_map(|start| self.match_at(text, start).map(|end| start..end))
+    }
+
+    /// Check whether this expression matches anywhere in the text
+    pub fn is_match(&self, text: &str) -> bool {
+        self.find(text).is_some()
+    }
+}
+
+impl<'a> Parser<'a, &'a str, ParserError> for Regex {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, &'a str, ParserError> {
+        match self.match_at(input, 0) {
+            Some(end) => ParserResult::from_val(&input[..end], &input[end..]),
+            None => ParserResult::from_err(ParserError::ExpectedToken("regex match".into()), input),
+        }
+    }
+}
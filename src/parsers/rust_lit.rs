@@ -0,0 +1,249 @@
+use crate::{
+    advance,
+    error::ParserError,
+    escape::{escape, COMMON_ESCAPES},
+    identifier, is_ident_start, literal, matching_char,
+    num::digits_radix,
+    try_parse, ParserResult,
+};
+
+/// A Rust literal token, with the suffix following it such as the `u8` in `1u8`
+#[derive(Debug, Clone, PartialEq)]
+pub enum RustLit<'a> {
+    Char(char),
+    Byte(u8),
+    Str(String),
+    ByteStr(Vec<u8>),
+    Int {
+        value: u128,
+        suffix: Option<&'a str>,
+    },
+    Float {
+        value: f64,
+        suffix: Option<&'a str>,
+    },
+    Bool(bool),
+}
+
+/// Parse an escape in a char or string literal, where `\x` escapes are limited to ASCII
+fn char_escape(input: &str) -> ParserResult<'_, char, ParserError> {
+    let (c, s) = try_parse!(escape(COMMON_ESCAPES, input));
+    if input.starts_with("\\x") && !c.is_ascii() {
+        return ParserResult::from_err(ParserError::InvalidEscape('x'), input);
+    }
+    ParserResult::from_val(c, s)
+}
+
+/// Parse an escape in a byte or byte string literal, where `\x` escapes cover every byte and `\u` isn't allowed
+fn byte_escape(input: &str) -> ParserResult<'_, u8, ParserError> {
+    if input.starts_with("\\u") {
+        return ParserResult::from_err(ParserError::InvalidEscape('u'), input);
+    }
+    escape(COMMON_ESCAPES, input).map(|c| c as u8)
+}
+
+/// Parse a single ASCII character of a byte literal, which must be escaped if it is a quote or backslash
+fn ascii_char(quote: char, input: &str) -> ParserResult<'_, u8, ParserError> {
+    matching_char(
+        "ASCII character",
+        |c| c.is_ascii() && c != quote && c != '\\',
+        input,
+    )
+    .map(|c| c as u8)
+}
+
+/// Skip a backslash, newline and the whitespace following it inside a string literal, if present
+fn continuation(input: &str) -> Option<&str> {
+    let rest = input
+        .strip_prefix("\\\n")
+        .or_else(|| input.strip_prefix("\\\r\n"))?;
+    Some(rest.trim_start_matches([' ', '\t', '\n', '\r']))
+}
+
+/// An optional identifier suffix directly after a literal
+fn suffix(input: &str) -> ParserResult<'_, Option<&str>, ParserError> {
+    identifier(input).optional(input)
+}
+
+/// Parse a char literal such as `'a'`, `'\n'` or `'\u{1F600}'`
+pub fn char_lit(input: &str) -> ParserResult<'_, char, ParserError> {
+    let (_, s) = try_parse!(literal("'", input));
+    let (c, s) = if s.starts_with('\\') {
+        try_parse!(char_escape(s))
+    } else {
+        try_parse!(matching_char("character", |c| c != '\'' && c != '\n', s))
+    };
+    let (_, s) = try_parse!(literal("'", s));
+    ParserResult::from_val(c, s)
+}
+
+/// Parse a byte literal such as `b'a'` or `b'\xFF'`
+pub fn byte_lit(input: &str) -> ParserResult<'_, u8, ParserError> {
+    let (_, s) = try_parse!(literal("b'", input));
+    let (b, s) = if s.starts_with('\\') {
+        try_parse!(byte_escape(s))
+    } else {
+        try_parse!(ascii_char('\'', s))
+    };
+    let (_, s) = try_parse!(literal("'", s));
+    ParserResult::from_val(b, s)
+}
+
+/// Parse a string literal such as `"a\tb"`, removing line continuations
+pub fn str_lit(input: &str) -> ParserResult<'_, String, ParserError> {
+    let (_, mut s) = try_parse!(literal("\"", input));
+    let mut string = String::new();
+    loop {
+        if let Some(rest) = s.strip_prefix('"') {
+            return ParserResult::from_val(string, rest);
+        }
+        if let Some(rest) = continuation(s) {
+            s = rest;
+        } else if s.starts_with('\\') {
+            let (c, rest) = try_parse!(char_escape(s));
+            string.push(c);
+            s = rest;
+        } else {
+            let (c, rest) = try_parse!(advance(s));
+            string.push(c);
+            s = rest;
+        }
+    }
+}
+
+/// Parse a byte string literal such as `b"a\xFF"`, removing line continuations
+pub fn byte_str_lit(input: &str) -> ParserResult<'_, Vec<u8>, ParserError> {
+    let (_, mut s) = try_parse!(literal("b\"", input));
+    let mut bytes = vec![];
+    loop {
+        if let Some(rest) = s.strip_prefix('"') {
+            return ParserResult::from_val(bytes, rest);
+        }
+        if let Some(rest) = continuation(s) {
+            s = rest;
+            continue;
+        }
+        let (b, rest) = if s.starts_with('\\') {
+            try_parse!(byte_escape(s))
+        } else if s.is_empty() {
+            return ParserResult::from_err(ParserError::UnexpectedEndOfFile, s);
+        } else {
+            try_parse!(ascii_char('"', s))
+        };
+        bytes.push(b);
+        s = rest;
+    }
+}
+
+/// Parse a raw string literal such as `r"a\b"` or `r#"say "hi""#`, returning its contents without processing escapes
+pub fn raw_str_lit(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let (_, s) = try_parse!(literal("r", input));
+    raw_body(s)
+}
+
+/// Parse the hashes, quotes and contents of a raw string after its prefix
+fn raw_body(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let hashes = input.len() - input.trim_start_matches('#').len();
+    let (_, s) = try_parse!(literal("\"", &input[hashes..]));
+    let close = format!("\"{}", "#".repeat(hashes));
+    match s.find(&close) {
+        Some(end) => ParserResult::from_val(&s[..end], &s[end + close.len()..]),
+        None => ParserResult::from_err(ParserError::UnexpectedEndOfFile, input),
+    }
+}
+
+/// Parse a raw byte string literal such as `br#"a"#`, whose contents must be ASCII
+pub fn raw_byte_str_lit(input: &str) -> ParserResult<'_, &[u8], ParserError> {
+    let (_, s) = try_parse!(literal("br", input));
+    let (body, rest) = try_parse!(raw_body(s));
+    if !body.is_ascii() {
+        return ParserResult::from_err(ParserError::ExpectedToken("ASCII character".into()), s);
+    }
+    ParserResult::from_val(body.as_bytes(), rest)
+}
+
+/// Parse an integer literal such as `42`, `0xFF_u8` or `0b1010`, along with its suffix
+pub fn int_lit(input: &str) -> ParserResult<'_, (u128, Option<&str>), ParserError> {
+    let (radix, s) = match input.get(..2) {
+        Some("0x") => (16, &input[2..]),
+        Some("0o") => (8, &input[2..]),
+        Some("0b") => (2, &input[2..]),
+        _ => (10, input),
+    };
+    let s = if radix == 10 {
+        s
+    } else {
+        s.trim_start_matches('_')
+    };
+    let (value, s) = try_parse!(digits_radix::<u128>(radix, s));
+    let (suffix, s) = try_parse!(suffix(s));
+    ParserResult::from_val((value, suffix), s)
+}
+
+/// Parse a float literal such as `1.5`, `1e10`, `2.` or `1f32`, along with its suffix
+pub fn float_lit(input: &str) -> ParserResult<'_, (f64, Option<&str>), ParserError> {
+    let digits = |s: &str| {
+        s.len()
+            - s.trim_start_matches(|c: char| c.is_ascii_digit() || c == '_')
+                .len()
+    };
+    let int = digits(input);
+    if int == 0 || input.starts_with('_') {
+        return ParserResult::from_err(ParserError::ExpectedToken("float".into()), input);
+    }
+    let mut end = int;
+    let mut is_float = false;
+    let rest = &input[end..];
+    if rest.starts_with('.') && !rest[1..].starts_with(|c: char| c == '.' || is_ident_start(c)) {
+        is_float = true;
+        end += 1 + digits(&rest[1..]);
+    }
+    let rest = &input[end..];
+    if rest.starts_with(['e', 'E']) {
+        let sign = usize::from(rest[1..].starts_with(['+', '-']));
+        let exp = digits(&rest[1 + sign..]);
+        if exp > 0 {
+            is_float = true;
+            end += 1 + sign + exp;
+        }
+    }
+    let (suffix, s) = try_parse!(suffix(&input[end..]));
+    if !is_float && !matches!(suffix, Some("f32" | "f64")) {
+        return ParserResult::from_err(ParserError::ExpectedToken("float".into()), input);
+    }
+    match input[..end].replace('_', "").parse() {
+        Ok(value) => ParserResult::from_val((value, suffix), s),
+        Err(_) => ParserResult::from_err(ParserError::ExpectedToken("float".into()), input),
+    }
+}
+
+/// Parse any Rust literal, including `true` and `false`
+pub fn rust_lit(input: &str) -> ParserResult<'_, RustLit<'_>, ParserError> {
+    if input.starts_with("b'") {
+        byte_lit(input).map(RustLit::Byte)
+    } else if input.starts_with("b\"") {
+        byte_str_lit(input).map(RustLit::ByteStr)
+    } else if input.starts_with("br") {
+        raw_byte_str_lit(input).map(|b| RustLit::ByteStr(b.to_vec()))
+    } else if input.starts_with("r\"") || input.starts_with("r#") {
+        raw_str_lit(input).map(|s| RustLit::Str(s.to_string()))
+    } else if input.starts_with('\'') {
+        char_lit(input).map(RustLit::Char)
+    } else if input.starts_with('"') {
+        str_lit(input).map(RustLit::Str)
+    } else if input.starts_with(|c: char| c.is_ascii_digit()) {
+        float_lit(input)
+            .map(|(value, suffix)| RustLit::Float { value, suffix })
+            .or(
+                |s| int_lit(s).map(|(value, suffix)| RustLit::Int { value, suffix }),
+                input,
+            )
+    } else {
+        let (word, s) = try_parse!(identifier(input));
+        match word {
+            "true" => ParserResult::from_val(RustLit::Bool(true), s),
+            "false" => ParserResult::from_val(RustLit::Bool(false), s),
+            _ => ParserResult::from_err(ParserError::ExpectedToken("literal".into()), input),
+        }
+    }
+}
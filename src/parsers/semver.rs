@@ -0,0 +1,289 @@
+use std::{cmp::Ordering, fmt::Display, num::ParseIntError};
+
+use crate::*;
+
+type Result<'a, T> = ParserResult<'a, T, SemverError>;
+
+#[derive(Debug)]
+pub enum SemverError {
+    ParseInt(ParseIntError),
+    ParserError(ParserError),
+    LeadingZero,
+}
+
+impl From<ParserError> for SemverError {
+    fn from(value: ParserError) -> Self {
+        SemverError::ParserError(value)
+    }
+}
+
+/// A pre-release identifier, ordered with numeric identifiers before alphanumeric ones
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Identifier {
+    Numeric(u64),
+    AlphaNumeric(String),
+}
+
+impl Display for Identifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Identifier::Numeric(n) => write!(f, "{n}"),
+            Identifier::AlphaNumeric(s) => write!(f, "{s}"),
+        }
+    }
+}
+
+/// A semantic version as described by SemVer 2.0
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Version {
+    pub major: u64,
+    pub minor: u64,
+    pub patch: u64,
+    pub pre: Vec<Identifier>,
+    pub build: Vec<String>,
+}
+
+impl Version {
+    /// Compare two versions by precedence, which ignores build metadata
+    pub fn cmp_precedence(&self, other: &Version) -> Ordering {
+        (self.major, self.minor, self.patch)
+            .cmp(&(other.major, other.minor, other.patch))
+            .then_with(|| match (self.pre.is_empty(), other.pre.is_empty()) {
+                (true, true) => Ordering::Equal,
+                (true, false) => Ordering::Greater,
+                (false, true) => Ordering::Less,
+                (false, false) => self.pre.cmp(&other.pre),
+            })
+    }
+}
+
+impl Display for Version {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}.{}", self.major, self.minor, self.patch)?;
+        for (i, ident) in self.pre.iter().enumerate() {
+            write!(f, "{}{ident}", if i == 0 { '-' } else { '.' })?;
+        }
+        for (i, ident) in self.build.iter().enumerate() {
+            write!(f, "{}{ident}", if i == 0 { '+' } else { '.' })?;
+        }
+        Ok(())
+    }
+}
+
+/// The operator of a version requirement comparator
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Op {
+    Exact,
+    Greater,
+    GreaterEq,
+    Less,
+    LessEq,
+    Tilde,
+    Caret,
+}
+
+/// A single comparator such as `>=1.2`, where the minor and patch components may be omitted
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Comparator {
+    pub op: Op,
+    pub major: u64,
+    pub minor: Option<u64>,
+    pub patch: Option<u64>,
+    pub pre: Vec<Identifier>,
+}
+
+impl Comparator {
+    fn parts(&self) -> Vec<u64> {
+        std::iter::once(self.major)
+            .chain(self.minor)
+            .chain(self.patch)
+            .collect()
+    }
+
+    /// Whether a pre-release version may match this comparator, which Cargo only allows when the comparator has a
+    /// pre-release of the same `major.minor.patch`, so that `^1.0.0` doesn't match `1.2.0-alpha`
+    fn allows_pre(&self, version: &Version) -> bool {
+        !self.pre.is_empty()
+            && (self.major, self.minor, self.patch)
+                == (version.major, Some(version.minor), Some(version.patch))
+    }
+
+    /// Check whether a version satisfies this comparator, using Cargo's semantics for `~`, `^` and pre-releases
+    pub fn matches(&self, version: &Version) -> bool {
+        (version.pre.is_empty() || self.allows_pre(version)) && self.matches_ignoring_pre(version)
+    }
+
+    fn matches_ignoring_pre(&self, version: &Version) -> bool {
+        let parts = self.parts();
+        let k = parts.len();
+        let v = [version.major, version.minor, version.patch];
+        let lower = Version {
+            major: self.major,
+            minor: self.minor.unwrap_or(0),
+            patch: self.patch.unwrap_or(0),
+            pre: self.pre.clone(),
+            build: vec![],
+        };
+        let cmp = version.cmp_precedence(&lower);
+        match self.op {
+            Op::Exact if k == 3 => cmp.is_eq(),
+            Op::Exact => v[..k] == parts[..],
+            Op::Greater if k == 3 => cmp.is_gt(),
+            Op::Greater => v[..k] > parts[..],
+            Op::GreaterEq => cmp.is_ge(),
+            Op::Less => cmp.is_lt(),
+            Op::LessEq if k == 3 => cmp.is_le(),
+            Op::LessEq => v[..k] <= parts[..],
+            Op::Tilde => cmp.is_ge() && v[..k.min(2)] == parts[..k.min(2)],
+            Op::Caret => {
+                let significant = parts.iter().position(|&p| p != 0).map_or(k, |i| i + 1);
+                cmp.is_ge() && v[..significant] == parts[..significant]
+            }
+        }
+    }
+}
+
+/// A comma-separated set of comparators, all of which must match
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionReq {
+    pub comparators: Vec<Comparator>,
+}
+
+impl VersionReq {
+    /// Check whether a version satisfies every comparator in this requirement, where a pre-release version must be
+    /// allowed by at least one of them like in [Comparator::matches], so `>=1.0.0-alpha, <2` matches `1.0.0-beta`
+    pub fn matches(&self, version: &Version) -> bool {
+        (version.pre.is_empty() || self.comparators.iter().any(|c| c.allows_pre(version)))
+            && self
+                .comparators
+                .iter()
+                .all(|c| c.matches_ignoring_pre(version))
+    }
+}
+
+/// Parse a numeric identifier, which may not have leading zeroes
+fn numeric(s: &str) -> Result<'_, u64> {
+    let (digits, rest) = try_parse!(take_while("digit", |c| c.is_ascii_digit(), s));
+    if digits.len() > 1 && digits.starts_with('0') {
+        return ParserResult::from_err(SemverError::LeadingZero, s);
+    }
+    match digits.parse() {
+        Ok(n) => ParserResult::from_val(n, rest),
+        Err(e) => ParserResult::from_err(SemverError::ParseInt(e), s),
+    }
+}
+
+fn identifier_chars(s: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("identifier", |c| c.is_ascii_alphanumeric() || c == '-', s)
+}
+
+fn pre_identifier(s: &str) -> Result<'_, Identifier> {
+    let (ident, rest) = try_parse!(identifier_chars(s));
+    if ident.bytes().all(|b| b.is_ascii_digit()) {
+        numeric(s).map(Identifier::Numeric)
+    } else {
+        ParserResult::from_val(Identifier::AlphaNumeric(ident.to_string()), rest)
+    }
+}
+
+fn dot_separated<'a, T>(elem: impl Parser<'a, T, SemverError>, s: &'a str) -> Result<'a, Vec<T>> {
+    delimited_list::<_, _, _, Vec<T>, ()>(elem, |s| literal(".", s).err_into(), s).map(|(v, _)| v)
+}
+
+/// Parse a `-` and the pre-release identifiers after it, which must be valid once the `-` is there
+fn pre_release(s: &str) -> Result<'_, Vec<Identifier>> {
+    match s.strip_prefix('-') {
+        Some(rest) => dot_separated(pre_identifier, rest),
+        None => ParserResult::from_val(vec![], s),
+    }
+}
+
+fn build_metadata(s: &str) -> Result<'_, Vec<String>> {
+    literal("+", s)
+        .err_into()
+        .and(|s| dot_separated(|s| identifier_chars(s).map(str::to_string).err_into(), s))
+        .map(|(_, build)| build)
+        .optional(s)
+        .map(Option::unwrap_or_default)
+}
+
+/// Parse a full version such as `1.0.0-alpha.1+build.5`
+pub fn version(s: &str) -> Result<'_, Version> {
+    let (major, s) = try_parse!(numeric(s));
+    let (_, s) = try_parse!(literal(".", s));
+    let (minor, s) = try_parse!(numeric(s));
+    let (_, s) = try_parse!(literal(".", s));
+    let (patch, s) = try_parse!(numeric(s));
+    let (pre, s) = try_parse!(pre_release(s));
+    let (build, s) = try_parse!(build_metadata(s));
+    ParserResult::from_val(
+        Version {
+            major,
+            minor,
+            patch,
+            pre,
+            build,
+        },
+        s,
+    )
+}
+
+fn op(s: &str) -> Result<'_, Op> {
+    literal(">=", s)
+        .is(Op::GreaterEq)
+        .or(|s| literal("<=", s).is(Op::LessEq), s)
+        .or(|s| literal(">", s).is(Op::Greater), s)
+        .or(|s| literal("<", s).is(Op::Less), s)
+        .or(|s| literal("=", s).is(Op::Exact), s)
+        .or(|s| literal("~", s).is(Op::Tilde), s)
+        .or(|s| literal("^", s).is(Op::Caret), s)
+        .optional(s)
+        .map(|op| op.unwrap_or(Op::Caret))
+        .err_into()
+}
+
+fn component(s: &str) -> Result<'_, Option<u64>> {
+    literal(".", s)
+        .err_into()
+        .and(numeric)
+        .map(|(_, n)| n)
+        .optional(s)
+}
+
+/// Parse a single comparator such as `>=1.2`, defaulting to `^` if no operator is given
+pub fn comparator(s: &str) -> Result<'_, Comparator> {
+    let (op, s) = try_parse!(op(s));
+    let (_, s) = try_parse!(opt_whitespace(s));
+    let (major, s) = try_parse!(numeric(s));
+    let (minor, s) = try_parse!(component(s));
+    let (patch, s) = match minor {
+        Some(_) => try_parse!(component(s)),
+        None => (None, s),
+    };
+    let (pre, s) = match patch {
+        Some(_) => try_parse!(pre_release(s)),
+        None => (vec![], s),
+    };
+    ParserResult::from_val(
+        Comparator {
+            op,
+            major,
+            minor,
+            patch,
+            pre,
+        },
+        s,
+    )
+}
+
+/// Parse a version requirement made of comma-separated comparators, such as `>=1.2, <2`
+pub fn version_req(s: &str) -> Result<'_, VersionReq> {
+    let (_, s) = try_parse!(opt_whitespace(s));
+    let ((comparators, ()), s) = try_parse!(delimited_list(
+        comparator,
+        |s| (opt_whitespace, ",", opt_whitespace).parse(s).err_into(),
+        s,
+    ));
+    let (_, s) = try_parse!(opt_whitespace(s));
+    ParserResult::from_val(VersionReq { comparators }, s)
+}
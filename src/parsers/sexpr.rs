@@ -0,0 +1,110 @@
+use crate::*;
+
+/// A generic s-expression tree
+#[derive(Debug, Clone, PartialEq)]
+pub enum SExpr {
+    Symbol(String),
+    String(String),
+    Integer(i64),
+    Float(f64),
+    List(Vec<SExpr>),
+}
+
+fn is_atom_char(c: char) -> bool {
+    !c.is_whitespace() && !matches!(c, '(' | ')' | '"' | ';')
+}
+
+/// Parse a `;` comment up to the end of the line, returning its body
+pub fn comment(input: &str) -> ParserResult<'_, &str, ParserError> {
+    comment::line_comment(";", input)
+}
+
+/// Skip any amount of whitespace and comments
+fn trivia(mut input: &str) -> ParserResult<'_, (), ParserError> {
+    loop {
+        try_parse!(ignore(opt_whitespace, &mut input));
+        if !input.starts_with(';') {
+            return ParserResult::from_val((), input);
+        }
+        try_parse!(ignore(comment, &mut input));
+    }
+}
+
+fn string(input: &str) -> ParserResult<'_, String, ParserError> {
+    let (_, mut s) = try_parse!(literal("\"", input));
+    let string = iter(
+        |s| {
+            literal("\\", s)
+                .and(advance)
+                .map(|(_, c)| match c {
+                    'n' => '\n',
+                    't' => '\t',
+                    'r' => '\r',
+                    c => c,
+                })
+                .or(|s| matching_char("character", |c| c != '"', s), s)
+        },
+        &mut s,
+    )
+    .ok()
+    .collect();
+    let (_, s) = try_parse!(literal("\"", s));
+    ParserResult::from_val(string, s)
+}
+
+/// Classify a bare token as a number if it starts like one, or a symbol otherwise
+fn atom(input: &str) -> ParserResult<'_, SExpr, ParserError> {
+    let (token, s) = try_parse!(take_while("atom", is_atom_char, input));
+    let unsigned = token.strip_prefix(['-', '+']).unwrap_or(token);
+    let numeric = unsigned.starts_with(|c: char| c.is_ascii_digit());
+    let value = match (token.parse(), token.parse()) {
+        (Ok(i), _) if numeric => SExpr::Integer(i),
+        (_, Ok(f)) if numeric => SExpr::Float(f),
+        _ => SExpr::Symbol(token.to_string()),
+    };
+    ParserResult::from_val(value, s)
+}
+
+fn list(input: &str) -> ParserResult<'_, SExpr, ParserError> {
+    let (_, mut s) = try_parse!(literal("(", input));
+    let mut elems = vec![];
+    loop {
+        try_parse!(ignore(trivia, &mut s));
+        if s.starts_with(')') {
+            break;
+        }
+        if s.is_empty() {
+            return ParserResult::from_err(ParserError::ExpectedLiteral(")"), s);
+        }
+        let (elem, rest) = try_parse!(sexpr(s));
+        elems.push(elem);
+        s = rest;
+    }
+    let (_, s) = try_parse!(literal(")", s));
+    ParserResult::from_val(SExpr::List(elems), s)
+}
+
+/// Parse a single s-expression, skipping any leading whitespace and comments
+pub fn sexpr(input: &str) -> ParserResult<'_, SExpr, ParserError> {
+    let (_, s) = try_parse!(trivia(input));
+    match try_parse!(peek(s)).0 {
+        '(' => list(s),
+        '"' => string(s).map(SExpr::String),
+        ')' => ParserResult::from_err(ParserError::ExpectedToken("expression".into()), s),
+        _ => atom(s),
+    }
+}
+
+/// Parse every top-level s-expression in a document
+pub fn parse_sexprs(mut input: &str) -> ParserResult<'_, Vec<SExpr>, ParserError> {
+    let mut exprs = vec![];
+    loop {
+        try_parse!(ignore(trivia, &mut input));
+        if input.is_empty() {
+            return ParserResult::from_val(exprs, input);
+        }
+        let (expr, rest) = try_parse!(sexpr(input));
+        exprs.push(expr);
+        input = rest;
+    }
+}
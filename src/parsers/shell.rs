@@ -0,0 +1,144 @@
+use crate::{error::ParserError, is_ident_continue, is_ident_start, try_parse, ParserResult};
+
+/// A part of a shell word
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WordPart {
+    /// Literal text, with quotes and escapes already removed
+    Text(String),
+    /// A variable reference such as `$HOME` or `${HOME}`, outside of single quotes
+    Var(String),
+}
+
+/// A single shell word, which may be made of several quoted and unquoted parts such as `"$HOME"/bin`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Word {
+    pub parts: Vec<WordPart>,
+}
+
+impl Word {
+    fn push_char(&mut self, c: char) {
+        match self.parts.last_mut() {
+            Some(WordPart::Text(text)) => text.push(c),
+            _ => self.parts.push(WordPart::Text(c.to_string())),
+        }
+    }
+
+    /// Build the text of the word, replacing variables using a lookup function and treating unknown ones as empty
+    pub fn expand(&self, lookup: impl Fn(&str) -> Option<String>) -> String {
+        self.parts
+            .iter()
+            .map(|part| match part {
+                WordPart::Text(text) => text.clone(),
+                WordPart::Var(name) => lookup(name).unwrap_or_default(),
+            })
+            .collect()
+    }
+}
+
+/// Parse a variable reference after its `$`, returning `None` if it isn't followed by a name
+fn var(input: &str) -> ParserResult<'_, Option<String>, ParserError> {
+    if let Some(body) = input.strip_prefix('{') {
+        return match body.find('}') {
+            Some(end) => ParserResult::from_val(Some(body[..end].to_string()), &body[end + 1..]),
+            None => ParserResult::from_err(ParserError::ExpectedLiteral("}"), input),
+        };
+    }
+    if !input.starts_with(is_ident_start) {
+        return ParserResult::from_val(None, input);
+    }
+    let end = input.find(|c| !is_ident_continue(c)).unwrap_or(input.len());
+    ParserResult::from_val(Some(input[..end].to_string()), &input[end..])
+}
+
+/// Add a variable after a `$` to the word, or a literal `$` if it isn't followed by a name
+fn push_var<'a>(word: &mut Word, input: &'a str) -> ParserResult<'a, (), ParserError> {
+    let (name, s) = try_parse!(var(input));
+    match name {
+        Some(name) => word.parts.push(WordPart::Var(name)),
+        None => word.push_char('$'),
+    }
+    ParserResult::from_val((), s)
+}
+
+/// Parse the inside of a double-quoted string after its opening quote, where only `$`, `` ` ``, `"`, `\` and newlines
+/// can be escaped
+fn double_quoted<'a>(word: &mut Word, input: &'a str) -> ParserResult<'a, (), ParserError> {
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '"' => return ParserResult::from_val((), &input[i + 1..]),
+            '\\' => match chars.next() {
+                Some((_, '\n')) => {}
+                Some((_, c @ ('$' | '`' | '"' | '\\'))) => word.push_char(c),
+                Some((_, c)) => {
+                    word.push_char('\\');
+                    word.push_char(c);
+                }
+                None => break,
+            },
+            '$' => {
+                let (_, s) = try_parse!(push_var(word, &input[i + 1..]));
+                return double_quoted(word, s);
+            }
+            c => word.push_char(c),
+        }
+    }
+    ParserResult::from_err(ParserError::ExpectedLiteral("\""), input)
+}
+
+/// Parse a single word, stopping at unquoted whitespace
+pub fn word(input: &str) -> ParserResult<'_, Word, ParserError> {
+    let mut word = Word::default();
+    let mut s = input;
+    while let Some(c) = s.chars().next() {
+        let rest = &s[c.len_utf8()..];
+        s = match c {
+            c if c.is_whitespace() => break,
+            '\'' => match rest.find('\'') {
+                Some(end) => {
+                    word.parts.push(WordPart::Text(rest[..end].to_string()));
+                    &rest[end + 1..]
+                }
+                None => return ParserResult::from_err(ParserError::ExpectedLiteral("'"), s),
+            },
+            '"' => {
+                word.parts.push(WordPart::Text(String::new()));
+                try_parse!(double_quoted(&mut word, rest)).1
+            }
+            '\\' => match rest.chars().next() {
+                Some('\n') => &rest[1..],
+                Some(c) => {
+                    word.push_char(c);
+                    &rest[c.len_utf8()..]
+                }
+                None => return ParserResult::from_err(ParserError::UnexpectedEndOfFile, s),
+            },
+            '$' => try_parse!(push_var(&mut word, rest)).1,
+            c => {
+                word.push_char(c);
+                rest
+            }
+        };
+    }
+    if s.len() == input.len() {
+        return ParserResult::from_err(ParserError::ExpectedToken("word".into()), input);
+    }
+    ParserResult::from_val(word, s)
+}
+
+/// Split a command line into words, skipping whitespace and `#` comments which start a word
+pub fn split(input: &str) -> ParserResult<'_, Vec<Word>, ParserError> {
+    let mut words = vec![];
+    let mut s = input.trim_start();
+    while !s.is_empty() {
+        if s.starts_with('#') {
+            s = s.find('\n').map_or("", |end| &s[end..]);
+        } else {
+            let (word, rest) = try_parse!(word(s));
+            words.push(word);
+            s = rest;
+        }
+        s = s.trim_start();
+    }
+    ParserResult::from_val(words, s)
+}
@@ -0,0 +1,169 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, try_parse, ParserResult, Spanned};
+
+/// A lexical token of SQL, which covers the syntax most dialects share
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Token<'a> {
+    /// An unquoted keyword or identifier, such as `SELECT` or `users`, which are told apart by the grammar
+    Word(&'a str),
+    /// An identifier in double quotes or backticks, with doubled quotes replaced
+    QuotedIdent(Cow<'a, str>),
+    /// A string literal in single quotes, with doubled quotes replaced
+    String(Cow<'a, str>),
+    /// A numeric literal such as `42`, `3.14`, `.5` or `1e-3`
+    Number(&'a str),
+    /// An operator such as `=`, `<>`, `||` or `::`
+    Operator(&'a str),
+    /// One of `(`, `)`, `[`, `]`, `,`, `;` or `.`
+    Punct(char),
+    /// A placeholder such as `?`, `$1`, `:name` or `@name`
+    Parameter(&'a str),
+    /// A `-- line` or `/* block */` comment, including its delimiters
+    Comment(&'a str),
+}
+
+/// Operators which are made of more than one character, longest first so that they win over their prefixes
+const OPERATORS: &[&str] = &[
+    "->>", "<=>", "<>", "<=", ">=", "!=", "==", "||", "::", "->", "<<", ">>", "+", "-", "*", "/",
+    "%", "=", "<", ">", "!", "|", "&", "^", "~",
+];
+
+/// Parse text in `quote` characters, where a doubled quote stands for one
+fn quoted(quote: char, input: &str) -> ParserResult<'_, Cow<'_, str>, ParserError> {
+    let body = &input[1..];
+    let mut owned: Option<String> = None;
+    let mut start = 0;
+    let mut chars = body.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        if c != quote {
+            continue;
+        }
+        if chars.next_if(|&(_, c)| c == quote).is_none() {
+            let text = match owned {
+                Some(mut owned) => {
+                    owned.push_str(&body[start..i]);
+                    Cow::Owned(owned)
+                }
+                None => Cow::Borrowed(&body[..i]),
+            };
+            return ParserResult::from_val(text, &body[i + 1..]);
+        }
+        // Keep one of the two quotes
+        owned
+            .get_or_insert_with(String::new)
+            .push_str(&body[start..=i]);
+        start = i + 2;
+    }
+    let expected = match quote {
+        '\'' => "'",
+        '"' => "\"",
+        _ => "`",
+    };
+    ParserResult::from_err(ParserError::ExpectedLiteral(expected), input)
+}
+
+/// Parse a block comment, which may be nested as in PostgreSQL and the SQL standard
+fn block_comment(input: &str) -> ParserResult<'_, &str, ParserError> {
+    let mut depth = 0;
+    let mut s = input;
+    loop {
+        if let Some(rest) = s.strip_prefix("/*") {
+            depth += 1;
+            s = rest;
+        } else if let Some(rest) = s.strip_prefix("*/") {
+            depth -= 1;
+            s = rest;
+            if depth == 0 {
+                return ParserResult::from_val(&input[..input.len() - s.len()], s);
+            }
+        } else if let Some(c) = s.chars().next() {
+            s = &s[c.len_utf8()..];
+        } else {
+            return ParserResult::from_err(ParserError::ExpectedLiteral("*/"), input);
+        }
+    }
+}
+
+/// Get the length of a numeric literal at the start of the input, if there is one
+fn number_len(input: &str) -> usize {
+    let digits = |s: &str| s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    let mut len = digits(input);
+    if input[len..].starts_with('.') && (len > 0 || digits(&input[1..]) > 0) {
+        len += 1 + digits(&input[len + 1..]);
+    }
+    if len == 0 {
+        return 0;
+    }
+    let rest = &input[len..];
+    if let Some(exp) = rest.strip_prefix(['e', 'E']) {
+        let sign = usize::from(exp.starts_with(['+', '-']));
+        let exp_digits = digits(&exp[sign..]);
+        if exp_digits > 0 {
+            len += 1 + sign + exp_digits;
+        }
+    }
+    len
+}
+
+/// Parse a single token, which must not start with whitespace
+pub fn token(input: &str) -> ParserResult<'_, Token<'_>, ParserError> {
+    let word_len = |s: &str| {
+        s.find(|c: char| !(c.is_alphanumeric() || c == '_' || c == '$'))
+            .unwrap_or(s.len())
+    };
+    let Some(c) = input.chars().next() else {
+        return ParserResult::from_err(ParserError::UnexpectedEndOfFile, input);
+    };
+    let split = |len: usize| (&input[..len], &input[len..]);
+    match c {
+        '\'' => quoted('\'', input).map(Token::String),
+        '"' | '`' => quoted(c, input).map(Token::QuotedIdent),
+        '-' if input.starts_with("--") => {
+            let line = &input[..input.find('\n').unwrap_or(input.len())];
+            let (comment, rest) = split(line.trim_end_matches('\r').len());
+            ParserResult::from_val(Token::Comment(comment), rest)
+        }
+        '/' if input.starts_with("/*") => block_comment(input).map(Token::Comment),
+        c if c.is_alphabetic() || c == '_' => {
+            let (word, rest) = split(word_len(input));
+            ParserResult::from_val(Token::Word(word), rest)
+        }
+        '0'..='9' | '.' if number_len(input) > 0 => {
+            let (number, rest) = split(number_len(input));
+            ParserResult::from_val(Token::Number(number), rest)
+        }
+        '?' => ParserResult::from_val(Token::Parameter(&input[..1]), &input[1..]),
+        '$' | ':' | '@' if !input.starts_with("::") && word_len(&input[1..]) > 0 => {
+            let (param, rest) = split(1 + word_len(&input[1..]));
+            ParserResult::from_val(Token::Parameter(param), rest)
+        }
+        '(' | ')' | '[' | ']' | ',' | ';' | '.' => {
+            ParserResult::from_val(Token::Punct(c), &input[1..])
+        }
+        _ => match OPERATORS.iter().find(|op| input.starts_with(**op)) {
+            Some(op) => {
+                let (op, rest) = split(op.len());
+                ParserResult::from_val(Token::Operator(op), rest)
+            }
+            None => ParserResult::from_err(ParserError::ExpectedToken("SQL token".into()), input),
+        },
+    }
+}
+
+/// Split SQL into tokens along with their byte ranges, skipping whitespace but keeping comments
+pub fn tokenize(input: &str) -> ParserResult<'_, Vec<Spanned<Token<'_>>>, ParserError> {
+    let mut tokens = vec![];
+    let mut s = input.trim_start();
+    while !s.is_empty() {
+        let start = input.len() - s.len();
+        let (token, rest) = try_parse!(token(s));
+        let end = input.len() - rest.len();
+        tokens.push(Spanned {
+            value: token,
+            range: start..end,
+        });
+        s = rest.trim_start();
+    }
+    ParserResult::from_val(tokens, s)
+}
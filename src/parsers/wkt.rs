@@ -0,0 +1,169 @@
+use crate::{error::ParserError, num::float, try_parse, ParserResult};
+
+/// A position, with an elevation `z` and a measure `m` if the geometry has them
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub x: f64,
+    pub y: f64,
+    pub z: Option<f64>,
+    pub m: Option<f64>,
+}
+
+/// A geometry in well-known text, where an empty geometry has no coordinates
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Option<Coord>),
+    LineString(Vec<Coord>),
+    /// The rings of a polygon, starting with its exterior
+    Polygon(Vec<Vec<Coord>>),
+    MultiPoint(Vec<Coord>),
+    MultiLineString(Vec<Vec<Coord>>),
+    MultiPolygon(Vec<Vec<Vec<Coord>>>),
+    GeometryCollection(Vec<Geometry>),
+}
+
+/// How many numbers each coordinate of a geometry has, which every coordinate must agree on
+struct Layout {
+    dims: Option<usize>,
+    /// Whether the third number is a measure rather than an elevation, as with the `M` modifier
+    measured: bool,
+}
+
+fn ws(input: &str) -> &str {
+    input.trim_start()
+}
+
+/// Parse a coordinate of two to four numbers separated by whitespace
+fn coord<'a>(layout: &mut Layout, input: &'a str) -> ParserResult<'a, Coord, ParserError> {
+    let mut values = vec![];
+    let mut s = ws(input);
+    while values.len() < 4 {
+        let res = float::<f64>(s);
+        if values.len() >= 2 && !res.is_ok() {
+            break;
+        }
+        let (value, rest) = try_parse!(res);
+        values.push(value);
+        s = ws(rest);
+    }
+    match layout.dims {
+        Some(dims) if dims != values.len() => {
+            return ParserResult::from_err(
+                ParserError::ExpectedToken(format!("{dims} numbers per coordinate").into()),
+                ws(input),
+            )
+        }
+        _ => layout.dims = Some(values.len()),
+    }
+    let third = values.get(2).copied();
+    let coord = Coord {
+        x: values[0],
+        y: values[1],
+        z: third.filter(|_| !layout.measured),
+        m: values.get(3).copied().or(third.filter(|_| layout.measured)),
+    };
+    ParserResult::from_val(coord, s)
+}
+
+/// Parse `EMPTY` or a parenthesized, comma-separated list of items
+fn list<'a, T>(
+    mut item: impl FnMut(&'a str) -> ParserResult<'a, T, ParserError>,
+    input: &'a str,
+) -> ParserResult<'a, Vec<T>, ParserError> {
+    let s = ws(input);
+    if let Some(rest) = strip_keyword("EMPTY", s) {
+        return ParserResult::from_val(vec![], rest);
+    }
+    let Some(mut s) = s.strip_prefix('(') else {
+        return ParserResult::from_err(ParserError::ExpectedLiteral("("), s);
+    };
+    let mut items = vec![];
+    loop {
+        let (value, rest) = try_parse!(item(s));
+        items.push(value);
+        let rest = ws(rest);
+        if let Some(rest) = rest.strip_prefix(',') {
+            s = rest;
+        } else if let Some(rest) = rest.strip_prefix(')') {
+            return ParserResult::from_val(items, rest);
+        } else {
+            return ParserResult::from_err(ParserError::ExpectedLiteral(")"), rest);
+        }
+    }
+}
+
+/// Strip a keyword from the start of the input ignoring case, if it isn't followed by more letters
+fn strip_keyword<'a>(keyword: &str, input: &'a str) -> Option<&'a str> {
+    let len = input
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(input.len());
+    input[..len]
+        .eq_ignore_ascii_case(keyword)
+        .then(|| &input[len..])
+}
+
+/// Parse a point of a multipoint, which may or may not be in its own parentheses
+fn multipoint_item<'a>(
+    layout: &mut Layout,
+    input: &'a str,
+) -> ParserResult<'a, Coord, ParserError> {
+    let s = ws(input);
+    let Some(s) = s.strip_prefix('(') else {
+        return coord(layout, s);
+    };
+    let (coord, s) = try_parse!(coord(layout, s));
+    match ws(s).strip_prefix(')') {
+        Some(rest) => ParserResult::from_val(coord, rest),
+        None => ParserResult::from_err(ParserError::ExpectedLiteral(")"), s),
+    }
+}
+
+/// Parse a WKT geometry such as `POINT (30 10)`, `LINESTRING Z (1 2 3, 4 5 6)` or
+/// `MULTIPOLYGON (((30 20, 45 40, 10 40, 30 20)))`, where coordinates without a `Z`, `M` or `ZM` modifier may still
+/// have an elevation and measure
+pub fn geometry(input: &str) -> ParserResult<'_, Geometry, ParserError> {
+    let s = ws(input);
+    let len = s
+        .find(|c: char| !c.is_ascii_alphabetic())
+        .unwrap_or(s.len());
+    let tag = s[..len].to_ascii_uppercase();
+    let mut s = ws(&s[len..]);
+    let mut layout = Layout {
+        dims: None,
+        measured: false,
+    };
+    for (modifier, dims, measured) in [("ZM", 4, false), ("Z", 3, false), ("M", 3, true)] {
+        if let Some(rest) = strip_keyword(modifier, s) {
+            layout = Layout {
+                dims: Some(dims),
+                measured,
+            };
+            s = rest;
+            break;
+        }
+    }
+    let layout = &mut layout;
+    match tag.as_str() {
+        "POINT" => {
+            let (coords, s) = try_parse!(list(|s| coord(layout, s), s));
+            if coords.len() > 1 {
+                return ParserResult::from_err(ParserError::ExpectedLiteral(")"), input);
+            }
+            ParserResult::from_val(Geometry::Point(coords.into_iter().next()), s)
+        }
+        "LINESTRING" => list(|s| coord(layout, s), s).map(Geometry::LineString),
+        "POLYGON" => list(|s| list(|s| coord(layout, s), s), s).map(Geometry::Polygon),
+        "MULTIPOINT" => list(|s| multipoint_item(layout, s), s).map(Geometry::MultiPoint),
+        "MULTILINESTRING" => {
+            list(|s| list(|s| coord(layout, s), s), s).map(Geometry::MultiLineString)
+        }
+        "MULTIPOLYGON" => {
+            list(|s| list(|s| list(|s| coord(layout, s), s), s), s).map(Geometry::MultiPolygon)
+        }
+        "GEOMETRYCOLLECTION" => list(geometry, s).map(Geometry::GeometryCollection),
+        _ => ParserResult::from_err(
+            ParserError::ExpectedToken("geometry type".into()),
+            ws(input),
+        ),
+    }
+}
@@ -0,0 +1,130 @@
+use std::ops::RangeBounds;
+
+use crate::{
+    check_bounds, container::Container, error::ParserError, is_under, lower_bound, no_progress,
+    try_parse, Parser, ParserResult,
+};
+
+/// A parser which is given mutable access to a user context such as a symbol table, interner or indentation stack,
+/// which is threaded through every parser it runs
+pub trait StatefulParser<'a, S, T, E> {
+    /// Parse a value from the input, reading and updating the context
+    fn parse(&self, state: &mut S, input: &'a str) -> ParserResult<'a, T, E>;
+
+    /// Map the output type of this parser using a mapping function
+    fn map<V>(&self, f: impl Fn(T) -> V) -> impl StatefulParser<'a, S, V, E>
+    where
+        Self: Sized,
+    {
+        move |state: &mut S, s| self.parse(state, s).map(&f)
+    }
+
+    /// Map the output type of this parser using a mapping function which can also update the context
+    fn map_state<V>(&self, f: impl Fn(&mut S, T) -> V) -> impl StatefulParser<'a, S, V, E>
+    where
+        Self: Sized,
+    {
+        move |state: &mut S, s| {
+            let (val, s) = try_parse!(self.parse(state, s));
+            ParserResult::from_val(f(state, val), s)
+        }
+    }
+
+    /// Make this parser optional, parsing nothing if it would otherwise fail
+    fn optional(&self) -> impl StatefulParser<'a, S, Option<T>, E>
+    where
+        Self: Sized,
+    {
+        |state: &mut S, s| self.parse(state, s).optional(s)
+    }
+
+    /// Parse this parser and then another, returning both values in a tuple
+    fn and<V>(
+        &self,
+        other: impl StatefulParser<'a, S, V, E>,
+    ) -> impl StatefulParser<'a, S, (T, V), E>
+    where
+        Self: Sized,
+    {
+        move |state: &mut S, s| {
+            let (first, s) = try_parse!(self.parse(state, s));
+            other.parse(state, s).map(|second| (first, second))
+        }
+    }
+
+    /// Try this parser, then another if it fails
+    fn or(&self, other: impl StatefulParser<'a, S, T, E>) -> impl StatefulParser<'a, S, T, E>
+    where
+        Self: Sized,
+    {
+        move |state: &mut S, s| {
+            let res = self.parse(state, s);
+            if res.is_ok() {
+                return res;
+            }
+            other.parse(state, s)
+        }
+    }
+
+    /// Parse this parser a number of times within the given range
+    fn repeating(
+        &self,
+        bounds: impl RangeBounds<usize> + Clone + 'a,
+    ) -> impl StatefulParser<'a, S, Vec<T>, E>
+    where
+        Self: Sized,
+        E: From<ParserError>,
+    {
+        move |state: &mut S, s| {
+            repeating_into(
+                |state: &mut S, s| self.parse(state, s),
+                bounds.clone(),
+                state,
+                s,
+            )
+        }
+    }
+}
+
+impl<'a, S, T, E, F> StatefulParser<'a, S, T, E> for F
+where
+    F: Fn(&mut S, &'a str) -> ParserResult<'a, T, E>,
+{
+    fn parse(&self, state: &mut S, input: &'a str) -> ParserResult<'a, T, E> {
+        self(state, input)
+    }
+}
+
+/// Use a parser which doesn't need the context as a [StatefulParser]
+pub fn stateless<'a, S, T, E>(parser: impl Parser<'a, T, E>) -> impl StatefulParser<'a, S, T, E> {
+    move |_: &mut S, s| parser.parse(s)
+}
+
+/// Parse a stateful parser a number of times within the given range into any [Container]
+///
+/// Panics if no count is within `bounds`, like [repeating_into](crate::repeating_into).
+pub fn repeating_into<'a, S, T, E: From<ParserError>, C: Container<T>>(
+    parser: impl StatefulParser<'a, S, T, E>,
+    bounds: impl RangeBounds<usize> + 'a,
+    state: &mut S,
+    mut source: &'a str,
+) -> ParserResult<'a, C, E> {
+    check_bounds(&bounds);
+    let mut elems = C::with_capacity(lower_bound(bounds.start_bound()));
+    let mut count = 0;
+    while is_under(count, bounds.end_bound()) {
+        match parser.parse(state, source).into_result::<C>() {
+            Ok((v, s)) => {
+                if no_progress(source, s, bounds.end_bound()) {
+                    return ParserResult::from_err(ParserError::NoProgress.into(), source);
+                }
+                elems.add(v);
+                count += 1;
+                source = s;
+            }
+            Err(err) if !bounds.contains(&count) => return err,
+            Err(_) => break,
+        }
+    }
+    ParserResult::from_val(elems, source)
+}
@@ -0,0 +1,64 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, Literal, Needed, ParserResult};
+
+/// Parse a literal string token, which is incomplete if the input ends partway through it
+pub fn literal<'a>(
+    literal: impl Into<Literal>,
+    input: &'a str,
+) -> ParserResult<'a, Literal, ParserError> {
+    let Literal(literal) = literal.into();
+    if let Some(rest) = input.strip_prefix(literal) {
+        ParserResult::from_val(Literal(literal), rest)
+    } else if literal.starts_with(input) {
+        ParserResult::incomplete_needing(Needed::new(literal.len() - input.len()), input)
+    } else {
+        ParserResult::from_err(ParserError::ExpectedLiteral(literal), input)
+    }
+}
+
+/// Parse a single character matching a predicate, which is incomplete at the end of the input
+pub fn matching_char<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    mut filter: impl FnMut(char) -> bool,
+    input: &'a str,
+) -> ParserResult<'a, char, ParserError> {
+    match input.chars().next() {
+        Some(c) if filter(c) => ParserResult::from_val(c, &input[c.len_utf8()..]),
+        Some(_) => ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input),
+        None => ParserResult::incomplete_needing(Needed::new(1), input),
+    }
+}
+
+/// Consume characters as long as they match a predicate, which is incomplete if they run to the end of the input
+/// since more matching characters may follow
+pub fn take_while<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    mut filter: impl FnMut(char) -> bool,
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    match input.find(|c| !filter(c)) {
+        Some(0) => ParserResult::from_err(ParserError::ExpectedToken(token_name.into()), input),
+        Some(len) => {
+            let (parsed, rest) = input.split_at(len);
+            ParserResult::from_val(parsed, rest)
+        }
+        None => ParserResult::incomplete_needing(Needed::new(1), input),
+    }
+}
+
+/// Take exactly `n` characters, which is incomplete until all of them have arrived
+pub fn take(n: usize, input: &str) -> ParserResult<'_, &str, ParserError> {
+    let mut chars = input.char_indices();
+    match chars.nth(n) {
+        Some((end, _)) => ParserResult::from_val(&input[..end], &input[end..]),
+        None => {
+            let count = input.chars().count();
+            if count == n {
+                ParserResult::from_val(input, &input[input.len()..])
+            } else {
+                ParserResult::incomplete_needing(Needed::new(n - count), input)
+            }
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::fmt::Debug;
+
+#[cfg(feature = "proptest")]
+use proptest::{
+    strategy::Strategy,
+    test_runner::{Config, TestCaseError, TestRunner},
+};
+
+use crate::{parser_result::ParserResultType, Parser};
+
+/// Check that a value serializes to text which parses back in its entirety to an equal value, describing the failure
+/// if it doesn't
+fn roundtrip_failure<T: PartialEq + Debug, E: Debug>(
+    parser: &impl for<'a> Parser<'a, T, E>,
+    serializer: &impl Fn(&T) -> String,
+    value: &T,
+) -> Option<String> {
+    let text = serializer(value);
+    let res = parser.parse(&text);
+    let rest = res.source;
+    match res.typ {
+        ParserResultType::Ok(parsed) if !rest.is_empty() => Some(format!(
+            "{value:?} serialized to {text:?}, which parsed to {parsed:?} leaving {rest:?}"
+        )),
+        ParserResultType::Ok(parsed) if parsed != *value => Some(format!(
+            "{value:?} serialized to {text:?}, which parsed to {parsed:?}"
+        )),
+        ParserResultType::Ok(_) => None,
+        ParserResultType::Err(err) => Some(format!(
+            "{value:?} serialized to {text:?}, which failed to parse at {:?} with {err:?}",
+            &text[text.len() - rest.len()..]
+        )),
+        ParserResultType::Incomplete(needed) => Some(format!(
+            "{value:?} serialized to {text:?}, which was incomplete needing {needed:?}"
+        )),
+    }
+}
+
+/// Assert that a value serializes to text which parses back in its entirety to an equal value
+#[track_caller]
+pub fn assert_roundtrip<T: PartialEq + Debug, E: Debug>(
+    parser: impl for<'a> Parser<'a, T, E>,
+    serializer: impl Fn(&T) -> String,
+    value: T,
+) {
+    if let Some(failure) = roundtrip_failure(&parser, &serializer, &value) {
+        panic!("round trip failed: {failure}");
+    }
+}
+
+/// Assert that the parser consumes the entire input, producing the expected value
+#[track_caller]
+pub fn assert_parses<'a, T: PartialEq + Debug, E: Debug>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+    expected: T,
+) {
+    assert_parses_prefix(parser, input, expected, "");
+}
+
+/// Assert that the parser produces the expected value, leaving exactly `rest` of the input unconsumed
+#[track_caller]
+pub fn assert_parses_prefix<'a, T: PartialEq + Debug, E: Debug>(
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+    expected: T,
+    rest: &str,
+) {
+    let res = parser.parse(input);
+    let source = res.source;
+    match res.typ {
+        ParserResultType::Ok(value) => {
+            assert_eq!(value, expected, "unexpected value parsing {input:?}");
+            assert_eq!(source, rest, "unexpected input left parsing {input:?}");
+        }
+        ParserResultType::Err(err) => {
+            panic!("parsing {input:?} failed at {source:?} with {err:?}, expected {expected:?}")
+        }
+        ParserResultType::Incomplete(needed) => {
+            panic!("parsing {input:?} was incomplete needing {needed:?}, expected {expected:?}")
+        }
+    }
+}
+
+/// Assert that the parser fails on the input, returning the error
+#[track_caller]
+pub fn assert_fails<'a, T: Debug, E>(parser: impl Parser<'a, T, E>, input: &'a str) -> E {
+    let res = parser.parse(input);
+    let source = res.source;
+    match res.typ {
+        ParserResultType::Err(err) => err,
+        ParserResultType::Ok(value) => {
+            panic!(
+                "parsing {input:?} succeeded with {value:?} leaving {source:?}, expected an error"
+            )
+        }
+        ParserResultType::Incomplete(needed) => {
+            panic!("parsing {input:?} was incomplete needing {needed:?}, expected an error")
+        }
+    }
+}
+
+/// Assert that every value from a proptest strategy round trips like with [assert_roundtrip], panicking with the
+/// simplest failing value that was found
+#[cfg(feature = "proptest")]
+#[track_caller]
+pub fn assert_roundtrips<T: PartialEq + Debug, E: Debug>(
+    parser: impl for<'a> Parser<'a, T, E>,
+    serializer: impl Fn(&T) -> String,
+    strategy: impl Strategy<Value = T>,
+) {
+    let config = Config {
+        failure_persistence: None,
+        ..Config::default()
+    };
+    let result = TestRunner::new(config).run(&strategy, |value| {
+        match roundtrip_failure(&parser, &serializer, &value) {
+            Some(failure) => Err(TestCaseError::fail(failure)),
+            None => Ok(()),
+        }
+    });
+    if let Err(err) = result {
+        panic!("round trip failed: {err}");
+    }
+}
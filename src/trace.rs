@@ -0,0 +1,273 @@
+use std::{
+    cell::{Cell, RefCell},
+    collections::{HashMap, HashSet},
+    fmt::{self, Display, Formatter, Write},
+};
+
+use crate::{Parser, ParserResult, ParserResultType};
+
+thread_local! {
+    /// The number of traced parsers currently running on this thread
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+    /// The address of the input given to the outermost traced parser, which offsets are measured from
+    static ORIGIN: Cell<usize> = const { Cell::new(0) };
+    /// The trace being recorded by an active [capture]
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+    /// The counters being collected by an active [profile]
+    static PROFILE: RefCell<Option<Profiling>> = const { RefCell::new(None) };
+}
+
+/// A trace being recorded, with the rules which are still running on a stack
+#[derive(Default)]
+struct Capture {
+    roots: Vec<TraceNode>,
+    running: Vec<TraceNode>,
+}
+
+/// Counters being collected, along with the offsets each rule has already been run from
+#[derive(Default)]
+struct Profiling {
+    profile: Profile,
+    visited: HashSet<(&'static str, usize)>,
+}
+
+/// How an attempt to run a traced rule ended
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    Matched,
+    Failed,
+    Incomplete,
+}
+
+impl Outcome {
+    fn name(self) -> &'static str {
+        match self {
+            Outcome::Matched => "matched",
+            Outcome::Failed => "failed",
+            Outcome::Incomplete => "incomplete",
+        }
+    }
+}
+
+/// A single attempt to run a traced rule, along with the rules it attempted in turn
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TraceNode {
+    pub name: &'static str,
+    /// The byte offset the rule started at
+    pub start: usize,
+    /// The byte offset the rule stopped at, which is where the error was reported if it failed
+    pub end: usize,
+    pub outcome: Outcome,
+    pub children: Vec<TraceNode>,
+}
+
+impl TraceNode {
+    fn write_indented(&self, f: &mut Formatter<'_>, depth: usize) -> fmt::Result {
+        let indent = depth * 2;
+        writeln!(
+            f,
+            "{:indent$}{} {}..{} {}",
+            "",
+            self.name,
+            self.start,
+            self.end,
+            self.outcome.name()
+        )?;
+        self.children
+            .iter()
+            .try_for_each(|child| child.write_indented(f, depth + 1))
+    }
+
+    fn write_json(&self, out: &mut String) {
+        out.push_str("{\"name\":\"");
+        for c in self.name.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                c if c.is_control() => {
+                    let _ = write!(out, "\\u{:04x}", c as u32);
+                }
+                c => out.push(c),
+            }
+        }
+        let _ = write!(
+            out,
+            "\",\"start\":{},\"end\":{},\"outcome\":\"{}\",\"children\":",
+            self.start,
+            self.end,
+            self.outcome.name()
+        );
+        write_json_list(&self.children, out);
+        out.push('}');
+    }
+}
+
+fn write_json_list(nodes: &[TraceNode], out: &mut String) {
+    out.push('[');
+    for (i, node) in nodes.iter().enumerate() {
+        if i > 0 {
+            out.push(',');
+        }
+        node.write_json(out);
+    }
+    out.push(']');
+}
+
+/// The tree of traced rules attempted during a [capture]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Trace {
+    pub roots: Vec<TraceNode>,
+}
+
+impl Trace {
+    /// Serialize the trace as a JSON array of nodes, each with a name, start, end, outcome and children
+    pub fn to_json(&self) -> String {
+        let mut out = String::new();
+        write_json_list(&self.roots, &mut out);
+        out
+    }
+}
+
+/// Writes one line per attempted rule, indented by nesting depth
+impl Display for Trace {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        self.roots
+            .iter()
+            .try_for_each(|node| node.write_indented(f, 0))
+    }
+}
+
+/// Run a function, recording every traced rule it attempts on this thread into a [Trace]
+pub fn capture<R>(f: impl FnOnce() -> R) -> (R, Trace) {
+    let outer = CAPTURE.with(|c| c.replace(Some(Capture::default())));
+    let res = f();
+    let capture = CAPTURE.with(|c| c.replace(outer)).unwrap_or_default();
+    (
+        res,
+        Trace {
+            roots: capture.roots,
+        },
+    )
+}
+
+/// Counters for a single traced rule collected during a [profile]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct RuleStats {
+    /// The number of times the rule was run
+    pub invocations: usize,
+    /// The number of times the rule failed, causing its caller to backtrack
+    pub backtracks: usize,
+    /// The number of bytes the rule scanned when run from an offset it had already been run from before
+    pub rescanned: usize,
+}
+
+/// Counters for each traced rule run during a [profile]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct Profile {
+    pub rules: HashMap<&'static str, RuleStats>,
+}
+
+/// Writes one line per rule, with the most rescanned bytes first
+impl Display for Profile {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        let mut rules: Vec<_> = self.rules.iter().collect();
+        rules.sort_by(|(a_name, a), (b_name, b)| {
+            (b.rescanned, b.backtracks, a_name).cmp(&(a.rescanned, a.backtracks, b_name))
+        });
+        for (name, stats) in rules {
+            writeln!(
+                f,
+                "{name}: {} invocations, {} backtracks, {} bytes rescanned",
+                stats.invocations, stats.backtracks, stats.rescanned
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Run a function, counting invocations, backtracks and rescanned bytes for every traced rule it runs on this thread
+pub fn profile<R>(f: impl FnOnce() -> R) -> (R, Profile) {
+    let outer = PROFILE.with(|p| p.replace(Some(Profiling::default())));
+    let res = f();
+    let profiling = PROFILE.with(|p| p.replace(outer)).unwrap_or_default();
+    (res, profiling.profile)
+}
+
+/// Enter a traced rule, returning its nesting depth and the byte offset of its input
+fn enter(name: &'static str, input: &str) -> (usize, usize) {
+    let depth = DEPTH.with(|d| d.replace(d.get() + 1));
+    if depth == 0 {
+        ORIGIN.with(|o| o.set(input.as_ptr() as usize));
+    }
+    let offset = (input.as_ptr() as usize).saturating_sub(ORIGIN.with(Cell::get));
+    CAPTURE.with(|c| {
+        if let Some(capture) = c.borrow_mut().as_mut() {
+            capture.running.push(TraceNode {
+                name,
+                start: offset,
+                end: offset,
+                outcome: Outcome::Matched,
+                children: vec![],
+            });
+        }
+    });
+    (depth, offset)
+}
+
+/// Leave a traced rule, restoring the nesting depth of its caller and recording how the rule ended
+fn exit(name: &'static str, depth: usize, start: usize, end: usize, outcome: Outcome) {
+    DEPTH.with(|d| d.set(depth));
+    PROFILE.with(|p| {
+        if let Some(profiling) = p.borrow_mut().as_mut() {
+            let stats = profiling.profile.rules.entry(name).or_default();
+            stats.invocations += 1;
+            if outcome != Outcome::Matched {
+                stats.backtracks += 1;
+            }
+            if !profiling.visited.insert((name, start)) {
+                stats.rescanned += end - start;
+            }
+        }
+    });
+    CAPTURE.with(|c| {
+        if let Some(capture) = c.borrow_mut().as_mut() {
+            let Some(mut node) = capture.running.pop() else {
+                return;
+            };
+            node.end = end;
+            node.outcome = outcome;
+            match capture.running.last_mut() {
+                Some(parent) => parent.children.push(node),
+                None => capture.roots.push(node),
+            }
+        }
+    });
+}
+
+/// Run a parser under a rule name, logging its nesting depth, starting offset and outcome at the trace level with the
+/// `log` feature, and recording it if a [capture] is active
+pub fn traced<'a, T, E>(
+    name: &'static str,
+    parser: impl Parser<'a, T, E>,
+    input: &'a str,
+) -> ParserResult<'a, T, E> {
+    let (depth, offset) = enter(name, input);
+    #[cfg(feature = "log")]
+    log::trace!("{:indent$}{name} at {offset}", "", indent = depth * 2);
+    let res = parser.parse(input);
+    let end = offset + input.len().saturating_sub(res.source.len());
+    let outcome = match res.typ {
+        ParserResultType::Ok(_) => Outcome::Matched,
+        ParserResultType::Err(_) => Outcome::Failed,
+        ParserResultType::Incomplete(_) => Outcome::Incomplete,
+    };
+    exit(name, depth, offset, end, outcome);
+    #[cfg(feature = "log")]
+    log::trace!(
+        "{:indent$}{name} {} {offset}..{end}",
+        "",
+        outcome.name(),
+        indent = depth * 2
+    );
+    res
+}
@@ -0,0 +1,70 @@
+use std::borrow::Cow;
+
+use crate::{error::ParserError, Parser, ParserResult};
+
+/// A node of a [Trie], which ends a literal if `literal` is set
+#[derive(Debug, Clone, Default)]
+struct Node {
+    literal: Option<&'static str>,
+    /// The byte leading to each child node, sorted by byte
+    children: Vec<(u8, usize)>,
+}
+
+/// A parser matching the longest of a set of literals, such as `<`, `<=`, `<<` and `<<=`, regardless of the order
+/// they were given in, and returning which one matched
+#[derive(Debug, Clone)]
+pub struct Trie {
+    name: Cow<'static, str>,
+    nodes: Vec<Node>,
+}
+
+impl Trie {
+    /// Create a trie of literals, which reports an expected token error naming what it parses if none of them match
+    pub fn new(name: impl Into<Cow<'static, str>>, literals: &[&'static str]) -> Self {
+        let mut trie = Self {
+            name: name.into(),
+            nodes: vec![Node::default()],
+        };
+        for literal in literals {
+            trie.insert(literal);
+        }
+        trie
+    }
+
+    fn insert(&mut self, literal: &'static str) {
+        let mut node = 0;
+        for byte in literal.bytes() {
+            node = match self.nodes[node]
+                .children
+                .binary_search_by_key(&byte, |(b, _)| *b)
+            {
+                Ok(i) => self.nodes[node].children[i].1,
+                Err(i) => {
+                    let child = self.nodes.len();
+                    self.nodes.push(Node::default());
+                    self.nodes[node].children.insert(i, (byte, child));
+                    child
+                }
+            };
+        }
+        self.nodes[node].literal = Some(literal);
+    }
+}
+
+impl<'a> Parser<'a, &'static str, ParserError> for Trie {
+    fn parse(&self, input: &'a str) -> ParserResult<'a, &'static str, ParserError> {
+        let mut node = &self.nodes[0];
+        let mut longest = node.literal;
+        for byte in input.bytes() {
+            let Ok(i) = node.children.binary_search_by_key(&byte, |(b, _)| *b) else {
+                break;
+            };
+            node = &self.nodes[node.children[i].1];
+            longest = node.literal.or(longest);
+        }
+        match longest {
+            Some(literal) => ParserResult::from_val(literal, &input[literal.len()..]),
+            None => ParserResult::from_err(ParserError::ExpectedToken(self.name.clone()), input),
+        }
+    }
+}
@@ -0,0 +1,87 @@
+use std::marker::PhantomData;
+
+use crate::{skip_many, try_parse, Parser, ParserResult};
+
+/// A grammar mode where a trivia parser, such as whitespace or comments, is registered once and skipped automatically around sequenced parsers
+pub struct Trivia<P, V, E> {
+    trivia: P,
+    phantom: PhantomData<fn() -> (V, E)>,
+}
+
+/// A sequence of parsers which skips trivia before each element and after the last
+pub struct Seq<'t, P, V, E, S> {
+    trivia: &'t Trivia<P, V, E>,
+    parsers: S,
+}
+
+impl<P, V, E> Trivia<P, V, E> {
+    /// Register a trivia parser, which is repeated until it fails or stops consuming input whenever trivia is skipped
+    pub fn new(trivia: P) -> Self {
+        Self {
+            trivia,
+            phantom: PhantomData,
+        }
+    }
+
+    /// Wrap a tuple of parsers so trivia is skipped between its elements
+    pub fn seq<S>(&self, parsers: S) -> Seq<'_, P, V, E, S> {
+        Seq {
+            trivia: self,
+            parsers,
+        }
+    }
+}
+
+impl<'a, P: Parser<'a, V, E>, V, E> Trivia<P, V, E> {
+    /// Skip any trivia at the start of the input
+    pub fn skip(&self, input: &'a str) -> &'a str {
+        skip_many(|s| self.trivia.parse(s), input).source
+    }
+
+    /// Wrap a single parser so trivia is skipped before and after it
+    pub fn token<'t, T, E2>(
+        &'t self,
+        parser: impl Parser<'a, T, E2> + 't,
+    ) -> impl Parser<'a, T, E2> + 't {
+        move |s| {
+            let (v, s) = try_parse!(parser.parse(self.skip(s)));
+            ParserResult::from_val(v, self.skip(s))
+        }
+    }
+}
+
+macro_rules! impl_trivia_seq {
+    ($($t:ident),+ : $($p:ident),+) => {
+        impl<'a, 't, P, V, E, $($t),+, $($p),+, Error> Parser<'a, ($($t),+), Error> for Seq<'t, P, V, E, ($($p),+)>
+        where
+            P: Parser<'a, V, E>,
+            $($p: Parser<'a, $t, Error>),+ {
+            fn parse(&self, mut input: &'a str) -> ParserResult<'a, ($($t),+), Error> {
+                #[allow(non_snake_case)]
+                let ($($p),+) = &self.parsers;
+                let tuple = (
+                    $(
+                        {
+                            let (val, s) = try_parse!($p.parse(self.trivia.skip(input)));
+                            input = s;
+                            val
+                        }
+                    ),+
+                );
+                ParserResult::from_val(tuple, self.trivia.skip(input))
+            }
+        }
+    };
+}
+
+impl_trivia_seq!(T1, T2: P1, P2);
+impl_trivia_seq!(T1, T2, T3: P1, P2, P3);
+impl_trivia_seq!(T1, T2, T3, T4: P1, P2, P3, P4);
+impl_trivia_seq!(T1, T2, T3, T4, T5: P1, P2, P3, P4, P5);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6: P1, P2, P3, P4, P5, P6);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7: P1, P2, P3, P4, P5, P6, P7);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7, T8: P1, P2, P3, P4, P5, P6, P7, P8);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7, T8, T9: P1, P2, P3, P4, P5, P6, P7, P8, P9);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10: P1, P2, P3, P4, P5, P6, P7, P8, P9, P10);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11: P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11);
+impl_trivia_seq!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12: P1, P2, P3, P4, P5, P6, P7, P8, P9, P10, P11, P12);
@@ -0,0 +1,102 @@
+use std::borrow::Cow;
+use unicode_general_category::get_general_category;
+
+pub use unicode_general_category::GeneralCategory;
+
+use crate::{error::ParserError, matching_char, take_while, ParserResult};
+
+/// Parse a single character in a Unicode general category, reporting the category's abbreviation such as `Lu` if it
+/// isn't
+pub fn category(category: GeneralCategory, input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char(
+        category.abbreviation(),
+        |c| get_general_category(c) == category,
+        input,
+    )
+}
+
+/// Parse a single character in any of the given Unicode general categories
+pub fn in_categories<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    categories: &[GeneralCategory],
+    input: &'a str,
+) -> ParserResult<'a, char, ParserError> {
+    matching_char(
+        token_name,
+        |c| categories.contains(&get_general_category(c)),
+        input,
+    )
+}
+
+/// Consume characters as long as they are in any of the given Unicode general categories
+pub fn take_in_categories<'a>(
+    token_name: impl Into<Cow<'static, str>>,
+    categories: &[GeneralCategory],
+    input: &'a str,
+) -> ParserResult<'a, &'a str, ParserError> {
+    take_while(
+        token_name,
+        |c| categories.contains(&get_general_category(c)),
+        input,
+    )
+}
+
+/// Whether a character is in a major Unicode general category, given by its one letter abbreviation such as `L`
+fn in_major(major: char, c: char) -> bool {
+    get_general_category(c).abbreviation().starts_with(major)
+}
+
+/// Parse a single character in any letter category (`L`)
+pub fn letter(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("letter", |c| in_major('L', c), input)
+}
+
+/// Parse a single character in any mark category (`M`)
+pub fn mark(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("mark", |c| in_major('M', c), input)
+}
+
+/// Parse a single character in any number category (`N`)
+pub fn number(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("number", |c| in_major('N', c), input)
+}
+
+/// Parse a single character in any punctuation category (`P`)
+pub fn punctuation(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("punctuation", |c| in_major('P', c), input)
+}
+
+/// Parse a single character in any symbol category (`S`)
+pub fn symbol(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("symbol", |c| in_major('S', c), input)
+}
+
+/// Parse a single character with the Unicode `Alphabetic` property
+pub fn alphabetic(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("alphabetic character", char::is_alphabetic, input)
+}
+
+/// Parse a single Unicode numeric character
+pub fn numeric(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("numeric character", char::is_numeric, input)
+}
+
+/// Parse a single Unicode alphabetic or numeric character
+pub fn alphanumeric(input: &str) -> ParserResult<'_, char, ParserError> {
+    matching_char("alphanumeric character", char::is_alphanumeric, input)
+}
+
+/// Consume at least one Unicode alphabetic character
+pub fn alphabetic1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("alphabetic characters", char::is_alphabetic, input)
+}
+
+/// Consume at least one Unicode numeric character
+pub fn numeric1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("numeric characters", char::is_numeric, input)
+}
+
+/// Consume at least one Unicode alphabetic or numeric character
+pub fn alphanumeric1(input: &str) -> ParserResult<'_, &str, ParserError> {
+    take_while("alphanumeric characters", char::is_alphanumeric, input)
+}